@@ -0,0 +1,123 @@
+use coin_cbc;
+use super::{Backend, CutFamily, CutLevel, Model, MipSolution, Params, Sense, VarType};
+
+/// CBC's parameter for each cut family it has.
+const CUT_PARAMS: &'static [(CutFamily, &'static str)] =
+    &[(CutFamily::Clique, "cliqueCuts"),
+      (CutFamily::Cover, "knapsackCuts"),
+      (CutFamily::FlowCover, "flowCoverCuts"),
+      (CutFamily::Gomory, "gomoryCuts"),
+      (CutFamily::LiftProject, "liftAndProjectCuts"),
+      (CutFamily::Mir, "mixedIntegerRoundingCuts"),
+      (CutFamily::ZeroHalf, "zeroHalfCuts")];
+
+/// CBC's value for a cut level: moderate cuts are only generated at the root.
+fn cut_value(level: CutLevel) -> &'static str {
+    match level {
+        CutLevel::Off => "off",
+        CutLevel::Auto => "on",
+        CutLevel::Moderate => "root",
+        CutLevel::Aggressive => "forceOn",
+    }
+}
+
+/// Solves models in-process with COIN-OR CBC. Needs the `cbc` feature. CBC has no memory limit,
+/// node files or MIP emphasis, nor separate GUB cover and implied bound cuts.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Cbc;
+
+impl Backend for Cbc {
+    fn name(&self) -> &'static str {
+        "cbc"
+    }
+
+    fn solve(&self, model: &Model, params: &Params) -> Result<MipSolution, String> {
+        let mut m = coin_cbc::Model::default();
+        m.set_obj_sense(if model.maximize {
+            coin_cbc::Sense::Maximize
+        } else {
+            coin_cbc::Sense::Minimize
+        });
+        if let Some(threads) = params.threads {
+            m.set_parameter("threads", &threads.to_string());
+        }
+        if let Some(limit) = params.time_limit {
+            m.set_parameter("seconds", &limit.to_string());
+        }
+        if let Some(gap) = params.gap {
+            m.set_parameter("ratioGap", &gap.to_string());
+        }
+        if let Some(gap) = params.abs_gap {
+            m.set_parameter("allowableGap", &gap.to_string());
+        }
+        if let Some(seed) = params.seed_i32() {
+            let seed = seed.to_string();
+            m.set_parameter("randomSeed", &seed);
+            m.set_parameter("randomCbcSeed", &seed);
+        }
+        if let Some(limit) = params.solution_limit {
+            m.set_parameter("maxSolutions", &limit.to_string());
+        }
+        if let Some(cuts) = params.cuts {
+            m.set_parameter("cuts", cut_value(cuts));
+        }
+        for &(family, level) in &params.cut_families {
+            if let Some(&(_, name)) = CUT_PARAMS.iter().find(|&&(f, _)| f == family) {
+                m.set_parameter(name, cut_value(level));
+            }
+        }
+
+        let cols = model.vars
+            .iter()
+            .map(|var| {
+                let col = m.add_col();
+                match var.ty {
+                    VarType::Binary => m.set_binary(col),
+                    VarType::Integer => m.set_integer(col),
+                    VarType::Continuous => m.set_continuous(col),
+                }
+                m.set_col_lower(col, var.lb);
+                m.set_col_upper(col, var.ub);
+                m.set_obj_coeff(col, var.obj);
+                col
+            })
+            .collect::<Vec<_>>();
+        if let Some(ref start) = model.start {
+            for (&col, &value) in cols.iter().zip(start) {
+                m.set_col_initial_solution(col, value);
+            }
+        }
+
+        for row in &model.rows {
+            let r = m.add_row();
+            match row.sense {
+                Sense::LessEq => m.set_row_upper(r, row.rhs),
+                Sense::Eq => m.set_row_equal(r, row.rhs),
+                Sense::GreaterEq => m.set_row_lower(r, row.rhs),
+            }
+            for &(j, c) in &row.terms {
+                m.set_weight(r, cols[j], c);
+            }
+        }
+
+        let sol = m.solve();
+        if sol.raw().is_proven_infeasible() {
+            return Err("cbc: model is infeasible".to_string());
+        }
+        let optimal = sol.raw().is_proven_optimal();
+        // CBC reports an objective of 1e50 when it has no solution
+        let limited = sol.raw().is_seconds_limit_reached() || sol.raw().is_solution_limit_reached();
+        let incumbent = limited && sol.raw().obj_value().abs() < 1e49;
+        if !optimal && !incumbent {
+            return Err(format!("cbc stopped without a solution (status {})", sol.raw().status()));
+        }
+
+        Ok(MipSolution {
+            objective: sol.raw().obj_value(),
+            values: cols.iter().map(|&col| sol.col(col)).collect(),
+            optimal: optimal,
+            bound: Some(sol.raw().best_possible_value()),
+            sets: None,
+        })
+    }
+}
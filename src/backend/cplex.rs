@@ -0,0 +1,171 @@
+use std::time::Instant;
+use rplex::*;
+use cplex::{cplex_env, cplex_env_with};
+use super::{Backend, CutFamily, CutLevel, Emphasis, Model, MipSolution, NodeFile, Params, Sense,
+            VarType, seconds_since};
+
+/// CPLEX's parameter for each cut family.
+const CUT_PARAMS: &'static [(CutFamily, &'static str)] =
+    &[(CutFamily::Clique, "CPXPARAM_MIP_Cuts_Cliques"),
+      (CutFamily::Cover, "CPXPARAM_MIP_Cuts_Covers"),
+      (CutFamily::FlowCover, "CPXPARAM_MIP_Cuts_FlowCovers"),
+      (CutFamily::Gomory, "CPXPARAM_MIP_Cuts_Gomory"),
+      (CutFamily::GubCover, "CPXPARAM_MIP_Cuts_GUBCovers"),
+      (CutFamily::Implied, "CPXPARAM_MIP_Cuts_Implied"),
+      (CutFamily::LiftProject, "CPXPARAM_MIP_Cuts_LiftProj"),
+      (CutFamily::Mir, "CPXPARAM_MIP_Cuts_MIRCut"),
+      (CutFamily::ZeroHalf, "CPXPARAM_MIP_Cuts_ZeroHalfCut")];
+
+/// Solves models in-process with CPLEX through rplex. Needs the `cplex` feature.
+///
+/// rplex returns the incumbent when CPLEX stops on its time limit, but reports neither the
+/// status nor the best bound, so a solve that used up the whole time limit, or had a solution
+/// limit, is taken as not proven optimal and no bound is reported. The memory limit is CPLEX's
+/// working memory, beyond which it uses node files. rplex has no way to set branching
+/// priorities, so models that have any are rejected.
+///
+/// Any CPLEX parameter can be set by its full `CPXPARAM_*` name, e.g.
+/// `CPXPARAM_Emphasis_MIP`. These are applied after, and so override, the common `Params`.
+#[derive(Debug, Clone, Default)]
+pub struct Cplex {
+    pub params: Vec<(String, String)>,
+}
+
+impl Backend for Cplex {
+    fn name(&self) -> &'static str {
+        "cplex"
+    }
+
+    fn set_param(&mut self, name: &str, value: &str) -> Result<(), String> {
+        if !name.starts_with("CPXPARAM_") {
+            return Err(format!("cplex: unknown parameter {}", name));
+        }
+        self.params.push((name.to_string(), value.to_string()));
+        Ok(())
+    }
+
+    /// rplex reports a missing library or license when the environment is created, so
+    /// creating one is the check.
+    fn available(&self) -> Result<(), String> {
+        cplex_env(None).map(|_| ()).map_err(|e| format!("cplex: {}", e))
+    }
+
+    fn solve(&self, model: &Model, params: &Params) -> Result<MipSolution, String> {
+        if model.vars.iter().any(|var| var.priority > 0) {
+            return Err("cplex: rplex cannot set branching priorities; use the gurobi backend \
+                        for them"
+                .to_string());
+        }
+        let mut cplex_params = Vec::new();
+        if let Some(limit) = params.time_limit {
+            cplex_params.push(("CPXPARAM_TimeLimit".to_string(), limit.to_string()));
+        }
+        if let Some(gap) = params.gap {
+            cplex_params.push(("CPXPARAM_MIP_Tolerances_MIPGap".to_string(), gap.to_string()));
+        }
+        if let Some(gap) = params.abs_gap {
+            cplex_params.push(("CPXPARAM_MIP_Tolerances_AbsMIPGap".to_string(), gap.to_string()));
+        }
+        if let Some(seed) = params.seed_i32() {
+            cplex_params.push(("CPXPARAM_RandomSeed".to_string(), seed.to_string()));
+        }
+        if let Some(limit) = params.mem_limit {
+            cplex_params.push(("CPXPARAM_WorkMem".to_string(), limit.to_string()));
+        }
+        if let Some(node_file) = params.node_file {
+            let strategy = match node_file {
+                NodeFile::Off => 0,
+                NodeFile::Memory => 1,
+                NodeFile::Disk => 2,
+                NodeFile::DiskCompressed => 3,
+            };
+            cplex_params.push(("CPXPARAM_MIP_Strategy_File".to_string(), strategy.to_string()));
+        }
+        if let Some(ref dir) = params.node_dir {
+            cplex_params.push(("CPXPARAM_WorkDir".to_string(), dir.clone()));
+        }
+        if let Some(emphasis) = params.emphasis {
+            let value = match emphasis {
+                Emphasis::Balanced => 0,
+                Emphasis::Feasibility => 1,
+                Emphasis::Optimality => 2,
+                Emphasis::Bound => 3,
+            };
+            cplex_params.push(("CPXPARAM_Emphasis_MIP".to_string(), value.to_string()));
+        }
+        if let Some(limit) = params.solution_limit {
+            cplex_params.push(("CPXPARAM_MIP_Limits_Solutions".to_string(), limit.to_string()));
+        }
+        // CPLEX has no overall cut level, so `cuts` applies to each family
+        for &(family, name) in CUT_PARAMS {
+            if let Some(level) = params.cut_level(family) {
+                let value = match level {
+                    CutLevel::Off => -1,
+                    CutLevel::Auto => 0,
+                    CutLevel::Moderate => 1,
+                    CutLevel::Aggressive => 2,
+                };
+                cplex_params.push((name.to_string(), value.to_string()));
+            }
+        }
+        cplex_params.extend(self.params.iter().cloned());
+        let env = cplex_env_with(params.threads, &cplex_params)?;
+        let mut prob = Problem::new(&env, model.name.as_str())?;
+        prob.set_objective_type(if model.maximize {
+                ObjectiveType::Maximize
+            } else {
+                ObjectiveType::Minimize
+            })?;
+
+        let mut vars = Vec::with_capacity(model.vars.len());
+        for var in &model.vars {
+            let ty = match var.ty {
+                VarType::Binary => VariableType::Binary,
+                VarType::Integer => VariableType::Integer,
+                VarType::Continuous => VariableType::Continuous,
+            };
+            let name = var.name.as_str();
+            vars.push(prob.add_variable(Variable::new(ty, var.obj, var.lb, var.ub, name))?);
+        }
+
+        for row in &model.rows {
+            let ty = match row.sense {
+                Sense::LessEq => ConstraintType::LessThanEq,
+                Sense::Eq => ConstraintType::Eq,
+                Sense::GreaterEq => ConstraintType::GreaterThanEq,
+            };
+            let mut con = Constraint::new(ty, row.rhs, row.name.as_str());
+            for &(j, c) in &row.terms {
+                con.add_wvar(WeightedVariable::new_idx(vars[j], c));
+            }
+            prob.add_constraint(con)?;
+        }
+        if let Some(ref start) = model.start {
+            prob.add_initial_soln(&vars, start)?;
+        }
+
+        let start = Instant::now();
+        let sol = prob.solve()?;
+        let elapsed = seconds_since(start);
+        let values = vars.iter()
+            .map(|&var| match sol.variables[var] {
+                VariableValue::Continuous(v) => v,
+                VariableValue::Binary(b) => if b { 1.0 } else { 0.0 },
+                VariableValue::Integer(v) => v as f64,
+            })
+            .collect();
+
+        let timed_out = params.time_limit.is_some_and(|limit| elapsed >= limit);
+        if timed_out {
+            eprintln!("warning: cplex stopped on its time limit, but rplex cannot read its best \
+                       bound, so no bound or gap is reported");
+        }
+        Ok(MipSolution {
+            objective: sol.objective,
+            values: values,
+            optimal: params.solution_limit.is_none() && !timed_out,
+            bound: None,
+            sets: None,
+        })
+    }
+}
@@ -0,0 +1,118 @@
+use cp_sat::builder::{CpModelBuilder, LinearExpr};
+use cp_sat::proto::{CpSolverStatus, SatParameters};
+use super::{Backend, Model, MipSolution, Params, Sense, VarType};
+
+/// Solves models with OR-Tools' CP-SAT. Needs the `cpsat` feature. CP-SAT only handles
+/// integral data, so every variable must be binary and every coefficient an integer, which
+/// holds for the coverage model. The memory limit, node files, emphasis and cuts, which are
+/// MIP notions, are ignored.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CpSat {
+    /// Number of parallel search workers. Defaults to `threads`.
+    pub workers: Option<usize>,
+}
+
+/// Converts `c` to an integer, failing if it is not one.
+fn integral(c: f64) -> Result<i64, String> {
+    if c.fract() == 0.0 {
+        Ok(c as i64)
+    } else {
+        Err(format!("cpsat: coefficient {} is not integral", c))
+    }
+}
+
+impl Backend for CpSat {
+    fn name(&self) -> &'static str {
+        "cpsat"
+    }
+
+    fn set_param(&mut self, name: &str, value: &str) -> Result<(), String> {
+        match name {
+            "workers" => {
+                self.workers = Some(value.parse().map_err(|_| format!("bad workers: {}", value))?)
+            }
+            _ => return Err(format!("cpsat: unknown parameter {}", name)),
+        }
+        Ok(())
+    }
+
+    fn solve(&self, model: &Model, params: &Params) -> Result<MipSolution, String> {
+        let mut m = CpModelBuilder::default();
+        let mut vars = Vec::with_capacity(model.vars.len());
+        for var in &model.vars {
+            if var.ty != VarType::Binary {
+                return Err(format!("cpsat: variable {} is not binary", var.name));
+            }
+            let x = m.new_bool_var_with_name(var.name.as_str());
+            if var.lb > 0.0 {
+                m.add_eq(x, 1);
+            } else if var.ub < 1.0 {
+                m.add_eq(x, 0);
+            }
+            vars.push(x);
+        }
+        if let Some(ref start) = model.start {
+            for (&x, &value) in vars.iter().zip(start) {
+                m.add_hint(x, value > 0.5);
+            }
+        }
+
+        for row in &model.rows {
+            let terms = row.terms
+                .iter()
+                .map(|&(j, c)| integral(c).map(|c| (c, vars[j])))
+                .collect::<Result<Vec<_>, _>>()?;
+            let lhs = terms.into_iter().collect::<LinearExpr>();
+            let rhs = integral(row.rhs)?;
+            match row.sense {
+                Sense::LessEq => m.add_le(lhs, rhs),
+                Sense::Eq => m.add_eq(lhs, rhs),
+                Sense::GreaterEq => m.add_ge(lhs, rhs),
+            };
+        }
+
+        let objective = model.vars
+            .iter()
+            .zip(&vars)
+            .filter(|&(var, _)| var.obj != 0.0)
+            .map(|(var, &x)| integral(var.obj).map(|c| (c, x)))
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .collect::<LinearExpr>();
+        if model.maximize {
+            m.maximize(objective);
+        } else {
+            m.minimize(objective);
+        }
+
+        let mut sat_params = SatParameters {
+            num_search_workers: self.workers.or(params.threads).map(|w| w as i32),
+            max_time_in_seconds: params.time_limit,
+            relative_gap_limit: params.gap,
+            absolute_gap_limit: params.abs_gap,
+            random_seed: params.seed_i32(),
+            ..Default::default()
+        };
+        // CP-SAT can only stop after its first solution, not after several
+        if params.solution_limit == Some(1) {
+            sat_params.stop_after_first_solution = Some(true);
+        }
+
+        let response = m.solve_with_parameters(&sat_params);
+        let optimal = match response.status() {
+            CpSolverStatus::Optimal => true,
+            CpSolverStatus::Feasible => false,
+            status => return Err(format!("cpsat stopped with status {:?}", status)),
+        };
+
+        Ok(MipSolution {
+            objective: response.objective_value,
+            values: vars.iter()
+                .map(|x| if x.solution_value(&response) { 1.0 } else { 0.0 })
+                .collect(),
+            optimal: optimal,
+            bound: Some(response.best_objective_bound),
+            sets: None,
+        })
+    }
+}
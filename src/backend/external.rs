@@ -0,0 +1,71 @@
+use std::env;
+use std::fs::{self, File};
+use std::io::BufWriter;
+use std::process::{self, Command};
+use super::{Backend, Model, MipSolution, Params, read_sol};
+
+/// Solves models with an arbitrary solver executable, given by the `cmd` parameter. The
+/// command is split on whitespace, and `{lp}` and `{sol}` in it are replaced by the paths of
+/// the LP file to solve and of the solution file to read back, e.g. `cbc {lp} solve solu
+/// {sol}`. The solution file is read like Gurobi's, and is taken to be optimal. Threads, time
+/// limit and gap have to be given in the command itself, and MIP starts are ignored.
+#[derive(Debug, Clone, Default)]
+pub struct External {
+    pub cmd: Option<String>,
+}
+
+impl Backend for External {
+    fn name(&self) -> &'static str {
+        "external"
+    }
+
+    fn available(&self) -> Result<(), String> {
+        match self.cmd {
+            Some(_) => Ok(()),
+            None => Err("external: no solver command".to_string()),
+        }
+    }
+
+    fn set_param(&mut self, name: &str, value: &str) -> Result<(), String> {
+        match name {
+            "cmd" => self.cmd = Some(value.to_string()),
+            _ => return Err(format!("external: unknown parameter {}", name)),
+        }
+        Ok(())
+    }
+
+    fn solve(&self, model: &Model, _params: &Params) -> Result<MipSolution, String> {
+        let cmd = self.cmd.as_ref().ok_or_else(|| "external: no solver command".to_string())?;
+        let base = env::temp_dir().join(format!("maxcover-external-{}", process::id()));
+        let lp = base.with_extension("lp");
+        let sol = base.with_extension("sol");
+        {
+            let f = File::create(&lp).map_err(|e| e.to_string())?;
+            model.write_lp(&mut BufWriter::new(f)).map_err(|e| e.to_string())?;
+        }
+
+        let lp_path = lp.display().to_string();
+        let sol_path = sol.display().to_string();
+        let words = cmd.split_whitespace()
+            .map(|w| w.replace("{lp}", &lp_path).replace("{sol}", &sol_path))
+            .collect::<Vec<_>>();
+        let status = match words.split_first() {
+            Some((program, args)) => {
+                Command::new(program)
+                    .args(args)
+                    .status()
+                    .map_err(|e| format!("failed to run {}: {}", program, e))
+            }
+            None => Err("empty solver command".to_string()),
+        };
+        let _ = fs::remove_file(&lp);
+        let status = status?;
+        if !status.success() {
+            return Err(format!("{} exited with {}", words[0], status));
+        }
+
+        let result = read_sol(&sol, model);
+        let _ = fs::remove_file(&sol);
+        result
+    }
+}
@@ -0,0 +1,223 @@
+use std::env;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, BufWriter};
+use std::path::Path;
+use std::process::{self, Command};
+use std::time::Instant;
+use super::{Backend, CutFamily, CutLevel, Model, MipSolution, Params, Progress, on_path,
+            run_logged, seconds_since};
+
+/// glpsol's option for each cut family it has.
+const CUT_OPTIONS: &'static [(CutFamily, &'static str)] = &[(CutFamily::Clique, "--clique"),
+                                                          (CutFamily::Cover, "--cover"),
+                                                          (CutFamily::Gomory, "--gomory"),
+                                                          (CutFamily::Mir, "--mir")];
+
+/// Solves models with GLPK's command line tool, `glpsol`, which has to be on the `PATH`.
+/// GLPK is single-threaded, deterministic and has no absolute gap tolerance, so `threads`,
+/// `seed` and `abs_gap` are ignored, as are node files; the time and memory limits are rounded
+/// up to whole seconds and megabytes. The best bound is read from its log. glpsol cannot read
+/// a MIP start. Its cuts are either off, the default, or on, and there is no MIP emphasis or
+/// solution limit.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Glpk;
+
+impl Backend for Glpk {
+    fn name(&self) -> &'static str {
+        "glpk"
+    }
+
+    fn available(&self) -> Result<(), String> {
+        on_path("glpsol")
+    }
+
+    fn solve(&self, model: &Model, params: &Params) -> Result<MipSolution, String> {
+        self.solve_with_progress(model, params, &mut |_| {})
+    }
+
+    /// Follows the search through the `mip =` and `>>>>>` lines glpsol logs.
+    fn solve_with_progress(&self,
+                           model: &Model,
+                           params: &Params,
+                           progress: &mut dyn FnMut(&Progress))
+                           -> Result<MipSolution, String> {
+        let base = env::temp_dir().join(format!("maxcover-glpk-{}", process::id()));
+        let lp = base.with_extension("lp");
+        let sol = base.with_extension("sol");
+        {
+            let f = File::create(&lp).map_err(|e| e.to_string())?;
+            model.write_lp(&mut BufWriter::new(f)).map_err(|e| e.to_string())?;
+        }
+
+        let mut cmd = Command::new("glpsol");
+        cmd.arg("--lp").arg(&lp).arg("-w").arg(&sol);
+        if let Some(limit) = params.time_limit {
+            cmd.arg("--tmlim").arg(limit.ceil().to_string());
+        }
+        if let Some(gap) = params.gap {
+            cmd.arg("--mipgap").arg(gap.to_string());
+        }
+        if let Some(limit) = params.mem_limit {
+            cmd.arg("--memlim").arg(limit.ceil().to_string());
+        }
+        for &(family, option) in CUT_OPTIONS {
+            match params.cut_level(family) {
+                Some(CutLevel::Moderate) | Some(CutLevel::Aggressive) => {
+                    cmd.arg(option);
+                }
+                _ => {}
+            }
+        }
+        let start = Instant::now();
+        let mut incumbent = None;
+        let log = run_logged(&mut cmd, "glpsol", &mut |line| {
+            if let Some((objective, bound)) = log_incumbent(line) {
+                if incumbent != Some(objective) {
+                    incumbent = Some(objective);
+                    progress(&Progress::new(seconds_since(start), objective, bound));
+                }
+            }
+        });
+        let _ = fs::remove_file(&lp);
+        let log = log?;
+
+        let result = read_glpk_sol(&sol, model);
+        let _ = fs::remove_file(&sol);
+        let mut result = result?;
+        result.bound = if result.optimal {
+            Some(result.objective)
+        } else {
+            best_bound(&log)
+        };
+        Ok(result)
+    }
+}
+
+/// The incumbent and bound of a `mip = <incumbent> <= <bound>` progress line, or of a
+/// `>>>>> <incumbent> <= <bound>` line announcing a new incumbent. Before the first incumbent
+/// glpsol prints `not found yet` instead.
+fn log_incumbent(line: &str) -> Option<(f64, Option<f64>)> {
+    if !(line.contains("mip =") || line.contains(">>>>>")) {
+        return None;
+    }
+    let mut sides = line.splitn(2, "<=");
+    let objective = sides.next()
+        .and_then(|left| left.split_whitespace().last())
+        .and_then(|w| w.parse::<f64>().ok())?;
+    let bound = sides.next()
+        .and_then(|right| right.split_whitespace().next())
+        .and_then(|w| w.parse::<f64>().ok())
+        .filter(|bound| bound.is_finite());
+    Some((objective, bound))
+}
+
+/// The bound from the last `mip = <incumbent> <= <bound>` progress line of the log.
+fn best_bound(log: &str) -> Option<f64> {
+    log.lines()
+        .filter(|line| line.contains("mip ="))
+        .filter_map(|line| line.split("<=").nth(1))
+        .filter_map(|rest| rest.split_whitespace().next())
+        .filter_map(|bound| bound.parse().ok())
+        .next_back()
+}
+
+/// Reads GLPK's plain text MIP solution format. Columns are numbered in the order they first
+/// appear in the LP file, which `Model::write_lp` makes the order of the model's variables.
+fn read_glpk_sol(path: &Path, model: &Model) -> Result<MipSolution, String> {
+    let f = File::open(path).map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+    let mut sol = MipSolution {
+        objective: 0.0,
+        values: vec![0.0; model.vars.len()],
+        optimal: false,
+        bound: None,
+        sets: None,
+    };
+
+    for line in BufReader::new(f).lines() {
+        let line = line.map_err(|e| e.to_string())?;
+        let words = line.split_whitespace().collect::<Vec<_>>();
+        match words.first() {
+            // s mip <rows> <cols> <status> <objective>
+            Some(&"s") if words.len() >= 6 => {
+                // o: optimal, f: feasible, stopped by a limit
+                match words[4] {
+                    "o" => sol.optimal = true,
+                    "f" => sol.optimal = false,
+                    status => return Err(format!("glpsol found no solution (status {})", status)),
+                }
+                sol.objective = words[5].parse().map_err(|_| format!("bad objective: {}", line))?;
+            }
+            // j <col> <value>
+            Some(&"j") if words.len() >= 3 => {
+                let col = words[1].parse::<usize>().map_err(|_| format!("bad column: {}", line))?;
+                if col == 0 || col > sol.values.len() {
+                    return Err(format!("unknown column: {}", line));
+                }
+                sol.values[col - 1] = words[2].parse().map_err(|_| format!("bad value: {}", line))?;
+            }
+            _ => {}
+        }
+    }
+
+    Ok(sol)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use backend::VarType;
+
+    #[test]
+    fn reads_incumbents_from_the_log() {
+        let line = "+   123: mip =   1.500000000e+01 <=   2.000000000e+01  33.3% (5; 0)";
+        assert_eq!(log_incumbent(line), Some((15.0, Some(20.0))));
+        let line = ">>>>>   1.700000000e+01 <=   2.000000000e+01  17.6% (8; 0)";
+        assert_eq!(log_incumbent(line), Some((17.0, Some(20.0))));
+        let line = "+     0: mip =     not found yet <=              +inf        (1; 0)";
+        assert_eq!(log_incumbent(line), None);
+        let line = "+    10: mip =   1.500000000e+01 <=              +inf        (2; 0)";
+        assert_eq!(log_incumbent(line), Some((15.0, None)));
+    }
+
+    #[test]
+    fn reads_the_last_bound() {
+        let log = "+   123: mip =   1.500000000e+01 <=   2.000000000e+01  33.3% (5; 0)\n\
+                   +   456: mip =   1.700000000e+01 <=   1.800000000e+01   5.9% (9; 0)\n\
+                   TIME LIMIT EXCEEDED; SEARCH TERMINATED\n";
+        assert_eq!(best_bound(log), Some(18.0));
+    }
+
+    fn model() -> Model {
+        let mut model = Model::new("test", true);
+        for name in &["e0", "s0", "s1"] {
+            model.add_var(name.to_string(), VarType::Binary, 1.0, 0.0, 1.0);
+        }
+        model
+    }
+
+    /// Reads `contents` as a solution file, written to a file named after the test `name`.
+    fn read(name: &str, contents: &str) -> Result<MipSolution, String> {
+        let file = format!("maxcover-glpk-{}-{}.sol", name, process::id());
+        let path = env::temp_dir().join(file);
+        fs::write(&path, contents).unwrap();
+        let sol = read_glpk_sol(&path, &model());
+        let _ = fs::remove_file(&path);
+        sol
+    }
+
+    #[test]
+    fn reads_solution_files() {
+        let sol = read("feasible", "c Problem:\ns mip 2 3 f 2\ni 1 2\nj 1 1\nj 2 0\nj 3 1\ne o f\n")
+            .unwrap();
+        assert!(!sol.optimal);
+        assert_eq!(sol.objective, 2.0);
+        assert_eq!(sol.values, vec![1.0, 0.0, 1.0]);
+        assert!(read("optimal", "s mip 2 3 o 2\nj 1 1\n").unwrap().optimal);
+    }
+
+    #[test]
+    fn rejects_bad_solution_files() {
+        assert!(read("unsolved", "s mip 2 3 n 0\n").is_err());
+        assert!(read("column", "s mip 2 3 o 2\nj 4 1\n").is_err());
+    }
+}
@@ -0,0 +1,261 @@
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::process::{self, Command};
+use std::time::Instant;
+use super::{Backend, CutFamily, CutLevel, Emphasis, Model, MipSolution, NodeFile, Params,
+            Progress, on_path, read_sol, run_logged, seconds_since};
+
+/// Gurobi's parameter for each cut family. Gomory cuts are only controlled by their number of
+/// passes, so they can only be turned off.
+const CUT_PARAMS: &'static [(CutFamily, &'static str)] =
+    &[(CutFamily::Clique, "CliqueCuts"),
+      (CutFamily::Cover, "CoverCuts"),
+      (CutFamily::FlowCover, "FlowCoverCuts"),
+      (CutFamily::GubCover, "GUBCoverCuts"),
+      (CutFamily::Implied, "ImpliedCuts"),
+      (CutFamily::LiftProject, "LiftProjectCuts"),
+      (CutFamily::Mir, "MIRCuts"),
+      (CutFamily::ZeroHalf, "ZeroHalfCuts")];
+
+/// Gurobi's value for a cut level.
+fn cut_value(level: CutLevel) -> i32 {
+    match level {
+        CutLevel::Off => 0,
+        CutLevel::Auto => -1,
+        CutLevel::Moderate => 1,
+        CutLevel::Aggressive => 2,
+    }
+}
+
+/// Solves models with Gurobi's command line tool, `gurobi_cl`, which has to be on the `PATH`.
+/// The model is passed as an LP file and the solution read back from a `.sol` file, so no
+/// Gurobi headers are needed at build time. A MIP start and branching priorities are passed
+/// as `.mst` and `.ord` files, and the best bound is read from the log. With a disk node file
+/// the memory limit is where Gurobi starts writing nodes, which it always compresses;
+/// otherwise Gurobi stops at the limit.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Gurobi;
+
+impl Backend for Gurobi {
+    fn name(&self) -> &'static str {
+        "gurobi"
+    }
+
+    fn available(&self) -> Result<(), String> {
+        on_path("gurobi_cl")
+    }
+
+    fn solve(&self, model: &Model, params: &Params) -> Result<MipSolution, String> {
+        self.solve_with_progress(model, params, &mut |_| {})
+    }
+
+    /// Follows the search through the heuristic and node log lines that report incumbents.
+    fn solve_with_progress(&self,
+                           model: &Model,
+                           params: &Params,
+                           progress: &mut dyn FnMut(&Progress))
+                           -> Result<MipSolution, String> {
+        let base = env::temp_dir().join(format!("maxcover-gurobi-{}", process::id()));
+        let lp = base.with_extension("lp");
+        let sol = base.with_extension("sol");
+        let mst = base.with_extension("mst");
+        let ord = base.with_extension("ord");
+        {
+            let f = File::create(&lp).map_err(|e| e.to_string())?;
+            model.write_lp(&mut BufWriter::new(f)).map_err(|e| e.to_string())?;
+        }
+
+        let mut cmd = Command::new("gurobi_cl");
+        cmd.arg(format!("ResultFile={}", sol.display()));
+        if let Some(ref start) = model.start {
+            write_mst(&mst, model, start).map_err(|e| e.to_string())?;
+            cmd.arg(format!("InputFile={}", mst.display()));
+        }
+        if model.vars.iter().any(|var| var.priority > 0) {
+            write_ord(&ord, model).map_err(|e| e.to_string())?;
+            cmd.arg(format!("InputFile={}", ord.display()));
+        }
+        if let Some(threads) = params.threads {
+            cmd.arg(format!("Threads={}", threads));
+        }
+        if let Some(limit) = params.time_limit {
+            cmd.arg(format!("TimeLimit={}", limit));
+        }
+        if let Some(gap) = params.gap {
+            cmd.arg(format!("MIPGap={}", gap));
+        }
+        if let Some(gap) = params.abs_gap {
+            cmd.arg(format!("MIPGapAbs={}", gap));
+        }
+        if let Some(seed) = params.seed_i32() {
+            cmd.arg(format!("Seed={}", seed));
+        }
+        if let Some(limit) = params.mem_limit {
+            // Gurobi takes memory sizes in gigabytes
+            let gb = limit / 1024.0;
+            match params.node_file {
+                Some(NodeFile::Disk) | Some(NodeFile::DiskCompressed) => {
+                    cmd.arg(format!("NodefileStart={}", gb))
+                }
+                _ => cmd.arg(format!("SoftMemLimit={}", gb)),
+            };
+        }
+        if let Some(ref dir) = params.node_dir {
+            cmd.arg(format!("NodefileDir={}", dir));
+        }
+        if let Some(emphasis) = params.emphasis {
+            let focus = match emphasis {
+                Emphasis::Balanced => 0,
+                Emphasis::Feasibility => 1,
+                Emphasis::Optimality => 2,
+                Emphasis::Bound => 3,
+            };
+            cmd.arg(format!("MIPFocus={}", focus));
+        }
+        if let Some(limit) = params.solution_limit {
+            cmd.arg(format!("SolutionLimit={}", limit));
+        }
+        if let Some(cuts) = params.cuts {
+            cmd.arg(format!("Cuts={}", cut_value(cuts)));
+        }
+        for &(family, level) in &params.cut_families {
+            if let Some(&(_, name)) = CUT_PARAMS.iter().find(|&&(f, _)| f == family) {
+                cmd.arg(format!("{}={}", name, cut_value(level)));
+            } else if family == CutFamily::Gomory && level == CutLevel::Off {
+                cmd.arg("GomoryPasses=0");
+            }
+        }
+        // Gurobi writes each new incumbent to <SolFiles>_<n>.sol as it finds it
+        let incumbents = base.with_extension("incumbent");
+        let incumbent_file = |n: usize| format!("{}_{}.sol", incumbents.display(), n);
+        cmd.arg(format!("SolFiles={}", incumbents.display()));
+
+        let start = Instant::now();
+        let mut incumbent = None;
+        let mut read = 0;
+        let log = run_logged(cmd.arg(&lp), "gurobi_cl", &mut |line| {
+            if let Some((objective, bound)) = log_incumbent(line) {
+                if incumbent != Some(objective) {
+                    incumbent = Some(objective);
+                    let mut values = None;
+                    while let Ok(sol) = read_sol(Path::new(&incumbent_file(read)), model) {
+                        let _ = fs::remove_file(incumbent_file(read));
+                        values = Some(sol.values);
+                        read += 1;
+                    }
+                    let mut p = Progress::new(seconds_since(start), objective, bound);
+                    p.values = values;
+                    progress(&p);
+                }
+            }
+        });
+        while fs::remove_file(incumbent_file(read)).is_ok() {
+            read += 1;
+        }
+        let _ = fs::remove_file(&lp);
+        let _ = fs::remove_file(&mst);
+        let _ = fs::remove_file(&ord);
+        let log = log?;
+
+        // gurobi_cl only writes the result file if it found a solution
+        let result = read_sol(&sol, model);
+        let _ = fs::remove_file(&sol);
+        let mut result = result?;
+        result.optimal = log.contains("Optimal solution found");
+        result.bound = best_bound(&log);
+        Ok(result)
+    }
+}
+
+/// Writes `start` as a MIP start file, which lists one `name value` pair per line.
+fn write_mst(path: &Path, model: &Model, start: &[f64]) -> io::Result<()> {
+    let mut out = BufWriter::new(File::create(path)?);
+    for (var, value) in model.vars.iter().zip(start) {
+        writeln!(out, "{} {}", var.name, value)?;
+    }
+    Ok(())
+}
+
+/// Writes the branching priorities of `model` as a priority order file, which lists one
+/// `name priority` pair per line.
+fn write_ord(path: &Path, model: &Model) -> io::Result<()> {
+    let mut out = BufWriter::new(File::create(path)?);
+    for var in model.vars.iter().filter(|var| var.priority > 0) {
+        writeln!(out, "{} {}", var.name, var.priority)?;
+    }
+    Ok(())
+}
+
+/// The incumbent, and the bound if given, of a log line that reports a new incumbent: either
+/// `Found heuristic solution: objective <obj>`, or a node log line marked with `H` or `*` that
+/// ends in `<incumbent> <bound> <gap>% <it/node> <time>s`.
+fn log_incumbent(line: &str) -> Option<(f64, Option<f64>)> {
+    if line.starts_with("Found heuristic solution") {
+        let objective = line.split_whitespace().last().and_then(|w| w.parse().ok());
+        return objective.map(|objective| (objective, None));
+    }
+    if !(line.starts_with('H') || line.starts_with('*')) {
+        return None;
+    }
+    let words = line.split_whitespace().rev().collect::<Vec<_>>();
+    if words.len() < 5 || !words[2].ends_with('%') {
+        return None;
+    }
+    match (words[4].parse(), words[3].parse()) {
+        (Ok(objective), Ok(bound)) => Some((objective, Some(bound))),
+        _ => None,
+    }
+}
+
+/// The bound from the `Best objective ..., best bound ..., gap ...` summary line.
+fn best_bound(log: &str) -> Option<f64> {
+    log.lines()
+        .filter(|line| line.starts_with("Best objective"))
+        .filter_map(|line| line.split(',').nth(1))
+        .filter_map(|part| part.split_whitespace().last())
+        .filter_map(|bound| bound.parse().ok())
+        .next_back()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use backend::VarType;
+
+    #[test]
+    fn reads_incumbents_from_the_log() {
+        assert_eq!(log_incumbent("Found heuristic solution: objective 12.0000000"),
+                   Some((12.0, None)));
+        let node = "H    0     0                      15.0000000   20.00000  33.3%     -    0s";
+        assert_eq!(log_incumbent(node), Some((15.0, Some(20.0))));
+        let node = "*  123    45             12      17.0000000   18.00000  5.88%   3.2    1s";
+        assert_eq!(log_incumbent(node), Some((17.0, Some(18.0))));
+        let node = "     0     0   20.00000    0    4          -   20.00000      -     -    0s";
+        assert_eq!(log_incumbent(node), None);
+    }
+
+    #[test]
+    fn reads_the_best_bound() {
+        let log = "Explored 5 nodes\n\
+                   Best objective 1.700000000000e+01, best bound 1.800000000000e+01, gap 5.8824%\n";
+        assert_eq!(best_bound(log), Some(18.0));
+        assert_eq!(best_bound("Model is infeasible\n"), None);
+    }
+
+    #[test]
+    fn reads_solution_files() {
+        let mut model = Model::new("test", true);
+        for name in &["e0", "s0", "s1"] {
+            model.add_var(name.to_string(), VarType::Binary, 1.0, 0.0, 1.0);
+        }
+        let path = env::temp_dir().join(format!("maxcover-gurobi-test-{}.sol", process::id()));
+        fs::write(&path, "# Objective value = 2\ne0 1\ns1 1\n").unwrap();
+        let sol = read_sol(&path, &model);
+        let _ = fs::remove_file(&path);
+        let sol = sol.unwrap();
+        assert_eq!(sol.objective, 2.0);
+        assert_eq!(sol.values, vec![1.0, 0.0, 1.0]);
+    }
+}
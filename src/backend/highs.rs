@@ -0,0 +1,82 @@
+use highs::{HighsModelStatus, RowProblem, Sense as HighsSense};
+use super::{Backend, Model, MipSolution, Params, Sense, VarType};
+
+/// Solves models in-process with HiGHS. Needs the `highs` feature. The highs crate cannot pass
+/// a MIP start, and HiGHS has no memory limit, MIP emphasis or cut controls, so those are
+/// ignored.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Highs;
+
+impl Backend for Highs {
+    fn name(&self) -> &'static str {
+        "highs"
+    }
+
+    fn solve(&self, model: &Model, params: &Params) -> Result<MipSolution, String> {
+        let mut pb = RowProblem::default();
+        let cols = model.vars
+            .iter()
+            .map(|var| match var.ty {
+                VarType::Binary | VarType::Integer => {
+                    pb.add_integer_column(var.obj, var.lb..=var.ub)
+                }
+                VarType::Continuous => pb.add_column(var.obj, var.lb..=var.ub),
+            })
+            .collect::<Vec<_>>();
+
+        for row in &model.rows {
+            let factors = row.terms.iter().map(|&(j, c)| (cols[j], c)).collect::<Vec<_>>();
+            match row.sense {
+                Sense::LessEq => pb.add_row(..=row.rhs, factors),
+                Sense::Eq => pb.add_row(row.rhs..=row.rhs, factors),
+                Sense::GreaterEq => pb.add_row(row.rhs.., factors),
+            };
+        }
+
+        let mut m = pb.optimise(if model.maximize {
+            HighsSense::Maximise
+        } else {
+            HighsSense::Minimise
+        });
+        if let Some(threads) = params.threads {
+            m.set_option("threads", threads as i32);
+        }
+        if let Some(limit) = params.time_limit {
+            m.set_option("time_limit", limit);
+        }
+        if let Some(gap) = params.gap {
+            m.set_option("mip_rel_gap", gap);
+        }
+        if let Some(gap) = params.abs_gap {
+            m.set_option("mip_abs_gap", gap);
+        }
+        if let Some(seed) = params.seed_i32() {
+            m.set_option("random_seed", seed);
+        }
+
+        if let Some(limit) = params.solution_limit {
+            m.set_option("mip_max_improving_sols", limit as i32);
+        }
+
+        let solved = m.solve();
+        let optimal = match solved.status() {
+            HighsModelStatus::Optimal => true,
+            HighsModelStatus::ReachedTimeLimit | HighsModelStatus::SolutionLimit => false,
+            status => return Err(format!("highs stopped with status {:?}", status)),
+        };
+
+        // on a limit the returned point is only a solution if HiGHS found an incumbent
+        let values = solved.get_solution().columns().to_vec();
+        if !model.feasible(&values) {
+            return Err("highs stopped without a solution".to_string());
+        }
+        let objective = model.vars.iter().zip(&values).map(|(var, &x)| var.obj * x).sum();
+        Ok(MipSolution {
+            objective: objective,
+            values: values,
+            optimal: optimal,
+            bound: None,
+            sets: None,
+        })
+    }
+}
@@ -0,0 +1,1182 @@
+//! Solver-agnostic mixed integer programs and the backends that solve them.
+
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::ops::Range;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::str::FromStr;
+use std::time::Instant;
+use Instance;
+
+#[cfg(feature = "cbc")]
+mod cbc;
+#[cfg(feature = "cplex")]
+mod cplex;
+#[cfg(feature = "cpsat")]
+mod cpsat;
+mod external;
+mod glpk;
+mod gurobi;
+#[cfg(feature = "highs")]
+mod highs;
+#[cfg(feature = "scip")]
+mod scip;
+
+#[cfg(feature = "cbc")]
+pub use self::cbc::Cbc;
+#[cfg(feature = "cplex")]
+pub use self::cplex::Cplex;
+#[cfg(feature = "cpsat")]
+pub use self::cpsat::CpSat;
+pub use self::external::External;
+pub use self::glpk::Glpk;
+pub use self::gurobi::Gurobi;
+#[cfg(feature = "highs")]
+pub use self::highs::Highs;
+#[cfg(feature = "scip")]
+pub use self::scip::Scip;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VarType {
+    Binary,
+    /// A general integer between its bounds.
+    Integer,
+    Continuous,
+}
+
+#[derive(Debug, Clone)]
+pub struct Var {
+    pub name: String,
+    pub ty: VarType,
+    pub obj: f64,
+    pub lb: f64,
+    pub ub: f64,
+    /// Branching priority; higher is branched on first. 0 leaves it to the solver, as do
+    /// backends without priorities.
+    pub priority: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Sense {
+    LessEq,
+    Eq,
+    GreaterEq,
+}
+
+/// A linear constraint `sum terms (sense) rhs`, with terms given as (variable, coefficient).
+#[derive(Debug, Clone)]
+pub struct Row {
+    pub name: String,
+    pub terms: Vec<(usize, f64)>,
+    pub sense: Sense,
+    pub rhs: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct Model {
+    pub name: String,
+    pub maximize: bool,
+    pub vars: Vec<Var>,
+    pub rows: Vec<Row>,
+    /// A feasible solution to start from, with one value per variable. Backends that cannot
+    /// take a MIP start ignore it.
+    pub start: Option<Vec<f64>>,
+    /// The variables of the sets, in order, if every incumbent of the model selects the sets
+    /// whose variables are 1 and has that selection's objective.
+    pub selection: Option<Range<usize>>,
+}
+
+impl Model {
+    pub fn new(name: &str, maximize: bool) -> Model {
+        Model {
+            name: name.to_string(),
+            maximize: maximize,
+            vars: Vec::new(),
+            rows: Vec::new(),
+            start: None,
+            selection: None,
+        }
+    }
+
+    /// The sets selected by `values` of the model's variables, if it has a `selection`.
+    pub fn selected(&self, values: &[f64]) -> Option<Vec<usize>> {
+        self.selection.clone().map(|vars| {
+            vars.enumerate().filter(|&(_, j)| values[j] > 0.5).map(|(i, _)| i).collect()
+        })
+    }
+
+    /// Adds a variable and returns its index.
+    pub fn add_var(&mut self, name: String, ty: VarType, obj: f64, lb: f64, ub: f64) -> usize {
+        self.vars.push(Var {
+            name: name,
+            ty: ty,
+            obj: obj,
+            lb: lb,
+            ub: ub,
+            priority: 0,
+        });
+        self.vars.len() - 1
+    }
+
+    pub fn add_row(&mut self, name: String, terms: Vec<(usize, f64)>, sense: Sense, rhs: f64) {
+        self.rows.push(Row {
+            name: name,
+            terms: terms,
+            sense: sense,
+            rhs: rhs,
+        });
+    }
+
+    /// Whether `values` satisfies the bounds and constraints, up to a small tolerance.
+    pub fn feasible(&self, values: &[f64]) -> bool {
+        const TOL: f64 = 1e-6;
+        if values.len() != self.vars.len() {
+            return false;
+        }
+        let in_bounds = self.vars
+            .iter()
+            .zip(values)
+            .all(|(var, &x)| x >= var.lb - TOL && x <= var.ub + TOL);
+        in_bounds &&
+        self.rows.iter().all(|row| {
+            let lhs = row.terms.iter().map(|&(j, c)| c * values[j]).sum::<f64>();
+            match row.sense {
+                Sense::LessEq => lhs <= row.rhs + TOL,
+                Sense::Eq => (lhs - row.rhs).abs() <= TOL,
+                Sense::GreaterEq => lhs >= row.rhs - TOL,
+            }
+        })
+    }
+
+    /// Writes the model in CPLEX LP format, which every backend reads. Every variable appears
+    /// in the objective, in order, so that readers number the columns like the model.
+    pub fn write_lp<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        writeln!(out, "\\ {}", self.name)?;
+        writeln!(out, "{}", if self.maximize { "Maximize" } else { "Minimize" })?;
+        let objective = self.vars.iter().enumerate().map(|(j, v)| (j, v.obj)).collect::<Vec<_>>();
+        write!(out, " obj:")?;
+        self.write_terms(out, &objective)?;
+        writeln!(out)?;
+
+        writeln!(out, "Subject To")?;
+        for row in &self.rows {
+            write!(out, " {}:", row.name)?;
+            self.write_terms(out, &row.terms)?;
+            let sense = match row.sense {
+                Sense::LessEq => "<=",
+                Sense::Eq => "=",
+                Sense::GreaterEq => ">=",
+            };
+            writeln!(out, " {} {}", sense, row.rhs)?;
+        }
+
+        // binaries are declared with bounds 0 and 1, so fixed ones are general integers
+        let binary = |v: &Var| v.ty == VarType::Binary && v.lb <= 0.0 && v.ub >= 1.0;
+        writeln!(out, "Bounds")?;
+        for var in self.vars.iter().filter(|v| !binary(v)) {
+            writeln!(out, " {} <= {} <= {}", var.lb, var.name, var.ub)?;
+        }
+
+        writeln!(out, "Binaries")?;
+        for var in self.vars.iter().filter(|v| binary(v)) {
+            writeln!(out, " {}", var.name)?;
+        }
+        let general = |v: &Var| v.ty != VarType::Continuous && !binary(v);
+        if self.vars.iter().any(&general) {
+            writeln!(out, "Generals")?;
+            for var in self.vars.iter().filter(|v| general(v)) {
+                writeln!(out, " {}", var.name)?;
+            }
+        }
+        writeln!(out, "End")
+    }
+
+    /// Writes `terms`, breaking lines regularly since LP readers limit the line length.
+    fn write_terms<W: Write>(&self, out: &mut W, terms: &[(usize, f64)]) -> io::Result<()> {
+        for (i, &(j, c)) in terms.iter().enumerate() {
+            if i > 0 && i % 8 == 0 {
+                write!(out, "\n   ")?;
+            }
+            if c < 0.0 {
+                write!(out, " - {} {}", -c, self.vars[j].name)?;
+            } else {
+                write!(out, " + {} {}", c, self.vars[j].name)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Builds the max k-coverage model with set variables of type `ty`, returning it along with
+/// the index of each set's variable.
+pub fn coverage_model(inst: &Instance, k: usize, ty: VarType) -> (Model, Vec<usize>) {
+    let (mut model, set_vars) = lazy_coverage_model(inst, k, ty);
+    let elements = inst.ground.iter().cloned().enumerate().map(|(j, x)| (x, j)).collect::<Vec<_>>();
+    add_cover_rows(&mut model, inst, &set_vars, &elements);
+    (model, set_vars)
+}
+
+/// Builds `coverage_model` without any cover rows, so that every element variable is free to
+/// be 1. The variables are laid out the same way, so element `j` of the ground set has
+/// variable `j`.
+pub fn lazy_coverage_model(inst: &Instance, k: usize, ty: VarType) -> (Model, Vec<usize>) {
+    let mut model = Model::new("maxcover", true);
+
+    for (&x, w) in inst.ground.iter().zip(inst.weights()) {
+        model.add_var(format!("e{}", x), ty, w, 0.0, 1.0);
+    }
+    let set_vars = (0..inst.sets.len())
+        .map(|i| model.add_var(format!("s{}", i), ty, 0.0, 0.0, 1.0))
+        .collect::<Vec<_>>();
+    model.selection = Some(inst.ground.len()..model.vars.len());
+
+    let cardinality = set_vars.iter().map(|&j| (j, 1.0)).collect();
+    model.add_row("cardinality".to_string(), cardinality, Sense::LessEq, k as f64);
+    add_selection_rows(&mut model, inst, &set_vars);
+    add_fairness_rows(&mut model, inst);
+
+    (model, set_vars)
+}
+
+/// Adds a row per label with a `min_coverage` to a model whose element `j` has variable `j`,
+/// which asks the label's covered elements for its fair share.
+pub fn add_fairness_rows(model: &mut Model, inst: &Instance) {
+    let labels = inst.labels();
+    let weights = inst.weights();
+    for (l, share) in inst.fair_shares().into_iter().enumerate() {
+        let terms = (0..labels.len())
+            .filter(|&j| labels[j] == l && weights[j] != 0.0)
+            .map(|j| (j, weights[j]))
+            .collect();
+        model.add_row(format!("fair{}", l), terms, Sense::GreaterEq, share);
+    }
+}
+
+/// Adds the rows of the instance's constraints on which sets may be selected together, for
+/// the set variables `set_vars`: one per group limit, one per clique of conflicting sets and
+/// one per prerequisite.
+/// The variables of required and forbidden sets are fixed instead. Under a red limit, a
+/// variable per red element of `Instance::red` follows the model's others, which is 1 once a
+/// selected set contains the element, with `selection_values`.
+pub fn add_selection_rows(model: &mut Model, inst: &Instance, set_vars: &[usize]) {
+    for (i, fixed) in inst.fixed().into_iter().enumerate() {
+        let var = &mut model.vars[set_vars[i]];
+        match fixed {
+            Some(true) => var.lb = 1.0,
+            Some(false) => var.ub = 0.0,
+            None => {}
+        }
+    }
+    let groups = inst.groups();
+    for (g, &limit) in inst.group_limits().iter().enumerate() {
+        let terms = (0..set_vars.len())
+            .filter(|&i| groups[i] == g)
+            .map(|i| (set_vars[i], 1.0))
+            .collect();
+        model.add_row(format!("group{}", g), terms, Sense::LessEq, limit as f64);
+    }
+    for (c, clique) in inst.conflicts().iter().enumerate() {
+        let terms = clique.iter().map(|&i| (set_vars[i], 1.0)).collect();
+        model.add_row(format!("conflict{}", c), terms, Sense::LessEq, 1.0);
+    }
+    for (p, (a, b)) in inst.prerequisites().into_iter().enumerate() {
+        let terms = vec![(set_vars[a], 1.0), (set_vars[b], -1.0)];
+        model.add_row(format!("prereq{}", p), terms, Sense::LessEq, 0.0);
+    }
+    if let Some(limit) = inst.red_limit {
+        let mut red = Vec::new();
+        for x in inst.red() {
+            let r = model.add_var(format!("r{}", x), VarType::Binary, 0.0, 0.0, 1.0);
+            for i in (0..inst.sets.len()).filter(|&i| inst.sets[i].contains(&x)) {
+                let terms = vec![(r, 1.0), (set_vars[i], -1.0)];
+                model.add_row(format!("red{}_{}", x, i), terms, Sense::GreaterEq, 0.0);
+            }
+            red.push((r, 1.0));
+        }
+        model.add_row("red".to_string(), red, Sense::LessEq, limit as f64);
+    }
+}
+
+/// The values of the variables `add_selection_rows` adds when `sets` are selected.
+pub fn selection_values(inst: &Instance, sets: &[usize]) -> Vec<f64> {
+    if inst.red_limit.is_none() {
+        return Vec::new();
+    }
+    inst.red()
+        .iter()
+        .map(|x| if sets.iter().any(|&i| inst.sets[i].contains(x)) { 1.0 } else { 0.0 })
+        .collect()
+}
+
+/// Adds the cover row of each `(element, variable)` in `elements`, which only lets the
+/// element's variable be 1 if as many sets containing it as its demand are selected.
+pub fn add_cover_rows(model: &mut Model,
+                      inst: &Instance,
+                      set_vars: &[usize],
+                      elements: &[(usize, usize)]) {
+    let demands = inst.ground.iter().cloned().zip(inst.demands()).collect::<HashMap<_, _>>();
+    let mut containing = elements.iter()
+        .map(|&(x, _)| (x, Vec::new()))
+        .collect::<HashMap<_, _>>();
+    for (i, set) in inst.sets.iter().enumerate() {
+        for x in set {
+            if let Some(terms) = containing.get_mut(x) {
+                terms.push((set_vars[i], 1.0));
+            }
+        }
+    }
+    for &(x, j) in elements {
+        let mut terms = containing.remove(&x).unwrap_or_else(Vec::new);
+        terms.push((j, -(demands[&x] as f64)));
+        model.add_row(format!("cover{}", x), terms, Sense::GreaterEq, 0.0);
+    }
+}
+
+/// Builds the minimum set cover model, which selects sets of the least total cost that cover
+/// every element as often as its demand, with variables of type `ty`. The variables are the
+/// sets, in order.
+///
+/// With a `target` below 1 this is the partial cover model, which only has to cover
+/// `target` times the total weight. It has a variable per element, like `coverage_model`,
+/// which the set variables follow.
+pub fn set_cover_model(inst: &Instance, ty: VarType, target: f64) -> Model {
+    let mut model = Model::new("setcover", false);
+    let partial = target < 1.0;
+    let weights = inst.weights();
+    if partial {
+        for &x in &inst.ground {
+            model.add_var(format!("e{}", x), ty, 0.0, 0.0, 1.0);
+        }
+    }
+    let set_vars = inst.costs()
+        .into_iter()
+        .enumerate()
+        .map(|(i, cost)| model.add_var(format!("s{}", i), ty, cost, 0.0, 1.0))
+        .collect::<Vec<_>>();
+    model.selection = Some(model.vars.len() - inst.sets.len()..model.vars.len());
+
+    let mut containing = HashMap::new();
+    for (i, set) in inst.sets.iter().enumerate() {
+        for &x in set {
+            containing.entry(x).or_insert_with(Vec::new).push((set_vars[i], 1.0));
+        }
+    }
+    for (j, (&x, r)) in inst.ground.iter().zip(inst.demands()).enumerate() {
+        let mut terms = containing.remove(&x).unwrap_or_else(Vec::new);
+        if partial {
+            terms.push((j, -(r as f64)));
+            model.add_row(format!("cover{}", x), terms, Sense::GreaterEq, 0.0);
+        } else {
+            model.add_row(format!("cover{}", x), terms, Sense::GreaterEq, r as f64);
+        }
+    }
+    if partial {
+        let total = weights.iter().sum::<f64>();
+        let terms = weights.into_iter().enumerate().collect();
+        model.add_row("target".to_string(), terms, Sense::GreaterEq, target * total);
+    }
+    add_selection_rows(&mut model, inst, &set_vars);
+
+    model
+}
+
+/// The values of the variables of `coverage_model` when `sets` are selected, in the order the
+/// model adds them: elements first, then sets, then those of `selection_values`.
+pub fn coverage_values(inst: &Instance, sets: &[usize]) -> Vec<f64> {
+    let mut count = HashMap::new();
+    for &i in sets {
+        for &x in &inst.sets[i] {
+            *count.entry(x).or_insert(0) += 1;
+        }
+    }
+    let selected = sets.iter().collect::<HashSet<_>>();
+    let value = |b| if b { 1.0 } else { 0.0 };
+    inst.ground
+        .iter()
+        .zip(inst.demands())
+        .map(|(x, r)| value(count.get(x).map_or(0, |&c| c) >= r))
+        .chain((0..inst.sets.len()).map(|i| value(selected.contains(&i))))
+        .chain(selection_values(inst, sets))
+        .collect()
+}
+
+/// Where the solver keeps open branch and bound nodes once the memory limit is reached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeFile {
+    /// Keep every node in memory.
+    Off,
+    /// Compress nodes in memory.
+    Memory,
+    /// Write nodes to disk.
+    Disk,
+    /// Write compressed nodes to disk.
+    DiskCompressed,
+}
+
+impl NodeFile {
+    pub fn name(&self) -> &'static str {
+        match *self {
+            NodeFile::Off => "off",
+            NodeFile::Memory => "memory",
+            NodeFile::Disk => "disk",
+            NodeFile::DiskCompressed => "disk-compressed",
+        }
+    }
+}
+
+impl FromStr for NodeFile {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<NodeFile, String> {
+        match s {
+            "off" => Ok(NodeFile::Off),
+            "memory" => Ok(NodeFile::Memory),
+            "disk" => Ok(NodeFile::Disk),
+            "disk-compressed" => Ok(NodeFile::DiskCompressed),
+            _ => Err(format!("unknown node file mode: {}", s)),
+        }
+    }
+}
+
+/// What the MIP search concentrates on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Emphasis {
+    Balanced,
+    /// Finding good solutions quickly.
+    Feasibility,
+    /// Proving optimality.
+    Optimality,
+    /// Moving the best bound.
+    Bound,
+}
+
+impl Emphasis {
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Emphasis::Balanced => "balanced",
+            Emphasis::Feasibility => "feasibility",
+            Emphasis::Optimality => "optimality",
+            Emphasis::Bound => "bound",
+        }
+    }
+}
+
+impl FromStr for Emphasis {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Emphasis, String> {
+        match s {
+            "balanced" => Ok(Emphasis::Balanced),
+            "feasibility" => Ok(Emphasis::Feasibility),
+            "optimality" => Ok(Emphasis::Optimality),
+            "bound" => Ok(Emphasis::Bound),
+            _ => Err(format!("unknown emphasis: {}", s)),
+        }
+    }
+}
+
+/// How aggressively to generate cuts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CutLevel {
+    Off,
+    /// Leave it to the solver.
+    Auto,
+    Moderate,
+    Aggressive,
+}
+
+impl CutLevel {
+    pub fn name(&self) -> &'static str {
+        match *self {
+            CutLevel::Off => "off",
+            CutLevel::Auto => "auto",
+            CutLevel::Moderate => "moderate",
+            CutLevel::Aggressive => "aggressive",
+        }
+    }
+}
+
+impl FromStr for CutLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<CutLevel, String> {
+        match s {
+            "off" => Ok(CutLevel::Off),
+            "auto" => Ok(CutLevel::Auto),
+            "moderate" => Ok(CutLevel::Moderate),
+            "aggressive" => Ok(CutLevel::Aggressive),
+            _ => Err(format!("unknown cut level: {}", s)),
+        }
+    }
+}
+
+/// Cut families that several solvers can control separately.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CutFamily {
+    Clique,
+    Cover,
+    FlowCover,
+    Gomory,
+    GubCover,
+    Implied,
+    LiftProject,
+    Mir,
+    ZeroHalf,
+}
+
+impl CutFamily {
+    pub fn name(&self) -> &'static str {
+        match *self {
+            CutFamily::Clique => "clique",
+            CutFamily::Cover => "cover",
+            CutFamily::FlowCover => "flow-cover",
+            CutFamily::Gomory => "gomory",
+            CutFamily::GubCover => "gub-cover",
+            CutFamily::Implied => "implied",
+            CutFamily::LiftProject => "lift-project",
+            CutFamily::Mir => "mir",
+            CutFamily::ZeroHalf => "zero-half",
+        }
+    }
+}
+
+impl FromStr for CutFamily {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<CutFamily, String> {
+        match s {
+            "clique" => Ok(CutFamily::Clique),
+            "cover" => Ok(CutFamily::Cover),
+            "flow-cover" => Ok(CutFamily::FlowCover),
+            "gomory" => Ok(CutFamily::Gomory),
+            "gub-cover" => Ok(CutFamily::GubCover),
+            "implied" => Ok(CutFamily::Implied),
+            "lift-project" => Ok(CutFamily::LiftProject),
+            "mir" => Ok(CutFamily::Mir),
+            "zero-half" => Ok(CutFamily::ZeroHalf),
+            _ => Err(format!("unknown cut family: {}", s)),
+        }
+    }
+}
+
+/// Parses a cut family and its level from `<family>=<level>`.
+pub fn parse_cut(s: &str) -> Result<(CutFamily, CutLevel), String> {
+    let mut parts = s.splitn(2, '=');
+    match (parts.next(), parts.next()) {
+        (Some(family), Some(level)) => Ok((family.parse()?, level.parse()?)),
+        _ => Err(format!("expected FAMILY=LEVEL, not {}", s)),
+    }
+}
+
+/// Parameters every backend understands. Backends that lack a parameter ignore it.
+#[derive(Debug, Clone, Default)]
+pub struct Params {
+    pub threads: Option<usize>,
+    /// Wall-clock limit in seconds.
+    pub time_limit: Option<f64>,
+    /// Relative MIP gap at which to stop.
+    pub gap: Option<f64>,
+    /// Absolute MIP gap at which to stop.
+    pub abs_gap: Option<f64>,
+    /// Random seed, for reproducible runs.
+    pub seed: Option<usize>,
+    /// Memory in megabytes the solver may use for its search tree.
+    pub mem_limit: Option<f64>,
+    /// What to do with nodes beyond the memory limit.
+    pub node_file: Option<NodeFile>,
+    /// Directory for node files written to disk.
+    pub node_dir: Option<String>,
+    pub emphasis: Option<Emphasis>,
+    /// Cut level of every family not in `cut_families`.
+    pub cuts: Option<CutLevel>,
+    /// Cut levels of single families, overriding `cuts`.
+    pub cut_families: Vec<(CutFamily, CutLevel)>,
+    /// Number of improving solutions after which to stop.
+    pub solution_limit: Option<usize>,
+}
+
+impl Params {
+    /// Sets the parameter called `name` from its command line representation. Returns false
+    /// if there is no such parameter.
+    fn set(&mut self, name: &str, value: &str) -> Result<bool, String> {
+        let bad = || format!("bad value for {}: {}", name, value);
+        match name {
+            "threads" => self.threads = Some(value.parse().map_err(|_| bad())?),
+            "time_limit" => self.time_limit = Some(value.parse().map_err(|_| bad())?),
+            "gap" => self.gap = Some(value.parse().map_err(|_| bad())?),
+            "abs_gap" => self.abs_gap = Some(value.parse().map_err(|_| bad())?),
+            "seed" => self.seed = Some(value.parse().map_err(|_| bad())?),
+            "mem_limit" => self.mem_limit = Some(value.parse().map_err(|_| bad())?),
+            "node_file" => self.node_file = Some(value.parse()?),
+            "node_dir" => self.node_dir = Some(value.to_string()),
+            "emphasis" => self.emphasis = Some(value.parse()?),
+            "cuts" => self.cuts = Some(value.parse()?),
+            "cut" => self.cut_families.push(parse_cut(value)?),
+            "solution_limit" => self.solution_limit = Some(value.parse().map_err(|_| bad())?),
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    /// The seed reduced to the non-negative `i32` range that solvers take.
+    pub fn seed_i32(&self) -> Option<i32> {
+        self.seed.map(|s| (s % (i32::MAX as usize + 1)) as i32)
+    }
+
+    /// The cut level of `family`, if one was set for it or for all cuts.
+    pub fn cut_level(&self, family: CutFamily) -> Option<CutLevel> {
+        self.cut_families
+            .iter()
+            .rev()
+            .find(|&&(f, _)| f == family)
+            .map(|&(_, level)| level)
+            .or(self.cuts)
+    }
+
+    /// Passes every parameter that is set on to `backend`.
+    pub fn apply<B: SolverBackend + ?Sized>(&self, backend: &mut B) -> Result<(), String> {
+        if let Some(threads) = self.threads {
+            backend.set_param("threads", &threads.to_string())?;
+        }
+        if let Some(limit) = self.time_limit {
+            backend.set_param("time_limit", &limit.to_string())?;
+        }
+        if let Some(gap) = self.gap {
+            backend.set_param("gap", &gap.to_string())?;
+        }
+        if let Some(gap) = self.abs_gap {
+            backend.set_param("abs_gap", &gap.to_string())?;
+        }
+        if let Some(seed) = self.seed {
+            backend.set_param("seed", &seed.to_string())?;
+        }
+        if let Some(limit) = self.mem_limit {
+            backend.set_param("mem_limit", &limit.to_string())?;
+        }
+        if let Some(node_file) = self.node_file {
+            backend.set_param("node_file", node_file.name())?;
+        }
+        if let Some(ref dir) = self.node_dir {
+            backend.set_param("node_dir", dir)?;
+        }
+        if let Some(emphasis) = self.emphasis {
+            backend.set_param("emphasis", emphasis.name())?;
+        }
+        if let Some(cuts) = self.cuts {
+            backend.set_param("cuts", cuts.name())?;
+        }
+        for &(family, level) in &self.cut_families {
+            backend.set_param("cut", &format!("{}={}", family.name(), level.name()))?;
+        }
+        if let Some(limit) = self.solution_limit {
+            backend.set_param("solution_limit", &limit.to_string())?;
+        }
+        Ok(())
+    }
+}
+
+/// A solution of a `Model`, with one value per variable.
+#[derive(Debug, Clone)]
+pub struct MipSolution {
+    pub objective: f64,
+    pub values: Vec<f64>,
+    /// False if the solver stopped early, e.g. on the time limit, with only an incumbent.
+    pub optimal: bool,
+    /// Best bound on the objective, if the solver reports one.
+    pub bound: Option<f64>,
+    /// The sets the solution selects, if the model has a `selection`. `Staged` fills this in,
+    /// so backends leave it empty.
+    pub sets: Option<Vec<usize>>,
+}
+
+/// A new incumbent reported during a solve.
+#[derive(Serialize, Debug, Clone)]
+pub struct Progress {
+    /// Seconds since the solve started.
+    pub time: f64,
+    pub objective: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bound: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gap: Option<f64>,
+    /// The incumbent itself, with one value per variable, if the backend can read it back.
+    #[serde(skip_serializing)]
+    pub values: Option<Vec<f64>>,
+    /// The sets the incumbent selects, if its values are known and the model has a
+    /// `selection`.
+    #[serde(skip_serializing)]
+    pub sets: Option<Vec<usize>>,
+}
+
+impl Progress {
+    /// The progress at `time` with incumbent `objective` and best bound `bound`.
+    pub fn new(time: f64, objective: f64, bound: Option<f64>) -> Progress {
+        Progress {
+            time: time,
+            objective: objective,
+            bound: bound,
+            gap: bound.map(|bound| relative_gap(objective, bound)),
+            values: None,
+            sets: None,
+        }
+    }
+}
+
+/// The relative gap `|bound - objective| / objective`, of a maximization or a minimization
+/// problem.
+pub fn relative_gap(objective: f64, bound: f64) -> f64 {
+    (bound - objective).abs() / objective.max(1e-10)
+}
+
+/// Called with each new incumbent.
+pub type ProgressFn = Box<dyn FnMut(&Progress)>;
+
+/// Seconds since `start`.
+pub fn seconds_since(start: Instant) -> f64 {
+    let elapsed = start.elapsed();
+    elapsed.as_secs() as f64 + elapsed.subsec_nanos() as f64 * 1e-9
+}
+
+/// A MIP solver driven in stages: load a model, set parameters, solve, and read the solution
+/// back. Implement this, and add a factory to a `Registry`, to plug in a new solver.
+pub trait SolverBackend {
+    /// The name the backend is registered under.
+    fn name(&self) -> &'static str;
+
+    /// Loads `model`, replacing any previous one.
+    fn build_model(&mut self, model: Model) -> Result<(), String>;
+
+    /// Sets a parameter from its command line representation. Every backend accepts the
+    /// `Params`: `threads`, `time_limit`, `gap`, `abs_gap`, `seed`, `mem_limit`, `node_file`,
+    /// `node_dir`, `emphasis`, `cuts`, `cut`, given as `<family>=<level>`, and
+    /// `solution_limit`.
+    fn set_param(&mut self, name: &str, value: &str) -> Result<(), String>;
+
+    /// Solves the loaded model to optimality, or until a limit stops the solver. Stopping
+    /// early is only an error if no feasible solution was found.
+    fn solve(&mut self) -> Result<(), String>;
+
+    /// The solution found by the last call to `solve`.
+    fn extract_solution(&self) -> Result<MipSolution, String>;
+
+    /// Changes the right-hand side of the row named `row` of the loaded model, keeping the
+    /// rest of it, for a `solve` that does not need the model built again. By default the
+    /// loaded model cannot be changed.
+    fn set_rhs(&mut self, row: &str, _rhs: f64) -> Result<(), String> {
+        Err(format!("{}: cannot change row {} of the loaded model", self.name(), row))
+    }
+
+    /// Replaces the MIP start of the loaded model with `values`, one per variable, as
+    /// `set_rhs` does the right-hand side of a row.
+    fn set_start(&mut self, _values: Vec<f64>) -> Result<(), String> {
+        Err(format!("{}: cannot change the start of the loaded model", self.name()))
+    }
+
+    /// Whether the solver can run on this machine, and if not, why.
+    fn available(&self) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Sets a function to call with each new incumbent during `solve`. Backends that cannot
+    /// follow the search never call it.
+    fn set_progress(&mut self, _progress: ProgressFn) {}
+}
+
+/// A solver that handles a whole model in one call, which is all most bindings need. Wrap it
+/// in `Staged` to get a `SolverBackend`.
+pub trait Backend {
+    /// The name the backend is registered under.
+    fn name(&self) -> &'static str;
+
+    /// Sets a backend-specific parameter, one that `Params` does not cover.
+    fn set_param(&mut self, name: &str, _value: &str) -> Result<(), String> {
+        Err(format!("{}: unknown parameter {}", self.name(), name))
+    }
+
+    /// Solves `model` as `SolverBackend::solve` does.
+    fn solve(&self, model: &Model, params: &Params) -> Result<MipSolution, String>;
+
+    /// Solves `model`, calling `progress` with each new incumbent. By default the search is
+    /// not followed.
+    fn solve_with_progress(&self,
+                           model: &Model,
+                           params: &Params,
+                           _progress: &mut dyn FnMut(&Progress))
+                           -> Result<MipSolution, String> {
+        self.solve(model, params)
+    }
+
+    /// Whether the solver can run on this machine, and if not, why.
+    fn available(&self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+/// Holds the model, parameters and solution of a one-shot `Backend` between stages.
+pub struct Staged<B> {
+    backend: B,
+    params: Params,
+    model: Option<Model>,
+    solution: Option<MipSolution>,
+    progress: Option<ProgressFn>,
+}
+
+impl<B: Backend> Staged<B> {
+    pub fn new(backend: B) -> Staged<B> {
+        Staged {
+            backend: backend,
+            params: Params::default(),
+            model: None,
+            solution: None,
+            progress: None,
+        }
+    }
+}
+
+impl<B: Backend> SolverBackend for Staged<B> {
+    fn name(&self) -> &'static str {
+        self.backend.name()
+    }
+
+    fn build_model(&mut self, model: Model) -> Result<(), String> {
+        self.model = Some(model);
+        self.solution = None;
+        Ok(())
+    }
+
+    fn set_param(&mut self, name: &str, value: &str) -> Result<(), String> {
+        if self.params.set(name, value)? {
+            Ok(())
+        } else {
+            self.backend.set_param(name, value)
+        }
+    }
+
+    fn solve(&mut self) -> Result<(), String> {
+        let sol = {
+            let model = self.model.as_ref().ok_or_else(|| "no model to solve".to_string())?;
+            let sol = match self.progress {
+                Some(ref mut progress) => {
+                    let mut report = |p: &Progress| {
+                        let mut p = p.clone();
+                        p.sets = p.values.as_ref().and_then(|values| model.selected(values));
+                        progress(&p)
+                    };
+                    self.backend.solve_with_progress(model, &self.params, &mut report)?
+                }
+                None => self.backend.solve(model, &self.params)?,
+            };
+            MipSolution { sets: model.selected(&sol.values), ..sol }
+        };
+        self.solution = Some(sol);
+        Ok(())
+    }
+
+    fn extract_solution(&self) -> Result<MipSolution, String> {
+        self.solution.clone().ok_or_else(|| "the model has not been solved".to_string())
+    }
+
+    fn set_rhs(&mut self, row: &str, rhs: f64) -> Result<(), String> {
+        let model = self.model.as_mut().ok_or_else(|| "no model to change".to_string())?;
+        match model.rows.iter_mut().find(|r| r.name == row) {
+            Some(r) => r.rhs = rhs,
+            None => return Err(format!("the model has no row {}", row)),
+        }
+        self.solution = None;
+        Ok(())
+    }
+
+    fn set_start(&mut self, values: Vec<f64>) -> Result<(), String> {
+        let model = self.model.as_mut().ok_or_else(|| "no model to change".to_string())?;
+        if values.len() != model.vars.len() {
+            return Err(format!("the start has {} values, not one per variable, {}",
+                               values.len(),
+                               model.vars.len()));
+        }
+        model.start = Some(values);
+        self.solution = None;
+        Ok(())
+    }
+
+    fn available(&self) -> Result<(), String> {
+        self.backend.available()
+    }
+
+    fn set_progress(&mut self, progress: ProgressFn) {
+        self.progress = Some(progress);
+    }
+}
+
+/// Creates a fresh backend.
+pub type Factory = fn() -> Box<dyn SolverBackend>;
+
+fn staged<B: Backend + Default + 'static>() -> Box<dyn SolverBackend> {
+    Box::new(Staged::new(B::default()))
+}
+
+/// Backends that are only compiled in with the cargo feature of the same name.
+pub const FEATURE_BACKENDS: &'static [&'static str] = &["cplex", "cbc", "highs", "scip", "cpsat"];
+
+/// Backend factories by name, in order of preference.
+pub struct Registry {
+    factories: Vec<(&'static str, Factory)>,
+}
+
+impl Registry {
+    /// An empty registry.
+    pub fn new() -> Registry {
+        Registry { factories: Vec::new() }
+    }
+
+    /// Registers `factory` under `name`, replacing any backend of the same name. New backends
+    /// are least preferred.
+    pub fn register(&mut self, name: &'static str, factory: Factory) {
+        match self.factories.iter().position(|&(n, _)| n == name) {
+            Some(i) => self.factories[i].1 = factory,
+            None => self.factories.push((name, factory)),
+        }
+    }
+
+    /// Creates the backend called `name`.
+    pub fn create(&self, name: &str) -> Result<Box<dyn SolverBackend>, String> {
+        self.factories
+            .iter()
+            .find(|&&(n, _)| n == name)
+            .map(|&(_, factory)| factory())
+            .ok_or_else(|| {
+                format!("unknown backend: {} (available: {})", name, self.names().join(", "))
+            })
+    }
+
+    /// Creates the most preferred backend that is usable on this machine.
+    pub fn first_available(&self) -> Option<Box<dyn SolverBackend>> {
+        self.factories.iter().map(|&(_, factory)| factory()).find(|b| b.available().is_ok())
+    }
+
+    /// The registered names, in order of preference.
+    pub fn names(&self) -> Vec<&'static str> {
+        self.factories.iter().map(|&(n, _)| n).collect()
+    }
+}
+
+impl Default for Registry {
+    /// A registry holding every backend compiled into this build.
+    fn default() -> Registry {
+        let mut registry = Registry::new();
+        #[cfg(feature = "cplex")]
+        registry.register("cplex", staged::<Cplex>);
+        registry.register("gurobi", staged::<Gurobi>);
+        #[cfg(feature = "cbc")]
+        registry.register("cbc", staged::<Cbc>);
+        #[cfg(feature = "highs")]
+        registry.register("highs", staged::<Highs>);
+        #[cfg(feature = "scip")]
+        registry.register("scip", staged::<Scip>);
+        #[cfg(feature = "cpsat")]
+        registry.register("cpsat", staged::<CpSat>);
+        registry.register("glpk", staged::<Glpk>);
+        registry.register("external", staged::<External>);
+        registry
+    }
+}
+
+/// Runs `cmd`, passing its output through to stderr as it comes, so that it stays apart from
+/// the output of `cover`, and calling `on_line` with each line of its stdout, and returns
+/// all it printed there.
+fn run_logged(cmd: &mut Command,
+              program: &str,
+              on_line: &mut dyn FnMut(&str))
+              -> Result<String, String> {
+    let mut child = cmd.stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .map_err(|e| format!("failed to run {}: {}", program, e))?;
+    let mut stdout = String::new();
+    if let Some(out) = child.stdout.take() {
+        for line in BufReader::new(out).lines() {
+            let line = line.map_err(|e| format!("cannot read from {}: {}", program, e))?;
+            eprintln!("{}", line);
+            on_line(&line);
+            stdout.push_str(&line);
+            stdout.push('\n');
+        }
+    }
+    let status = child.wait().map_err(|e| format!("failed to run {}: {}", program, e))?;
+    if status.success() {
+        Ok(stdout)
+    } else {
+        Err(format!("{} exited with {}", program, status))
+    }
+}
+
+/// Checks that `program` is an executable file in one of the `PATH` directories.
+fn on_path(program: &str) -> Result<(), String> {
+    let found = env::var_os("PATH")
+        .is_some_and(|paths| env::split_paths(&paths).any(|dir| dir.join(program).is_file()));
+    if found {
+        Ok(())
+    } else {
+        Err(format!("{} is not on the PATH", program))
+    }
+}
+
+/// Reads a solution file that lists one variable per line as `name value`, optionally
+/// preceded by a column number, as written by Gurobi, CBC, SCIP and most other solvers. The
+/// objective is taken from the last number on a line mentioning the objective value; other
+/// lines are ignored and variables that are not listed are zero.
+fn read_sol(path: &Path, model: &Model) -> Result<MipSolution, String> {
+    let f = File::open(path).map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+    let index = model.vars
+        .iter()
+        .enumerate()
+        .map(|(j, v)| (v.name.as_str(), j))
+        .collect::<HashMap<_, _>>();
+    let mut sol = MipSolution {
+        objective: 0.0,
+        values: vec![0.0; model.vars.len()],
+        optimal: true,
+        bound: None,
+        sets: None,
+    };
+
+    for line in BufReader::new(f).lines() {
+        let line = line.map_err(|e| e.to_string())?;
+        if line.to_lowercase().contains("objective value") {
+            let value = line.split(|c: char| c.is_whitespace() || c == ':' || c == '=')
+                .rfind(|w| !w.is_empty())
+                .and_then(|w| w.parse().ok());
+            sol.objective = value.ok_or_else(|| format!("bad objective: {}", line))?;
+            continue;
+        }
+        if line.trim().starts_with('#') {
+            continue;
+        }
+        let words = line.split_whitespace().take(3).collect::<Vec<_>>();
+        let found = words.iter()
+            .take(2)
+            .position(|w| index.contains_key(w))
+            .and_then(|i| words.get(i + 1).map(|value| (index[words[i]], *value)));
+        if let Some((j, value)) = found {
+            sol.values[j] = value.parse().map_err(|_| format!("bad value: {}", line))?;
+        }
+    }
+
+    Ok(sol)
+}
+
+/// Solves models by trying every value of their integer variables, for testing the models
+/// of small instances without a solver. Fails on continuous variables.
+#[cfg(test)]
+#[derive(Debug, Default)]
+pub struct BruteForce;
+
+#[cfg(test)]
+impl Backend for BruteForce {
+    fn name(&self) -> &'static str {
+        "brute-force"
+    }
+
+    fn solve(&self, model: &Model, _params: &Params) -> Result<MipSolution, String> {
+        if model.vars.iter().any(|var| var.ty == VarType::Continuous) {
+            return Err("brute-force: continuous variables".to_string());
+        }
+        let mut values = model.vars.iter().map(|var| var.lb.ceil()).collect::<Vec<_>>();
+        let mut best: Option<(f64, Vec<f64>)> = None;
+        loop {
+            if model.feasible(&values) {
+                let objective = model.vars.iter().zip(&values).map(|(v, &x)| v.obj * x).sum();
+                let better = best.as_ref().is_none_or(|&(b, _)| {
+                    if model.maximize { objective > b } else { objective < b }
+                });
+                if better {
+                    best = Some((objective, values.clone()));
+                }
+            }
+            // the next assignment, counting with each variable as a digit
+            match (0..values.len()).find(|&j| values[j] + 1.0 <= model.vars[j].ub) {
+                Some(j) => {
+                    values[j] += 1.0;
+                    for i in 0..j {
+                        values[i] = model.vars[i].lb.ceil();
+                    }
+                }
+                None => break,
+            }
+        }
+        let (objective, values) = best.ok_or_else(|| "brute-force: infeasible".to_string())?;
+        Ok(MipSolution {
+            objective: objective,
+            values: values,
+            optimal: true,
+            bound: Some(objective),
+            sets: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn instance(n: usize, sets: Vec<Vec<usize>>) -> Instance {
+        Instance::new((0..n).collect(),
+                      sets.into_iter().map(|s| s.into_iter().collect()).collect())
+    }
+
+    fn knapsack() -> Model {
+        let mut model = Model::new("knapsack", true);
+        let x = model.add_var("x".to_string(), VarType::Binary, 3.0, 0.0, 1.0);
+        let y = model.add_var("y".to_string(), VarType::Integer, 2.0, 0.0, 2.0);
+        model.add_row("size".to_string(), vec![(x, 2.0), (y, -1.0)], Sense::LessEq, 1.0);
+        model
+    }
+
+    #[test]
+    fn feasible_checks_bounds_and_rows() {
+        let model = knapsack();
+        assert!(model.feasible(&[1.0, 1.0]));
+        assert!(!model.feasible(&[1.0, 0.0]));
+        assert!(!model.feasible(&[0.0, 3.0]));
+        assert!(!model.feasible(&[0.0]));
+    }
+
+    #[test]
+    fn write_lp_declares_variables_by_type() {
+        let mut lp = Vec::new();
+        knapsack().write_lp(&mut lp).unwrap();
+        assert_eq!(String::from_utf8(lp).unwrap(),
+                   "\\ knapsack\nMaximize\n obj: + 3 x + 2 y\nSubject To\n size: + 2 x - 1 y <= \
+                    1\nBounds\n 0 <= y <= 2\nBinaries\n x\nGenerals\n y\nEnd\n");
+    }
+
+    #[test]
+    fn staged_solves_and_reads_the_selection() {
+        let inst = instance(4, vec![vec![0, 1], vec![1, 2], vec![2, 3]]);
+        let (model, _) = coverage_model(&inst, 2, VarType::Binary);
+        let mut backend = Staged::new(BruteForce);
+        backend.build_model(model).unwrap();
+        backend.solve().unwrap();
+        let sol = backend.extract_solution().unwrap();
+        assert_eq!(sol.objective, 4.0);
+        assert_eq!(sol.sets, Some(vec![0, 2]));
+        backend.set_rhs("cardinality", 1.0).unwrap();
+        assert!(backend.extract_solution().is_err());
+        backend.solve().unwrap();
+        assert_eq!(backend.extract_solution().unwrap().objective, 2.0);
+    }
+
+    #[test]
+    fn registry_prefers_earlier_backends() {
+        let mut registry = Registry::new();
+        assert!(registry.first_available().is_none());
+        registry.register("glpk", staged::<Glpk>);
+        registry.register("brute-force", staged::<BruteForce>);
+        registry.register("glpk", staged::<BruteForce>);
+        assert_eq!(registry.names(), vec!["glpk", "brute-force"]);
+        assert_eq!(registry.create("glpk").unwrap().name(), "brute-force");
+        assert!(registry.create("cplex").is_err());
+    }
+}
@@ -0,0 +1,88 @@
+use russcip::{Model as ScipModel, ObjSense, Status, VarType as ScipVarType};
+use super::{Backend, Model, MipSolution, Params, Sense, VarType};
+
+/// Solves models in-process with SCIP through russcip. Needs the `scip` feature. SCIP solves
+/// on a single thread here, so `threads` is ignored, and MIP starts are not passed on. SCIP
+/// stops at the memory limit, having no node files. Emphasis and cut settings are ignored.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Scip;
+
+impl Backend for Scip {
+    fn name(&self) -> &'static str {
+        "scip"
+    }
+
+    fn solve(&self, model: &Model, params: &Params) -> Result<MipSolution, String> {
+        let mut m = ScipModel::new()
+            .include_default_plugins()
+            .create_prob(&model.name)
+            .set_obj_sense(if model.maximize {
+                ObjSense::Maximize
+            } else {
+                ObjSense::Minimize
+            });
+        if let Some(limit) = params.time_limit {
+            m = m.set_real_param("limits/time", limit)
+                .map_err(|e| format!("scip: cannot set time limit: {:?}", e))?;
+        }
+        if let Some(gap) = params.gap {
+            m = m.set_real_param("limits/gap", gap)
+                .map_err(|e| format!("scip: cannot set gap: {:?}", e))?;
+        }
+        if let Some(gap) = params.abs_gap {
+            m = m.set_real_param("limits/absgap", gap)
+                .map_err(|e| format!("scip: cannot set absolute gap: {:?}", e))?;
+        }
+        if let Some(seed) = params.seed_i32() {
+            m = m.set_int_param("randomization/randomseedshift", seed)
+                .map_err(|e| format!("scip: cannot set seed: {:?}", e))?;
+        }
+        if let Some(limit) = params.solution_limit {
+            m = m.set_int_param("limits/solutions", limit as i32)
+                .map_err(|e| format!("scip: cannot set solution limit: {:?}", e))?;
+        }
+        if let Some(limit) = params.mem_limit {
+            m = m.set_real_param("limits/memory", limit)
+                .map_err(|e| format!("scip: cannot set memory limit: {:?}", e))?;
+        }
+
+        let vars = model.vars
+            .iter()
+            .map(|var| {
+                let ty = match var.ty {
+                    VarType::Binary => ScipVarType::Binary,
+                    VarType::Integer => ScipVarType::Integer,
+                    VarType::Continuous => ScipVarType::Continuous,
+                };
+                m.add_var(var.lb, var.ub, var.obj, &var.name, ty)
+            })
+            .collect::<Vec<_>>();
+
+        for row in &model.rows {
+            let (lhs, rhs) = match row.sense {
+                Sense::LessEq => (-f64::INFINITY, row.rhs),
+                Sense::Eq => (row.rhs, row.rhs),
+                Sense::GreaterEq => (row.rhs, f64::INFINITY),
+            };
+            let row_vars = row.terms.iter().map(|&(j, _)| vars[j].clone()).collect();
+            let coefs = row.terms.iter().map(|&(_, c)| c).collect::<Vec<_>>();
+            m.add_cons(row_vars, &coefs, lhs, rhs, &row.name);
+        }
+
+        let solved = m.solve();
+        let optimal = match solved.status() {
+            Status::Optimal | Status::GapLimit => true,
+            Status::TimeLimit | Status::MemoryLimit | Status::SolutionLimit => false,
+            status => return Err(format!("scip stopped with status {:?}", status)),
+        };
+        let sol = solved.best_sol().ok_or_else(|| "scip found no solution".to_string())?;
+
+        Ok(MipSolution {
+            objective: solved.obj_val(),
+            values: vars.iter().map(|var| sol.val(var.clone())).collect(),
+            optimal: optimal,
+            bound: None,
+            sets: None,
+        })
+    }
+}
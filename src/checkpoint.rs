@@ -0,0 +1,64 @@
+//! Checkpoints of long solves, from which `cover resume` continues.
+
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use serde_json;
+use Solution;
+
+/// The state of a solve, saved periodically to `<dir>/checkpoint.json`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Checkpoint {
+    /// The command line of the solve, without the program name. It always includes `--seed`,
+    /// so that randomized methods replay the same choices when resumed; their search state is
+    /// not saved, so they restart from the seed.
+    pub args: Vec<String>,
+    /// Seconds spent solving so far, over all runs.
+    pub elapsed: f64,
+    /// The best solution so far.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub incumbent: Option<Solution>,
+    /// Whether the solve ran to completion, in which case resuming only outputs the incumbent.
+    pub finished: bool,
+}
+
+impl Checkpoint {
+    pub fn new(args: Vec<String>) -> Checkpoint {
+        Checkpoint {
+            args: args,
+            elapsed: 0.0,
+            incumbent: None,
+            finished: false,
+        }
+    }
+
+    fn path(dir: &str) -> PathBuf {
+        Path::new(dir).join("checkpoint.json")
+    }
+
+    /// Reads the checkpoint in `dir`.
+    pub fn load(dir: &str) -> Result<Checkpoint, String> {
+        let path = Checkpoint::path(dir);
+        let f = File::open(&path).map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+        serde_json::from_reader(f).map_err(|e| format!("bad checkpoint {}: {}", path.display(), e))
+    }
+
+    /// Writes the checkpoint to `dir`, creating it if needed. The file is replaced atomically,
+    /// so that a job killed while saving keeps its previous checkpoint.
+    pub fn save(&self, dir: &str) -> Result<(), String> {
+        fs::create_dir_all(dir).map_err(|e| format!("cannot create {}: {}", dir, e))?;
+        let path = Checkpoint::path(dir);
+        let tmp = path.with_extension("json.tmp");
+        {
+            let mut f = File::create(&tmp).map_err(|e| e.to_string())?;
+            serde_json::to_writer_pretty(&mut f, self).map_err(|e| e.to_string())?;
+        }
+        fs::rename(&tmp, &path).map_err(|e| e.to_string())
+    }
+
+    /// Keeps `sol` as the incumbent if it is better.
+    pub fn offer(&mut self, sol: &Solution) {
+        if self.incumbent.as_ref().is_none_or(|inc| sol.objective > inc.objective) {
+            self.incumbent = Some(sol.clone());
+        }
+    }
+}
@@ -0,0 +1,103 @@
+//! Covering instances of text corpora, for representative document selection.
+//!
+//! A corpus is a directory of text files, one document per file. Documents are tokenized
+//! into their words, the maximal runs of alphanumeric characters, lowercased.
+
+use std::collections::BTreeSet;
+use std::fs::{self, File};
+use std::io::{BufRead, Read};
+use std::path::Path;
+use Instance;
+
+/// The distinct words of `text`.
+pub fn tokenize(text: &str) -> BTreeSet<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase())
+        .collect()
+}
+
+/// Reads stop words, separated by whitespace, and tokenized as in documents.
+pub fn read_stop_words<R: BufRead>(reader: R) -> Result<BTreeSet<String>, String> {
+    let mut words = BTreeSet::new();
+    for line in reader.lines() {
+        words.extend(tokenize(&line.map_err(|e| e.to_string())?));
+    }
+    Ok(words)
+}
+
+/// Reads the covering instance of the corpus in `dir`, whose sets are the documents, in file
+/// name order, and whose elements are the words of the vocabulary but `stop_words`, in
+/// lexicographic order, each document containing its words. Files that are not valid UTF-8
+/// are read lossily, and subdirectories are ignored. The file names are kept in `set_names`
+/// and the words in `element_names`.
+pub fn read_dir<P: AsRef<Path>>(dir: P, stop_words: &BTreeSet<String>) -> Result<Instance, String> {
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(dir).map_err(|e| e.to_string())? {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        if path.is_file() {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    let mut documents = Vec::new();
+    for path in &paths {
+        let mut bytes = Vec::new();
+        File::open(path)
+            .and_then(|mut f| f.read_to_end(&mut bytes))
+            .map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+        let words = tokenize(&String::from_utf8_lossy(&bytes));
+        documents.push(words.difference(stop_words).cloned().collect::<BTreeSet<_>>());
+    }
+    let vocabulary = documents.iter()
+        .flat_map(|words| words.iter().cloned())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect::<Vec<_>>();
+    let sets = documents.iter()
+        .map(|words| words.iter().map(|w| vocabulary.binary_search(w).unwrap()).collect())
+        .collect();
+    let names = paths.iter()
+        .map(|path| path.file_name().unwrap().to_string_lossy().into_owned())
+        .collect();
+
+    let ground = (0..vocabulary.len()).collect();
+    Ok(Instance {
+        set_names: Some(names),
+        element_names: Some(vocabulary),
+        ..Instance::new(ground, sets)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::io::Cursor;
+
+    #[test]
+    fn tokenizes_lowercased_words() {
+        let words = tokenize("The cat, the HAT -- and 2 cats!");
+        let expected = ["2", "and", "cat", "cats", "hat", "the"];
+        assert_eq!(words, expected.iter().map(|w| w.to_string()).collect());
+    }
+
+    #[test]
+    fn reads_documents_without_stop_words() {
+        let dir = env::temp_dir().join(format!("maxcover-corpus-{}", ::std::process::id()));
+        fs::create_dir_all(dir.join("sub")).unwrap();
+        fs::write(dir.join("b.txt"), "the dog barks").unwrap();
+        fs::write(dir.join("a.txt"), "The cat, the hat").unwrap();
+        let stop_words = read_stop_words(Cursor::new("THE\nand")).unwrap();
+        let inst = read_dir(&dir, &stop_words).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(inst.set_names, Some(vec!["a.txt".to_string(), "b.txt".to_string()]));
+        let vocabulary = ["barks", "cat", "dog", "hat"];
+        assert_eq!(inst.element_names,
+                   Some(vocabulary.iter().map(|w| w.to_string()).collect()));
+        assert_eq!(inst.sets,
+                   vec![vec![1, 3].into_iter().collect(), vec![0, 2].into_iter().collect()]);
+    }
+}
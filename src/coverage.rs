@@ -0,0 +1,423 @@
+use std::collections::BTreeMap;
+use Instance;
+
+/// Dense view of an instance: ground elements are renumbered `0..num_elements` and each set
+/// keeps only the elements that belong to the ground set.
+#[derive(Debug, Clone)]
+pub struct Index {
+    pub num_elements: usize,
+    pub sets: Vec<Vec<usize>>,
+    /// The sets containing each element.
+    pub containing: Vec<Vec<usize>>,
+    /// The weight of each element.
+    pub weights: Vec<f64>,
+    /// The number of selected sets each element must be in to count.
+    pub demands: Vec<usize>,
+    /// The group of each set.
+    pub groups: Vec<usize>,
+    /// Most sets that may be selected from each group, for the groups that have a limit.
+    pub group_limits: Vec<usize>,
+    /// The sets each set conflicts with.
+    pub conflicts: Vec<Vec<usize>>,
+    /// The sets each set needs. `Coverage::allows` does not keep to these, since adding a
+    /// set may allow others.
+    pub prerequisites: Vec<Vec<usize>>,
+    /// Whether each set is required, `Some(true)`, forbidden, `Some(false)`, or neither.
+    pub fixed: Vec<Option<bool>>,
+    /// Number of red elements in `Instance::red`.
+    pub num_red: usize,
+    /// The red elements of each set, by their index in `Instance::red`.
+    pub red: Vec<Vec<usize>>,
+    /// Most red elements the selection may cover.
+    pub red_limit: Option<usize>,
+}
+
+impl Index {
+    pub fn new(inst: &Instance) -> Index {
+        let pos = inst.ground
+            .iter()
+            .enumerate()
+            .map(|(i, &e)| (e, i))
+            .collect::<BTreeMap<_, _>>();
+
+        let sets = inst.sets
+            .iter()
+            .map(|set| set.iter().filter_map(|e| pos.get(e).cloned()).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        let mut containing = vec![Vec::new(); pos.len()];
+        for (i, set) in sets.iter().enumerate() {
+            for &e in set {
+                containing[e].push(i);
+            }
+        }
+
+        let mut conflicts = vec![Vec::new(); sets.len()];
+        for clique in inst.conflicts() {
+            for &i in &clique {
+                conflicts[i].extend(clique.iter().filter(|&&j| j != i));
+            }
+        }
+        for c in &mut conflicts {
+            c.sort();
+            c.dedup();
+        }
+
+        let mut prerequisites = vec![Vec::new(); sets.len()];
+        for (a, b) in inst.prerequisites() {
+            prerequisites[a].push(b);
+        }
+
+        let red_elements = inst.red();
+        let red = inst.sets
+            .iter()
+            .map(|set| {
+                (0..red_elements.len()).filter(|&r| set.contains(&red_elements[r])).collect()
+            })
+            .collect();
+
+        Index {
+            num_elements: pos.len(),
+            sets: sets,
+            containing: containing,
+            weights: inst.weights(),
+            demands: inst.demands(),
+            groups: inst.groups(),
+            group_limits: inst.group_limits(),
+            conflicts: conflicts,
+            prerequisites: prerequisites,
+            fixed: inst.fixed(),
+            num_red: red_elements.len(),
+            red: red,
+            red_limit: inst.red_limit,
+        }
+    }
+
+    pub fn num_sets(&self) -> usize {
+        self.sets.len()
+    }
+}
+
+/// Incremental coverage of a selection of sets. Tracks how many selected sets contain each
+/// element so that marginal gains and losses never rescan the selection. An element counts
+/// once as many selected sets as its demand contain it.
+#[derive(Debug, Clone)]
+pub struct Coverage<'a> {
+    index: &'a Index,
+    counts: Vec<usize>,
+    selected: Vec<bool>,
+    /// Number of selected sets in each limited group.
+    group_counts: Vec<usize>,
+    /// Number of selected sets each set conflicts with.
+    blocked: Vec<usize>,
+    /// Number of selected sets containing each red element.
+    red_counts: Vec<usize>,
+    /// Number of red elements covered.
+    red_covered: usize,
+    size: usize,
+    value: f64,
+}
+
+impl<'a> Coverage<'a> {
+    pub fn new(index: &'a Index) -> Coverage<'a> {
+        Coverage {
+            index: index,
+            counts: vec![0; index.num_elements],
+            selected: vec![false; index.num_sets()],
+            group_counts: vec![0; index.group_limits.len()],
+            blocked: vec![0; index.num_sets()],
+            red_counts: vec![0; index.num_red],
+            red_covered: 0,
+            size: 0,
+            value: 0.0,
+        }
+    }
+
+    pub fn from_sets(index: &'a Index, sets: &[usize]) -> Coverage<'a> {
+        let mut cov = Coverage::new(index);
+        for &s in sets {
+            cov.add(s);
+        }
+        cov
+    }
+
+    /// The selection of the instance's required sets, which heuristics start from.
+    pub fn initial(index: &'a Index) -> Coverage<'a> {
+        let required = (0..index.num_sets())
+            .filter(|&s| index.fixed[s] == Some(true))
+            .collect::<Vec<_>>();
+        Coverage::from_sets(index, &required)
+    }
+
+    pub fn index(&self) -> &'a Index {
+        self.index
+    }
+
+    /// Total weight covered by the current selection.
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    pub fn contains(&self, set: usize) -> bool {
+        self.selected[set]
+    }
+
+    /// Whether selecting `set` as well keeps to the instance's constraints on which sets may
+    /// be selected together. Adding sets never makes another set allowed.
+    pub fn allows(&self, set: usize) -> bool {
+        let g = self.index.groups[set];
+        let red = || self.index.red[set].iter().filter(|&&r| self.red_counts[r] == 0).count();
+        self.index.fixed[set] != Some(false) && self.blocked[set] == 0 &&
+        (g >= self.group_counts.len() || self.group_counts[g] < self.index.group_limits[g]) &&
+        self.index.red_limit.is_none_or(|limit| self.red_covered + red() <= limit)
+    }
+
+    /// Whether `set` may be dropped from the selection, which required sets may not.
+    pub fn removable(&self, set: usize) -> bool {
+        self.index.fixed[set] != Some(true)
+    }
+
+    /// Number of selected sets.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Number of times the selection covers an element beyond the first, over all elements.
+    pub fn overlap(&self) -> usize {
+        self.counts.iter().map(|&c| c.saturating_sub(1)).sum()
+    }
+
+    /// Whether element `e` is covered by the selection, as often as its demand.
+    pub fn covered(&self, e: usize) -> bool {
+        self.counts[e] >= self.index.demands[e]
+    }
+
+    /// The selected sets, in increasing order.
+    pub fn selection(&self) -> Vec<usize> {
+        (0..self.selected.len()).filter(|&s| self.selected[s]).collect()
+    }
+
+    /// The selected sets that may be dropped, in increasing order.
+    pub fn removable_selection(&self) -> Vec<usize> {
+        (0..self.selected.len()).filter(|&s| self.selected[s] && self.removable(s)).collect()
+    }
+
+    /// Coverage gained by adding `set` to the selection. With demands above 1 the coverage is
+    /// not submodular, so gains can grow as the selection does.
+    pub fn gain(&self, set: usize) -> f64 {
+        self.index.sets[set]
+            .iter()
+            .filter(|&&e| self.counts[e] + 1 == self.index.demands[e])
+            .map(|&e| self.index.weights[e])
+            .sum()
+    }
+
+    /// `gain` with the elements weighing `weights` instead, e.g. those of a scenario.
+    pub fn gain_by(&self, set: usize, weights: &[f64]) -> f64 {
+        self.index.sets[set]
+            .iter()
+            .filter(|&&e| self.counts[e] + 1 == self.index.demands[e])
+            .map(|&e| weights[e])
+            .sum()
+    }
+
+    /// `value` with the elements weighing `weights` instead.
+    pub fn value_by(&self, weights: &[f64]) -> f64 {
+        (0..self.counts.len()).filter(|&e| self.covered(e)).map(|e| weights[e]).sum()
+    }
+
+    /// Coverage lost by removing `set` from the selection.
+    pub fn loss(&self, set: usize) -> f64 {
+        self.index.sets[set]
+            .iter()
+            .filter(|&&e| self.counts[e] == self.index.demands[e])
+            .map(|&e| self.index.weights[e])
+            .sum()
+    }
+
+    /// Progress towards the demands lost by removing `set`, where each of its elements that
+    /// is covered at most as often as its demand contributes its weight divided by its
+    /// demand. This is `loss` without demands, and unlike it never shrinks as sets are
+    /// removed.
+    pub fn demand_loss(&self, set: usize) -> f64 {
+        self.index.sets[set]
+            .iter()
+            .filter(|&&e| self.counts[e] <= self.index.demands[e])
+            .map(|&e| self.index.weights[e] / self.index.demands[e] as f64)
+            .sum()
+    }
+
+    /// Progress towards the demands made by adding `set`, where each of its elements that
+    /// is not yet covered contributes its weight divided by its demand. This is `gain`
+    /// without demands, and unlike it never grows with the selection.
+    pub fn demand_gain(&self, set: usize) -> f64 {
+        self.index.sets[set]
+            .iter()
+            .filter(|&&e| !self.covered(e))
+            .map(|&e| self.index.weights[e] / self.index.demands[e] as f64)
+            .sum()
+    }
+
+    /// Weight of the elements of `set` that are not yet covered, which bounds its gain
+    /// however the selection grows. This is `gain` without demands.
+    pub fn potential(&self, set: usize) -> f64 {
+        self.index.sets[set]
+            .iter()
+            .filter(|&&e| !self.covered(e))
+            .map(|&e| self.index.weights[e])
+            .sum()
+    }
+
+    pub fn add(&mut self, set: usize) {
+        assert!(!self.selected[set], "set {} is already selected", set);
+        let gain = self.gain(set);
+        for &e in &self.index.sets[set] {
+            self.counts[e] += 1;
+        }
+        self.selected[set] = true;
+        if let Some(n) = self.group_counts.get_mut(self.index.groups[set]) {
+            *n += 1;
+        }
+        for &t in &self.index.conflicts[set] {
+            self.blocked[t] += 1;
+        }
+        for &r in &self.index.red[set] {
+            if self.red_counts[r] == 0 {
+                self.red_covered += 1;
+            }
+            self.red_counts[r] += 1;
+        }
+        self.size += 1;
+        self.value += gain;
+    }
+
+    pub fn remove(&mut self, set: usize) {
+        assert!(self.selected[set], "set {} is not selected", set);
+        let loss = self.loss(set);
+        for &e in &self.index.sets[set] {
+            self.counts[e] -= 1;
+        }
+        self.selected[set] = false;
+        if let Some(n) = self.group_counts.get_mut(self.index.groups[set]) {
+            *n -= 1;
+        }
+        for &t in &self.index.conflicts[set] {
+            self.blocked[t] -= 1;
+        }
+        for &r in &self.index.red[set] {
+            self.red_counts[r] -= 1;
+            if self.red_counts[r] == 0 {
+                self.red_covered -= 1;
+            }
+        }
+        self.size -= 1;
+        self.value -= loss;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn instance(n: usize, sets: Vec<Vec<usize>>) -> Instance {
+        Instance::new((0..n).collect(),
+                      sets.into_iter().map(|s| s.into_iter().collect()).collect())
+    }
+
+    #[test]
+    fn demands_count_once_met() {
+        let inst = Instance {
+            demands: Some(vec![2, 1, 1]),
+            weights: Some(vec![5.0, 1.0, 1.0]),
+            ..instance(3, vec![vec![0, 1], vec![0, 2], vec![0]])
+        };
+        let index = Index::new(&inst);
+        let mut cov = Coverage::new(&index);
+        assert_eq!(cov.gain(0), 1.0);
+        cov.add(0);
+        assert!(!cov.covered(0));
+        // the second set containing element 0 meets its demand
+        assert_eq!(cov.gain(1), 6.0);
+        assert_eq!(cov.demand_gain(1), 3.5);
+        cov.add(1);
+        assert_eq!(cov.value(), 7.0);
+        assert_eq!(cov.gain(2), 0.0);
+        assert_eq!(cov.loss(0), 6.0);
+        cov.add(2);
+        assert_eq!(cov.loss(0), 1.0);
+        cov.remove(1);
+        assert_eq!(cov.value(), 6.0);
+        assert_eq!(cov.selection(), vec![0, 2]);
+    }
+
+    #[test]
+    fn group_limits_bound_the_selection() {
+        let inst = Instance {
+            groups: Some(vec![0, 0, 1, 1]),
+            group_limits: Some(vec![1]),
+            ..instance(4, vec![vec![0], vec![1], vec![2], vec![3]])
+        };
+        let index = Index::new(&inst);
+        let mut cov = Coverage::new(&index);
+        assert!(cov.allows(1));
+        cov.add(0);
+        assert!(!cov.allows(1));
+        // group 1 has no limit
+        cov.add(2);
+        assert!(cov.allows(3));
+        cov.remove(0);
+        assert!(cov.allows(1));
+    }
+
+    #[test]
+    fn red_limit_counts_red_elements_once() {
+        let red = vec![10, 11].into_iter().collect();
+        let inst = Instance {
+            red: Some(red),
+            red_limit: Some(1),
+            ..instance(3, vec![vec![0, 10], vec![1, 10], vec![2, 11]])
+        };
+        let index = Index::new(&inst);
+        let mut cov = Coverage::new(&index);
+        cov.add(0);
+        // set 1 only covers the red element already covered
+        assert!(cov.allows(1));
+        assert!(!cov.allows(2));
+        cov.add(1);
+        assert_eq!(cov.value(), 2.0);
+        cov.remove(0);
+        assert!(!cov.allows(2));
+        cov.remove(1);
+        assert!(cov.allows(2));
+    }
+
+    #[test]
+    fn conflicts_block_their_cliques() {
+        let inst = Instance {
+            conflicts: Some(vec![vec![0, 1, 2]]),
+            ..instance(4, vec![vec![0], vec![1], vec![2], vec![3]])
+        };
+        let index = Index::new(&inst);
+        let mut cov = Coverage::new(&index);
+        cov.add(1);
+        assert!(!cov.allows(0) && !cov.allows(2));
+        assert!(cov.allows(3));
+        cov.remove(1);
+        assert!(cov.allows(0));
+    }
+
+    #[test]
+    fn fixed_sets_start_and_stay() {
+        let inst = Instance {
+            required: Some(vec![2]),
+            forbidden: Some(vec![0]),
+            ..instance(3, vec![vec![0, 1, 2], vec![1], vec![2]])
+        };
+        let index = Index::new(&inst);
+        let cov = Coverage::initial(&index);
+        assert_eq!(cov.selection(), vec![2]);
+        assert!(cov.removable_selection().is_empty());
+        assert!(!cov.allows(0));
+        assert!(cov.allows(1));
+    }
+}
@@ -0,0 +1,106 @@
+//! Helpers for the solvers that drive CPLEX directly, beyond the `Cplex` backend.
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fs::{self, File};
+use std::io::Write;
+use std::process;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use rplex::*;
+use Instance;
+
+/// Held while an environment is created, as `ILOG_CPLEX_PARAMETER_FILE` is process-wide.
+static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+/// Numbers the parameter files, so that no two environments share one.
+static PARAM_FILES: AtomicUsize = AtomicUsize::new(0);
+
+/// Creates a CPLEX environment using `threads` threads.
+pub fn cplex_env(threads: Option<usize>) -> Result<Env, String> {
+    cplex_env_with(threads, &[])
+}
+
+/// Creates a CPLEX environment with additional parameters given by their `CPXPARAM_*` names.
+/// rplex only wraps a handful of parameters, so the rest are written to a parameter file
+/// that CPLEX reads on environment creation via `ILOG_CPLEX_PARAMETER_FILE`. Environments
+/// are created one at a time, so that each only reads its own file.
+pub fn cplex_env_with(threads: Option<usize>, params: &[(String, String)]) -> Result<Env, String> {
+    let _lock = ENV_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut env = if params.is_empty() {
+        Env::new()?
+    } else {
+        let n = PARAM_FILES.fetch_add(1, Ordering::SeqCst);
+        let path = env::temp_dir().join(format!("maxcover-{}-{}.prm", process::id(), n));
+        {
+            let mut f = File::create(&path).map_err(|e| e.to_string())?;
+            writeln!(f, "CPLEX Parameter File Version 12.6.0.0").map_err(|e| e.to_string())?;
+            for (name, value) in params {
+                writeln!(f, "{} {}", name, value).map_err(|e| e.to_string())?;
+            }
+        }
+        env::set_var("ILOG_CPLEX_PARAMETER_FILE", &path);
+        let env = Env::new();
+        env::remove_var("ILOG_CPLEX_PARAMETER_FILE");
+        let _ = fs::remove_file(&path);
+        env?
+    };
+
+    env.set_param(EnvParam::Threads(threads.unwrap_or(1) as u64))?;
+    env.set_param(EnvParam::ScreenOutput(true))?;
+    Ok(env)
+}
+
+/// Adds the max k-coverage model to `prob` with variables of type `ty`, returning the
+/// element and set variables.
+pub fn build_model(prob: &mut Problem,
+                   inst: &Instance,
+                   k: usize,
+                   ty: VariableType)
+                   -> Result<(Vec<usize>, Vec<usize>), String> {
+    let mut containment = BTreeMap::new();
+
+    prob.set_objective_type(ObjectiveType::Maximize)?;
+
+    let mut element_vars = Vec::with_capacity(inst.ground.len());
+    for (&x, w) in inst.ground.iter().zip(inst.weights()) {
+        let name = format!("e{}", x);
+        element_vars.push(prob.add_variable(Variable::new(ty, w, 0.0, 1.0, name))?);
+    }
+
+    let mut set_vars = Vec::with_capacity(inst.sets.len());
+    for (i, set) in inst.sets.iter().enumerate() {
+        let name = format!("s{}", i);
+        for element in set.iter() {
+            containment.entry(element).or_insert_with(Vec::new).push(i);
+        }
+        set_vars.push(prob.add_variable(Variable::new(ty, 0.0, 0.0, 1.0, name))?);
+    }
+
+    for ((&var, element), r) in element_vars.iter().zip(inst.ground.iter()).zip(inst.demands()) {
+        let name = format!("cover{}", element);
+        let mut con = con!(name: 0.0 <= sum containment.entry(element).or_insert_with(Vec::new).iter().map(|&i| &set_vars[i]));
+        con.add_wvar(WeightedVariable::new_idx(var, -(r as f64)));
+        prob.add_constraint(con)?;
+    }
+    prob.add_constraint(con!("cardinality": (k as f64) >= sum set_vars.iter()))?;
+
+    Ok((element_vars, set_vars))
+}
+
+/// Fixes the given sets in or out of the model with equality constraints.
+pub fn fix_sets(prob: &mut Problem, set_vars: &[usize], sets: &[usize], value: f64) -> Result<(), String> {
+    for &i in sets {
+        let mut con = Constraint::new(ConstraintType::Eq, value, format!("fix{}", i));
+        con.add_wvar(WeightedVariable::new_idx(set_vars[i], 1.0));
+        prob.add_constraint(con)?;
+    }
+    Ok(())
+}
+
+/// The sets whose variables are true in `sol`.
+pub fn selected_sets(sol: &rplex::Solution, set_vars: &[usize]) -> Vec<usize> {
+    (0..set_vars.len())
+        .filter(|&i| sol.variables[set_vars[i]] == VariableValue::Binary(true))
+        .collect()
+}
@@ -0,0 +1,116 @@
+//! Influence maximization under the independent cascade model, by reverse-reachable sets.
+//!
+//! An RR set is the set of nodes that reach a random root over a random sample of the live
+//! edges, each edge being live with its propagation probability. The expected spread of
+//! seeds is the number of nodes times the probability that they hit an RR set, so
+//! maximizing the spread over many sampled RR sets is max coverage, with a set of RR sets
+//! per node (Borgs et al., 2014; Tang et al., 2014).
+//!
+//! The edge list format has one directed edge per line, given as the source node, the
+//! target node and optionally the probability that the source activates the target,
+//! separated by whitespace. Edges without a probability get 1 over the in-degree of their
+//! target, as in the weighted cascade model. Nodes are numbered from 0, blank lines and
+//! lines starting with `#` are ignored.
+
+use std::collections::{BTreeSet, VecDeque};
+use std::io::BufRead;
+use rand::Rng;
+use Instance;
+
+/// A directed graph with propagation probabilities, kept as the in-edges of each node, which
+/// RR sets are sampled over.
+#[derive(Debug, Clone)]
+pub struct Graph {
+    /// The source and probability of each edge into each node.
+    pub in_edges: Vec<Vec<(usize, f64)>>,
+}
+
+impl Graph {
+    /// Reads a graph in the edge list format.
+    pub fn read<R: BufRead>(reader: R) -> Result<Graph, String> {
+        let mut edges = Vec::new();
+        for line in reader.lines() {
+            let line = line.map_err(|e| e.to_string())?;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+            if fields.len() != 2 && fields.len() != 3 {
+                return Err(format!("bad edge: {}", line));
+            }
+            let node = |tok: &str| {
+                tok.parse::<usize>().map_err(|e| format!("bad node {}: {}", tok, e))
+            };
+            let p = match fields.get(2) {
+                Some(tok) => {
+                    let p = tok.parse::<f64>()
+                        .map_err(|e| format!("bad probability {}: {}", tok, e))?;
+                    if !(0.0..=1.0).contains(&p) {
+                        return Err(format!("probability must be in [0, 1], not {}", p));
+                    }
+                    Some(p)
+                }
+                None => None,
+            };
+            edges.push((node(fields[0])?, node(fields[1])?, p));
+        }
+
+        let n = edges.iter().map(|&(u, v, _)| u.max(v) + 1).max().unwrap_or(0);
+        let mut in_edges = vec![Vec::new(); n];
+        for &(u, v, p) in &edges {
+            in_edges[v].push((u, p));
+        }
+        let in_edges = in_edges.into_iter()
+            .map(|edges| {
+                let weighted = 1.0 / edges.len() as f64;
+                edges.into_iter().map(|(u, p)| (u, p.unwrap_or(weighted))).collect()
+            })
+            .collect();
+        Ok(Graph { in_edges: in_edges })
+    }
+
+    pub fn num_nodes(&self) -> usize {
+        self.in_edges.len()
+    }
+
+    /// Samples an RR set: the nodes that reach a uniformly random root over the live edges,
+    /// found by a breadth-first search backwards from the root that flips each edge it
+    /// meets.
+    pub fn sample_rr<R: Rng>(&self, rng: &mut R) -> Vec<usize> {
+        let root = rng.gen_range(0, self.num_nodes());
+        let mut reached = BTreeSet::new();
+        reached.insert(root);
+        let mut queue = VecDeque::new();
+        queue.push_back(root);
+        while let Some(v) = queue.pop_front() {
+            for &(u, p) in &self.in_edges[v] {
+                if !reached.contains(&u) && rng.gen::<f64>() < p {
+                    reached.insert(u);
+                    queue.push_back(u);
+                }
+            }
+        }
+        reached.into_iter().collect()
+    }
+}
+
+/// The max coverage instance of `samples` RR sets of `graph`: its elements are the RR sets
+/// and its sets the nodes, each containing the RR sets it is in. Every RR set weighs the
+/// number of nodes over `samples`, so that the weight covered by some seeds estimates their
+/// expected spread.
+pub fn rr_instance<R: Rng>(graph: &Graph, samples: usize, rng: &mut R) -> Instance {
+    assert!(graph.num_nodes() > 0, "the graph has no nodes");
+    assert!(samples > 0, "at least one RR set is needed");
+    let mut sets = vec![BTreeSet::new(); graph.num_nodes()];
+    for r in 0..samples {
+        for v in graph.sample_rr(rng) {
+            sets[v].insert(r);
+        }
+    }
+
+    Instance {
+        weights: Some(vec![graph.num_nodes() as f64 / samples as f64; samples]),
+        ..Instance::new((0..samples).collect(), sets)
+    }
+}
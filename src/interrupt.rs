@@ -0,0 +1,34 @@
+//! Cooperative interruption. The CLI raises the flag on Ctrl-C, and long-running methods check
+//! it and stop early with the best solution they have.
+
+use std::process;
+use std::sync::atomic::{AtomicBool, Ordering};
+use ctrlc;
+
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+/// Exit status of a command stopped by Ctrl-C.
+pub const EXIT_STATUS: i32 = 130;
+
+/// Makes Ctrl-C raise the flag, after printing `msg` to stderr, and a second Ctrl-C exit at
+/// once with `EXIT_STATUS`.
+pub fn install_interrupt(msg: &'static str) {
+    ctrlc::set_handler(move || {
+        if interrupted() {
+            process::exit(EXIT_STATUS);
+        }
+        eprintln!("{}", msg);
+        interrupt();
+    })
+        .unwrap();
+}
+
+/// Asks running methods to stop.
+pub fn interrupt() {
+    INTERRUPTED.store(true, Ordering::SeqCst);
+}
+
+/// Whether `interrupt` has been called.
+pub fn interrupted() -> bool {
+    INTERRUPTED.load(Ordering::SeqCst)
+}
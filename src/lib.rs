@@ -0,0 +1,2118 @@
+// Field names are written out and consts spell out their `'static` lifetimes, as the crate
+// always has. Loops run over set and element ids, which index several vectors at once, and
+// `!(x >= 0.0)` rejects NaN along with the negative values.
+#![allow(clippy::redundant_field_names, clippy::redundant_static_lifetimes,
+         clippy::needless_range_loop, clippy::neg_cmp_op_on_partial_ord)]
+
+#[macro_use]
+extern crate serde_derive;
+extern crate ctrlc;
+extern crate serde_json;
+extern crate rand;
+extern crate toml;
+#[cfg(feature = "cplex")]
+#[macro_use]
+extern crate rplex;
+#[cfg(feature = "cbc")]
+extern crate coin_cbc;
+#[cfg(feature = "cpsat")]
+extern crate cp_sat;
+#[cfg(feature = "highs")]
+extern crate highs;
+#[cfg(feature = "scip")]
+extern crate russcip;
+
+use std::cmp;
+use std::iter;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::str::FromStr;
+use std::fs::File;
+use rand::{sample, Rng, SeedableRng, StdRng};
+use rand::distributions::{Range, IndependentSample, LogNormal};
+use backend::{Sense, SolverBackend, VarType};
+use coverage::{Coverage, Index};
+use reductions::{Graph, GraphModel, Problem};
+
+pub mod backend;
+pub mod checkpoint;
+pub mod corpus;
+pub mod coverage;
+#[cfg(feature = "cplex")]
+mod cplex;
+pub mod influence;
+pub mod interrupt;
+pub mod maxsat;
+pub mod opb;
+pub mod perturb;
+pub mod reductions;
+pub mod solvers;
+pub mod stream;
+pub mod submodular;
+pub mod suite;
+pub mod transactions;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Instance {
+    pub ground: BTreeSet<usize>,
+    pub sets: Vec<BTreeSet<usize>>,
+    /// Nonnegative weight of each ground element, in order, which the objective sums over
+    /// the covered elements. Every element weighs 1 without them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weights: Option<Vec<f64>>,
+    /// Probability that each set covers each of its elements, one vector per set in the
+    /// order of its elements. Every set surely covers its elements without them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub probabilities: Option<Vec<Vec<f64>>>,
+    /// Nonnegative profit of covering each element by each set, one vector per set in the
+    /// order of its elements. An element is then worth the most profit of the selected sets
+    /// containing it, rather than its weight.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profits: Option<Vec<Vec<f64>>>,
+    /// Label of each ground element, in order, such as a demographic group, which
+    /// `min_coverage` asks a fair share of the coverage for.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub labels: Option<Vec<usize>>,
+    /// Least fraction of the weight of each label's elements that the selection must cover,
+    /// by label; labels past the end need none.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_coverage: Option<Vec<f64>>,
+    /// Alternative weights of the ground elements, one vector per scenario in the order of
+    /// the ground set, for the robust objective: the least weight covered in any scenario.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scenarios: Option<Vec<Vec<f64>>>,
+    /// Nonnegative penalty of each ground element, in order, which the prize-collecting
+    /// objective charges when the element is left uncovered. Elements cost nothing to leave
+    /// uncovered without them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub penalties: Option<Vec<f64>>,
+    /// Number of selected sets each ground element must be in, in order, to count as
+    /// covered. Every element needs 1 without them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub demands: Option<Vec<usize>>,
+    /// Nonnegative cost of each set, which a budget bounds the total of. Every set costs 1
+    /// without them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub costs: Option<Vec<f64>>,
+    /// Most elements each set may serve, in order. With them, an element only counts if it
+    /// is assigned to a selected set containing it, within the set's capacity.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capacities: Option<Vec<usize>>,
+    /// The time windows each set is active in, numbered from 0, one list per set. A set then
+    /// only covers its elements in its windows.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub windows: Option<Vec<Vec<usize>>>,
+    /// Group of each set, in order, which `group_limits` bound the selections from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub groups: Option<Vec<usize>>,
+    /// Most sets that may be selected from each group, by group; groups past the end are
+    /// unlimited.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group_limits: Option<Vec<usize>>,
+    /// Cliques of mutually exclusive sets, like alternative configurations of the same
+    /// sensor, of which at most one may be selected each.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub conflicts: Option<Vec<Vec<usize>>>,
+    /// Pairs `[a, b]` of sets where `a` may only be selected if `b` is, like a deployment
+    /// that builds on an earlier one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prerequisites: Option<Vec<(usize, usize)>>,
+    /// Sets every selection must include.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub required: Option<Vec<usize>>,
+    /// Sets no selection may include.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub forbidden: Option<Vec<usize>>,
+    /// Red elements, which sets may contain besides those of the ground set. They count
+    /// nothing towards the objective, and `red_limit` caps how many the selection covers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub red: Option<BTreeSet<usize>>,
+    /// Most red elements that the selected sets may cover together.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub red_limit: Option<usize>,
+    /// Position of each ground element in the plane, in order, for geometric instances.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coordinates: Option<Vec<(f64, f64)>>,
+    /// Center of each set in the plane, in order, for geometric instances whose sets are the
+    /// elements within a radius of their center.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub centers: Option<Vec<(f64, f64)>>,
+    /// Structure that the instance is known to have, which solvers may exploit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub structure: Option<Structure>,
+    /// Label of each set, in order, for instances read from labeled data.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub set_names: Option<Vec<String>>,
+    /// Label of each ground element, in order, for instances read from labeled data.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub element_names: Option<Vec<String>>,
+    /// How `generate` made the instance, for instances it made.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub generator: Option<Generator>,
+}
+
+/// A structure of the sets of an instance.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Structure {
+    /// The elements lie on a line in ground set order, and every set is an interval of
+    /// consecutive elements, which makes max coverage solvable in polynomial time.
+    #[serde(rename = "interval")]
+    Interval,
+}
+
+/// The seed and parameters of a generated instance, which `Generator::generate` makes again
+/// exactly, on the same platform.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Generator {
+    pub seed: usize,
+    pub elements: usize,
+    pub sets: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_size: Option<usize>,
+    /// Probability of each set containing each element, for `generate_bipartite` instead of
+    /// `generate`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub density: Option<f64>,
+    /// Exponent of the power law of the set sizes, for `generate_power_law` instead of
+    /// `generate`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub power_law: Option<f64>,
+    /// Exponent of the Zipf law of the elements' popularity for `generate_power_law`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub zipf: Option<f64>,
+    /// Radius of the disks of `generate_geometric` instead of `generate`, or of the sensors of
+    /// `generate_grid` with `grid`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub radius: Option<f64>,
+    /// Rows and columns of the grid of `generate_grid` instead of `generate`, with `elements`
+    /// their product.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grid: Option<(usize, usize)>,
+    /// Shape of the sensors of `generate_grid`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sensor: Option<Sensor>,
+    /// Whether the sets are the intervals of `generate_intervals` instead of `generate`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub intervals: Option<bool>,
+    /// Number of communities of `generate_communities` instead of `generate`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub communities: Option<usize>,
+    /// Probability of each element of a set coming from outside its community, for
+    /// `generate_communities`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mixing: Option<f64>,
+    /// Probability of a set of `generate_nested` keeping each element of its parent, for
+    /// `generate_nested` instead of `generate`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nested: Option<f64>,
+    /// Random graph model of the graph whose closed neighborhoods are the sets, for
+    /// `Graph::random` instead of `generate`, with `elements` nodes, as many as `sets`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub graph: Option<GraphModel>,
+    /// Average degree of the graph of `graph`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub degree: Option<f64>,
+    /// Number of disjoint sets planted among the others by `plant`, if any, which cover the
+    /// ground set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub planted: Option<usize>,
+    /// The `k` of `generate_adversarial`, which then makes the instance instead, with its
+    /// optimal sets as the planted solution.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub adversarial: Option<usize>,
+    /// Distribution of the element weights drawn by `sample_values`, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weights: Option<Distribution>,
+    /// Distribution of the set costs drawn by `sample_values`, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub costs: Option<Distribution>,
+    /// Number of weight scenarios sampled with `sample_scenarios`, with `presence`, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scenarios: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub presence: Option<f64>,
+}
+
+impl Generator {
+    /// Checks that the parameters make sense together, naming them by the flags of `cover
+    /// generate`: that they choose at most one family of instances, that the parameters of a
+    /// family are only given with it, and that the values are in range.
+    pub fn validate(&self) -> Result<(), String> {
+        let families = [("--density", self.density.is_some()),
+                        ("--power-law", self.power_law.is_some()),
+                        ("--radius", self.radius.is_some() && self.grid.is_none()),
+                        ("--grid", self.grid.is_some()),
+                        ("--communities", self.communities.is_some()),
+                        ("--nested", self.nested.is_some()),
+                        ("--intervals", self.intervals == Some(true)),
+                        ("--model graph", self.graph.is_some()),
+                        ("--adversarial", self.adversarial.is_some())];
+        let chosen = families.iter()
+            .filter(|&&(_, given)| given)
+            .map(|&(flag, _)| flag)
+            .collect::<Vec<_>>();
+        if chosen.len() > 1 {
+            return Err(format!("{} cannot be combined with {}", chosen[0], chosen[1]));
+        }
+        let family = chosen.first().cloned();
+
+        // each parameter that is given only with the one after it
+        let needs = [("--zipf", self.zipf.is_some(), "--power-law", self.power_law.is_some()),
+                     ("--sensor", self.sensor.is_some(), "--grid", self.grid.is_some()),
+                     ("--grid", self.grid.is_some(), "--radius", self.radius.is_some()),
+                     ("--mixing",
+                      self.mixing.is_some(),
+                      "--communities",
+                      self.communities.is_some()),
+                     ("--degree", self.degree.is_some(), "--model graph", self.graph.is_some()),
+                     ("--presence",
+                      self.presence.is_some(),
+                      "--scenarios",
+                      self.scenarios.is_some())];
+        for &(flag, given, needed, present) in &needs {
+            if given && !present {
+                return Err(format!("{} only applies with {}", flag, needed));
+            }
+        }
+        // each parameter with the families it does not apply to
+        let max_size = ["--density", "--radius", "--grid", "--model graph", "--adversarial"];
+        let planted = ["--radius",
+                       "--grid",
+                       "--communities",
+                       "--intervals",
+                       "--model graph",
+                       "--adversarial"];
+        let exclusions = [("--max-size", self.max_size.is_some(), &max_size[..]),
+                          ("--planted", self.planted.is_some(), &planted[..]),
+                          ("--weights", self.weights.is_some(), &["--adversarial"][..])];
+        for &(flag, given, excluded) in &exclusions {
+            if let Some(family) = family.filter(|family| given && excluded.contains(family)) {
+                return Err(format!("{} cannot be combined with {}", flag, family));
+            }
+        }
+
+        let probabilities = [("--density", self.density),
+                             ("--mixing", self.mixing),
+                             ("--nested", self.nested),
+                             ("--presence", self.presence)];
+        for &(flag, p) in &probabilities {
+            if let Some(p) = p.filter(|&p| !(0.0..=1.0).contains(&p)) {
+                return Err(format!("{} must be in [0, 1], not {}", flag, p));
+            }
+        }
+        if let Some(r) = self.radius.filter(|&r| !(r >= 0.0)) {
+            return Err(format!("--radius must be at least 0, not {}", r));
+        }
+        if self.intervals == Some(true) && self.elements == 0 {
+            return Err("--intervals needs at least one element".to_string());
+        }
+        if let Some(c) = self.communities.filter(|&c| c == 0 || c > self.elements) {
+            return Err(format!("cannot split {} elements into {} communities", self.elements, c));
+        }
+        if let Some(k) = self.planted.filter(|&k| k == 0 || k > self.sets || k > self.elements) {
+            return Err(format!("cannot plant {} of {} sets over {} elements",
+                               k,
+                               self.sets,
+                               self.elements));
+        }
+        if let Some((rows, cols)) = self.grid {
+            if self.elements != rows * cols {
+                return Err(format!("a {}x{} grid has {} elements, not {}",
+                                   rows,
+                                   cols,
+                                   rows * cols,
+                                   self.elements));
+            }
+        }
+        if self.graph.is_some() && self.elements != self.sets {
+            return Err("a graph has as many sets as elements".to_string());
+        }
+        if let Some(k) = self.adversarial {
+            if k == 0 || k > 13 {
+                return Err(format!("--adversarial must be from 1 to 13, not {}", k));
+            }
+            if (self.elements, self.sets) != (k * (k + 1), 2 * k) {
+                return Err(format!("the adversarial instance of {} has {} elements and {} sets",
+                                   k,
+                                   k * (k + 1),
+                                   2 * k));
+            }
+        }
+        Ok(())
+    }
+
+    /// Generates the instance, seeding the random number generator with `seed`, and records
+    /// the generator in it. With `planted`, the planted solution, which is optimal for any
+    /// `k` of at least `planted`, is returned too. Panics if the parameters do not
+    /// `validate`.
+    pub fn generate(&self) -> (Instance, Option<Solution>) {
+        if let Err(e) = self.validate() {
+            panic!("{}", e);
+        }
+        let mut rng = StdRng::from_seed(&[self.seed][..]);
+        let (mut inst, planted) = match self.adversarial {
+            Some(k) => (generate_adversarial(k), Some((k..2 * k).collect())),
+            None => {
+                let mut inst = self.random(self.sets - self.planted.unwrap_or(0), &mut rng);
+                let planted = self.planted.map(|k| plant(&mut inst, k, &mut rng));
+                (inst, planted)
+            }
+        };
+        if let Some(dist) = self.weights {
+            let mut frequencies = inst.ground.iter().map(|&x| (x, 0)).collect::<HashMap<_, _>>();
+            for &x in inst.sets.iter().flat_map(|set| set.iter()) {
+                *frequencies.get_mut(&x).unwrap() += 1;
+            }
+            let sizes = inst.ground.iter().map(|x| frequencies[x]).collect::<Vec<_>>();
+            inst.weights = Some(sample_values(dist, &sizes, &mut rng));
+        }
+        if let Some(dist) = self.costs {
+            let sizes = inst.sets.iter().map(|set| set.len()).collect::<Vec<_>>();
+            inst.costs = Some(sample_values(dist, &sizes, &mut rng));
+        }
+        let planted = planted.map(|sets: Vec<usize>| {
+            let objective = inst.weights().iter().sum::<f64>();
+            let mut sol = Solution::new(objective, sets);
+            sol.bound = Some(objective);
+            sol.gap = Some(0.0);
+            sol
+        });
+        if let Some(n) = self.scenarios {
+            let presence = self.presence.unwrap_or(0.5);
+            inst.scenarios = Some(sample_scenarios(&inst, n, presence, &mut rng));
+        }
+        inst.generator = Some(self.clone());
+        (inst, planted)
+    }
+
+    /// A random instance of `num_sets` sets from the family that the parameters choose.
+    fn random<R: Rng>(&self, num_sets: usize, rng: &mut R) -> Instance {
+        match (self.density, self.power_law, self.radius) {
+            _ if self.grid.is_some() => {
+                let (rows, cols) = self.grid.unwrap();
+                let sensor = self.sensor.unwrap_or(Sensor::Ball);
+                generate_grid(rows, cols, num_sets, sensor, self.radius.unwrap(), rng)
+            }
+            _ if self.graph.is_some() => {
+                let degree = self.degree.unwrap_or(4.0);
+                Graph::random(self.graph.unwrap(), self.elements, degree, rng)
+                    .instance(Problem::DominatingSet)
+            }
+            (Some(p), _, _) => generate_bipartite(self.elements, num_sets, p, rng),
+            (None, Some(a), _) => {
+                let zipf = self.zipf.unwrap_or(1.0);
+                generate_power_law(self.elements, num_sets, self.max_size, a, zipf, rng)
+            }
+            (None, None, Some(r)) => generate_geometric(self.elements, num_sets, r, rng),
+            _ if self.communities.is_some() => {
+                let c = self.communities.unwrap();
+                let mixing = self.mixing.unwrap_or(0.1);
+                generate_communities(self.elements, num_sets, self.max_size, c, mixing, rng)
+            }
+            _ if self.intervals == Some(true) => {
+                generate_intervals(self.elements, num_sets, self.max_size, rng)
+            }
+            _ if self.nested.is_some() => {
+                let keep = self.nested.unwrap();
+                generate_nested(self.elements, num_sets, self.max_size, keep, rng)
+            }
+            (None, None, None) => generate(self.elements, num_sets, self.max_size, rng),
+        }
+    }
+}
+
+impl Instance {
+    /// An instance of just `sets` over `ground`, with none of the optional fields, which
+    /// instances that have some of them are built from, as in
+    /// `Instance { weights: Some(weights), ..Instance::new(ground, sets) }`.
+    pub fn new(ground: BTreeSet<usize>, sets: Vec<BTreeSet<usize>>) -> Instance {
+        Instance {
+            ground: ground,
+            sets: sets,
+            weights: None,
+            labels: None,
+            min_coverage: None,
+            probabilities: None,
+            profits: None,
+            scenarios: None,
+            penalties: None,
+            demands: None,
+            costs: None,
+            capacities: None,
+            windows: None,
+            groups: None,
+            group_limits: None,
+            conflicts: None,
+            prerequisites: None,
+            required: None,
+            forbidden: None,
+            red: None,
+            red_limit: None,
+            coordinates: None,
+            centers: None,
+            structure: None,
+            set_names: None,
+            element_names: None,
+            generator: None,
+        }
+    }
+
+    /// The weight of each ground element, in order.
+    pub fn weights(&self) -> Vec<f64> {
+        match self.weights {
+            Some(ref weights) => {
+                assert_eq!(weights.len(),
+                           self.ground.len(),
+                           "there must be one weight per ground element");
+                weights.clone()
+            }
+            None => vec![1.0; self.ground.len()],
+        }
+    }
+
+    /// The profit of each set for each of its elements, which is the element's weight
+    /// without profits, and 0 for elements outside the ground set.
+    pub fn profits(&self) -> Vec<Vec<f64>> {
+        match self.profits {
+            Some(ref profits) => {
+                assert_eq!(profits.len(),
+                           self.sets.len(),
+                           "there must be profits for every set");
+                assert!(profits.iter().zip(&self.sets).all(|(p, set)| p.len() == set.len()),
+                        "there must be one profit per element of each set");
+                profits.clone()
+            }
+            None => {
+                let weights = self.ground.iter().zip(self.weights()).collect::<HashMap<_, _>>();
+                self.sets
+                    .iter()
+                    .map(|set| set.iter().map(|x| weights.get(x).map_or(0.0, |&w| w)).collect())
+                    .collect()
+            }
+        }
+    }
+
+    /// The probability that each set covers each of its elements, all 1 without them.
+    pub fn probabilities(&self) -> Vec<Vec<f64>> {
+        match self.probabilities {
+            Some(ref probabilities) => {
+                assert_eq!(probabilities.len(),
+                           self.sets.len(),
+                           "there must be probabilities for every set");
+                assert!(probabilities.iter().zip(&self.sets).all(|(p, set)| p.len() == set.len()),
+                        "there must be one probability per element of each set");
+                probabilities.clone()
+            }
+            None => self.sets.iter().map(|set| vec![1.0; set.len()]).collect(),
+        }
+    }
+
+    /// The label of each ground element, all 0 without labels.
+    pub fn labels(&self) -> Vec<usize> {
+        match self.labels {
+            Some(ref labels) => {
+                assert_eq!(labels.len(),
+                           self.ground.len(),
+                           "there must be one label per ground element");
+                labels.clone()
+            }
+            None => vec![0; self.ground.len()],
+        }
+    }
+
+    /// The weight each label's covered elements must reach, by label, empty without
+    /// `min_coverage`.
+    pub fn fair_shares(&self) -> Vec<f64> {
+        let fractions = self.min_coverage.clone().unwrap_or_default();
+        let mut shares = vec![0.0; fractions.len()];
+        for (l, w) in self.labels().into_iter().zip(self.weights()) {
+            if l < shares.len() {
+                shares[l] += fractions[l] * w;
+            }
+        }
+        shares
+    }
+
+    /// Checks that `sets` cover the fair share of each label.
+    pub fn check_fairness(&self, sets: &[usize]) -> Result<(), String> {
+        let shares = self.fair_shares();
+        if shares.is_empty() {
+            return Ok(());
+        }
+        let index = Index::new(self);
+        let cov = Coverage::from_sets(&index, sets);
+        let mut covered = vec![0.0; shares.len()];
+        for (e, l) in self.labels().into_iter().enumerate() {
+            if l < covered.len() && cov.covered(e) {
+                covered[l] += index.weights[e];
+            }
+        }
+        match (0..shares.len()).find(|&l| covered[l] < shares[l] - 1e-9) {
+            Some(l) => {
+                Err(format!("label {} has a weight of {} covered, short of its share {}",
+                            l,
+                            covered[l],
+                            shares[l]))
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// The weights of each scenario, just `weights` without scenarios.
+    pub fn scenarios(&self) -> Vec<Vec<f64>> {
+        match self.scenarios {
+            Some(ref scenarios) => {
+                assert!(!scenarios.is_empty(), "there must be at least one scenario");
+                assert!(scenarios.iter().all(|s| s.len() == self.ground.len()),
+                        "every scenario must weigh each ground element");
+                scenarios.clone()
+            }
+            None => vec![self.weights()],
+        }
+    }
+
+    /// The robust objective of `sets`: the least weight they cover in any scenario.
+    pub fn worst_case(&self, sets: &[usize]) -> f64 {
+        let index = Index::new(self);
+        let cov = Coverage::from_sets(&index, sets);
+        self.scenarios().iter().map(|w| cov.value_by(w)).fold(f64::INFINITY, f64::min)
+    }
+
+    /// The penalty of each ground element, in order.
+    pub fn penalties(&self) -> Vec<f64> {
+        match self.penalties {
+            Some(ref penalties) => {
+                assert_eq!(penalties.len(),
+                           self.ground.len(),
+                           "there must be one penalty per ground element");
+                penalties.clone()
+            }
+            None => vec![0.0; self.ground.len()],
+        }
+    }
+
+    /// This instance with each element weighing its weight plus its penalty, whose coverage
+    /// less the cost of the selection and the total penalty is the prize-collecting objective.
+    pub fn prize_instance(&self) -> Instance {
+        let prizes = self.weights().iter().zip(self.penalties()).map(|(w, p)| w + p).collect();
+        Instance { weights: Some(prizes), ..self.clone() }
+    }
+
+    /// The prize-collecting objective of `sets`: the weight they cover, less their cost and
+    /// the penalties of the elements they leave uncovered.
+    pub fn prize(&self, sets: &[usize]) -> f64 {
+        let prizes = self.prize_instance();
+        let covered = Coverage::from_sets(&Index::new(&prizes), sets).value();
+        covered - self.cost(sets) - self.penalties().iter().sum::<f64>()
+    }
+
+    /// The weight `sets` cover less `penalty` for each time they cover an element beyond the
+    /// first.
+    pub fn overlap_penalized(&self, sets: &[usize], penalty: f64) -> f64 {
+        let index = Index::new(self);
+        let cov = Coverage::from_sets(&index, sets);
+        cov.value() - penalty * cov.overlap() as f64
+    }
+
+    /// The demand of each ground element, in order.
+    pub fn demands(&self) -> Vec<usize> {
+        match self.demands {
+            Some(ref demands) => {
+                assert_eq!(demands.len(),
+                           self.ground.len(),
+                           "there must be one demand per ground element");
+                assert!(demands.iter().all(|&r| r >= 1), "demands must be at least 1");
+                demands.clone()
+            }
+            None => vec![1; self.ground.len()],
+        }
+    }
+
+    /// Whether some element needs to be covered more than once.
+    pub fn has_demands(&self) -> bool {
+        self.demands.as_ref().is_some_and(|demands| demands.iter().any(|&r| r > 1))
+    }
+
+    /// The cost of each set.
+    pub fn costs(&self) -> Vec<f64> {
+        match self.costs {
+            Some(ref costs) => {
+                assert_eq!(costs.len(), self.sets.len(), "there must be one cost per set");
+                costs.clone()
+            }
+            None => vec![1.0; self.sets.len()],
+        }
+    }
+
+    /// The capacity of each set, in order, which is its size without capacities.
+    pub fn capacities(&self) -> Vec<usize> {
+        match self.capacities {
+            Some(ref capacities) => {
+                assert_eq!(capacities.len(),
+                           self.sets.len(),
+                           "there must be one capacity per set");
+                capacities.clone()
+            }
+            None => self.sets.iter().map(|set| set.len()).collect(),
+        }
+    }
+
+    /// The windows each set is active in, all in the one window 0 without windows.
+    pub fn windows(&self) -> Vec<Vec<usize>> {
+        match self.windows {
+            Some(ref windows) => {
+                assert_eq!(windows.len(), self.sets.len(), "there must be windows for every set");
+                windows.clone()
+            }
+            None => vec![vec![0]; self.sets.len()],
+        }
+    }
+
+    /// The number of time windows, one past the last that a set is active in.
+    pub fn num_windows(&self) -> usize {
+        self.windows().iter().flat_map(|w| w.iter()).max().map_or(1, |&t| t + 1)
+    }
+
+    /// The group of each set, all 0 without groups.
+    pub fn groups(&self) -> Vec<usize> {
+        match self.groups {
+            Some(ref groups) => {
+                assert_eq!(groups.len(), self.sets.len(), "there must be one group per set");
+                groups.clone()
+            }
+            None => vec![0; self.sets.len()],
+        }
+    }
+
+    /// The limit of each group, empty without limits.
+    pub fn group_limits(&self) -> Vec<usize> {
+        self.group_limits.clone().unwrap_or_default()
+    }
+
+    /// The cliques of mutually exclusive sets, none without conflicts.
+    pub fn conflicts(&self) -> Vec<Vec<usize>> {
+        match self.conflicts {
+            Some(ref conflicts) => {
+                assert!(conflicts.iter().all(|c| c.iter().all(|&i| i < self.sets.len())),
+                        "conflicts must be between sets of the instance");
+                conflicts.clone()
+            }
+            None => Vec::new(),
+        }
+    }
+
+    /// The pairs `(a, b)` of sets where `a` needs `b`.
+    pub fn prerequisites(&self) -> Vec<(usize, usize)> {
+        match self.prerequisites {
+            Some(ref prerequisites) => {
+                let m = self.sets.len();
+                assert!(prerequisites.iter().all(|&(a, b)| a < m && b < m),
+                        "prerequisites must be between sets of the instance");
+                prerequisites.clone()
+            }
+            None => Vec::new(),
+        }
+    }
+
+    /// Whether each set is required, `Some(true)`, forbidden, `Some(false)`, or neither.
+    pub fn fixed(&self) -> Vec<Option<bool>> {
+        let mut fixed = vec![None; self.sets.len()];
+        for &(sets, value) in &[(&self.required, true), (&self.forbidden, false)] {
+            for &i in sets.iter().flat_map(|sets| sets.iter()) {
+                assert!(i < fixed.len(), "set {} is fixed, but there are only {}", i, fixed.len());
+                assert!(fixed[i] != Some(!value), "set {} is both required and forbidden", i);
+                fixed[i] = Some(value);
+            }
+        }
+        fixed
+    }
+
+    /// The sets every selection must include, in order.
+    pub fn required(&self) -> Vec<usize> {
+        let fixed = self.fixed();
+        (0..fixed.len()).filter(|&i| fixed[i] == Some(true)).collect()
+    }
+
+    /// The red elements that some set contains, in order.
+    pub fn red(&self) -> Vec<usize> {
+        let red = match self.red {
+            Some(ref red) => red,
+            None => return Vec::new(),
+        };
+        assert!(red.is_disjoint(&self.ground), "red elements must not be in the ground set");
+        red.iter().cloned().filter(|x| self.sets.iter().any(|set| set.contains(x))).collect()
+    }
+
+    /// Whether the instance restricts which sets may be selected together.
+    pub fn has_selection_constraints(&self) -> bool {
+        self.group_limits.is_some() || self.conflicts.as_ref().is_some_and(|c| !c.is_empty()) ||
+        self.fixed().iter().any(Option::is_some) || self.red_limit.is_some() ||
+        self.has_prerequisites()
+    }
+
+    /// Whether some set needs another, which only `precedence_greedy` and the ilp methods
+    /// keep to.
+    pub fn has_prerequisites(&self) -> bool {
+        self.prerequisites.as_ref().is_some_and(|p| !p.is_empty())
+    }
+
+    /// Checks that `sets` are sets of the instance and keep to its constraints on which sets
+    /// may be selected together, including its required and forbidden sets, but not to `k`
+    /// or a budget.
+    pub fn check_selection(&self, sets: &[usize]) -> Result<(), String> {
+        if let Some(&i) = sets.iter().find(|&&i| i >= self.sets.len()) {
+            return Err(format!("set {} is selected, but there are only {}", i, self.sets.len()));
+        }
+        for (i, fixed) in self.fixed().into_iter().enumerate() {
+            match fixed {
+                Some(true) if !sets.contains(&i) => {
+                    return Err(format!("set {} is required, but not selected", i))
+                }
+                Some(false) if sets.contains(&i) => {
+                    return Err(format!("set {} is forbidden, but selected", i))
+                }
+                _ => {}
+            }
+        }
+        let groups = self.groups();
+        for (g, &limit) in self.group_limits().iter().enumerate() {
+            let n = sets.iter().filter(|&&i| groups[i] == g).count();
+            if n > limit {
+                return Err(format!("{} sets of group {} are selected, more than its limit {}",
+                                   n,
+                                   g,
+                                   limit));
+            }
+        }
+        for clique in self.conflicts() {
+            let selected = clique.iter().filter(|i| sets.contains(i)).collect::<Vec<_>>();
+            if selected.len() > 1 {
+                return Err(format!("sets {} and {} are selected, but conflict",
+                                   selected[0],
+                                   selected[1]));
+            }
+        }
+        for (a, b) in self.prerequisites() {
+            if sets.contains(&a) && !sets.contains(&b) {
+                return Err(format!("set {} is selected, but not set {}, which it needs", a, b));
+            }
+        }
+        if let Some(limit) = self.red_limit {
+            let red = self.red()
+                .into_iter()
+                .filter(|x| sets.iter().any(|&i| self.sets[i].contains(x)))
+                .count();
+            if red > limit {
+                return Err(format!("{} red elements are covered, more than the limit {}",
+                                   red,
+                                   limit));
+            }
+        }
+        Ok(())
+    }
+
+    /// Total cost of the sets `sets`.
+    pub fn cost(&self, sets: &[usize]) -> f64 {
+        let costs = self.costs();
+        sets.iter().map(|&i| costs[i]).sum()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Solution {
+    pub objective: f64,
+    pub sol: Vec<usize>,
+    /// Objective of the LP relaxation, for methods that solve one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub relaxation: Option<f64>,
+    /// Value of the fractional point that was rounded, for methods that keep one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fractional: Option<f64>,
+    /// Proven upper bound on the optimal objective, for methods that compute one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bound: Option<f64>,
+    /// Relative gap `(bound - objective) / objective` achieved by ilp, when its backend reports
+    /// a bound; 0 for proven optima.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gap: Option<f64>,
+    /// The method that produced this solution, when several were tried.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub method: Option<String>,
+    /// `"interrupted"` if the method was stopped early by an interrupt.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    /// Every solution collected by `solve_pool`, best first, when ilp was asked for several.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pool: Option<Vec<Solution>>,
+    /// The sets selected in each scenario once it is known, for two-stage solutions, whose
+    /// `sol` is the sets selected beforehand.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recourse: Option<Vec<Vec<usize>>>,
+    /// The elements each selected set serves, in the order of `sol`, for capacitated
+    /// solutions, or is credited with, for solutions with profits.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assignment: Option<Vec<Vec<usize>>>,
+    /// The sets selected in each time window, for solutions that select per window, whose
+    /// `sol` is every set selected in some window.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schedule: Option<Vec<Vec<usize>>>,
+    /// The selected sets an adversary deletes to leave the least coverage, for interdiction
+    /// solutions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attack: Option<Vec<usize>>,
+}
+
+impl Solution {
+    pub fn new(objective: f64, sol: Vec<usize>) -> Solution {
+        Solution {
+            objective: objective,
+            sol: sol,
+            relaxation: None,
+            fractional: None,
+            bound: None,
+            gap: None,
+            method: None,
+            status: None,
+            pool: None,
+            recourse: None,
+            assignment: None,
+            schedule: None,
+            attack: None,
+        }
+    }
+
+    /// Improves this solution by local search over 1-swap and 2-swap exchanges.
+    pub fn polish_swap(&self, inst: &Instance) -> Solution {
+        solvers::local::swap(inst, self)
+    }
+}
+
+/// Generates a random instance with `num_elements` elements and `num_sets` distinct sets,
+/// drawing from `rng`.
+pub fn generate<R: Rng>(num_elements: usize,
+                        num_sets: usize,
+                        max_size: Option<usize>,
+                        rng: &mut R)
+                        -> Instance {
+    let ground: BTreeSet<usize> = (0..num_elements).collect();
+    let range = Range::new(1, max_size.unwrap_or(num_elements + 1));
+
+    let mut sets = BTreeSet::new();
+
+    for _ in 0..num_sets {
+        loop {
+            let size = range.ind_sample(rng);
+            let set: BTreeSet<usize> =
+                sample(rng, &ground, size).into_iter().copied().collect();
+            if !sets.contains(&set) {
+                sets.insert(set);
+                break;
+            }
+        }
+    }
+
+    Instance::new(ground, sets.into_iter().collect())
+}
+
+/// Generates a random instance with `num_elements` elements and `num_sets` sets, each of
+/// which contains each element independently with probability `density`, as in the
+/// Erdos-Renyi model of bipartite graphs. Sets may be empty or the same.
+pub fn generate_bipartite<R: Rng>(num_elements: usize,
+                                  num_sets: usize,
+                                  density: f64,
+                                  rng: &mut R)
+                                  -> Instance {
+    assert!((0.0..=1.0).contains(&density), "density must be in [0, 1], not {}", density);
+    let sets = (0..num_sets)
+        .map(|_| (0..num_elements).filter(|_| rng.gen::<f64>() < density).collect())
+        .collect();
+    Instance::new((0..num_elements).collect(), sets)
+}
+
+/// Generates a random instance with heavy tails, like tags or keywords: `num_sets` sets whose
+/// sizes `x`, up to `max_size` or the number of elements, are drawn with probability
+/// proportional to `x^-size_exponent`, each holding that many distinct elements drawn with
+/// probability proportional to `r^-zipf` for the element of rank `r`, element 0 being the
+/// most popular. Sets may be the same.
+pub fn generate_power_law<R: Rng>(num_elements: usize,
+                                  num_sets: usize,
+                                  max_size: Option<usize>,
+                                  size_exponent: f64,
+                                  zipf: f64,
+                                  rng: &mut R)
+                                  -> Instance {
+    assert!(num_elements > 0, "there must be elements to draw sets from");
+    let max_size = max_size.unwrap_or(num_elements).min(num_elements).max(1);
+    // cumulative weights of the sizes 1..max_size
+    let mut cumulative = Vec::with_capacity(max_size);
+    let mut total = 0.0;
+    for x in 1..max_size + 1 {
+        total += (x as f64).powf(-size_exponent);
+        cumulative.push(total);
+    }
+    let popularity = (0..num_elements)
+        .map(|r| (r as f64 + 1.0).powf(-zipf))
+        .collect::<Vec<_>>();
+
+    let mut sets = Vec::with_capacity(num_sets);
+    for _ in 0..num_sets {
+        let u = rng.gen::<f64>() * total;
+        let size = cumulative.iter().position(|&c| u < c).unwrap_or(max_size - 1) + 1;
+        // weighted sampling without replacement: the elements with the largest keys
+        // u^(1/w), compared by their logarithms (Efraimidis and Spirakis, 2006)
+        let mut keys = popularity.iter()
+            .enumerate()
+            .map(|(e, &w)| (rng.gen::<f64>().ln() / w, e))
+            .collect::<Vec<_>>();
+        keys.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(cmp::Ordering::Equal));
+        sets.push(keys.into_iter().take(size).map(|(_, e)| e).collect());
+    }
+
+    Instance::new((0..num_elements).collect(), sets)
+}
+
+/// Generates a random geometric instance, of sensors placed over an area: `num_elements`
+/// points drawn uniformly from the unit square, and `num_sets` disks of radius `radius` around
+/// centers drawn the same way, each holding the points within it. The points are recorded in
+/// `coordinates` and the centers in `centers`.
+pub fn generate_geometric<R: Rng>(num_elements: usize,
+                                  num_sets: usize,
+                                  radius: f64,
+                                  rng: &mut R)
+                                  -> Instance {
+    assert!(radius >= 0.0, "radius must be at least 0, not {}", radius);
+    let mut point = || (rng.gen::<f64>(), rng.gen::<f64>());
+    let points = (0..num_elements).map(|_| point()).collect::<Vec<_>>();
+    let centers = (0..num_sets).map(|_| point()).collect::<Vec<_>>();
+    let sets = centers.iter()
+        .map(|&(cx, cy)| {
+            (0..num_elements)
+                .filter(|&e| {
+                    let (x, y) = points[e];
+                    (x - cx).powi(2) + (y - cy).powi(2) <= radius * radius
+                })
+                .collect()
+        })
+        .collect();
+
+    Instance {
+        coordinates: Some(points),
+        centers: Some(centers),
+        ..Instance::new((0..num_elements).collect(), sets)
+    }
+}
+
+/// The shape of the area a sensor of `generate_grid` covers.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sensor {
+    /// The ball of the radius around the sensor.
+    #[serde(rename = "ball")]
+    Ball,
+    /// An axis-aligned rectangle centered on the sensor, with a uniformly random half-width
+    /// and half-height of at most the radius each.
+    #[serde(rename = "rect")]
+    Rect,
+}
+
+impl FromStr for Sensor {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Sensor, String> {
+        match s {
+            "ball" => Ok(Sensor::Ball),
+            "rect" => Ok(Sensor::Rect),
+            _ => Err(format!("unknown sensor shape: {}", s)),
+        }
+    }
+}
+
+/// Generates a random sensor placement instance on a grid: the elements are the points of a
+/// `rows` by `cols` grid, with the point in row `y` and column `x` numbered `y * cols + x`
+/// and placed at `(x, y)`, and each of the `num_sets` sets is a sensor at a uniformly random
+/// point of the grid, covering the points in its `sensor` area of `radius`. The points are
+/// recorded in `coordinates` and the sensors in `centers`.
+pub fn generate_grid<R: Rng>(rows: usize,
+                             cols: usize,
+                             num_sets: usize,
+                             sensor: Sensor,
+                             radius: f64,
+                             rng: &mut R)
+                             -> Instance {
+    assert!(rows > 0 && cols > 0, "the grid must have points");
+    assert!(radius >= 0.0, "radius must be at least 0, not {}", radius);
+    let points = (0..rows * cols)
+        .map(|e| ((e % cols) as f64, (e / cols) as f64))
+        .collect::<Vec<_>>();
+    let reach = radius.floor() as usize;
+    let mut centers = Vec::new();
+    let mut sets = Vec::new();
+    for _ in 0..num_sets {
+        let (x, y) = (rng.gen_range(0, cols), rng.gen_range(0, rows));
+        let (width, height) = match sensor {
+            Sensor::Ball => (radius, radius),
+            Sensor::Rect => (rng.gen::<f64>() * radius, rng.gen::<f64>() * radius),
+        };
+        let mut set = BTreeSet::new();
+        // only the points within reach in both directions can be covered
+        for py in y.saturating_sub(reach)..(y + reach + 1).min(rows) {
+            for px in x.saturating_sub(reach)..(x + reach + 1).min(cols) {
+                let (dx, dy) = (px as f64 - x as f64, py as f64 - y as f64);
+                let covered = match sensor {
+                    Sensor::Ball => dx * dx + dy * dy <= radius * radius,
+                    Sensor::Rect => dx.abs() <= width && dy.abs() <= height,
+                };
+                if covered {
+                    set.insert(py * cols + px);
+                }
+            }
+        }
+        centers.push((x as f64, y as f64));
+        sets.push(set);
+    }
+
+    Instance {
+        coordinates: Some(points),
+        centers: Some(centers),
+        ..Instance::new((0..rows * cols).collect(), sets)
+    }
+}
+
+/// Generates a random instance with community structure, as in stochastic block models: the
+/// `num_elements` elements are split into `communities` communities of consecutive elements,
+/// as even as can be, and each of the `num_sets` sets belongs to a uniformly random one.
+/// Sets have uniformly random sizes as in `generate`, and each of their elements comes from
+/// the rest of the ground set with probability `mixing`, and from their community otherwise,
+/// up to its size. Sets may be the same. The community of each element is recorded in
+/// `labels`, and that of each set in `groups`, which constrain nothing without
+/// `group_limits`.
+pub fn generate_communities<R: Rng>(num_elements: usize,
+                                    num_sets: usize,
+                                    max_size: Option<usize>,
+                                    communities: usize,
+                                    mixing: f64,
+                                    rng: &mut R)
+                                    -> Instance {
+    assert!(communities > 0 && communities <= num_elements,
+            "cannot split {} elements into {} communities",
+            num_elements,
+            communities);
+    assert!((0.0..=1.0).contains(&mixing),
+            "mixing must be in [0, 1], not {}",
+            mixing);
+    let labels = (0..num_elements).map(|e| e * communities / num_elements).collect::<Vec<_>>();
+    let members = (0..communities)
+        .map(|c| (0..num_elements).filter(|&e| labels[e] == c).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let range = Range::new(1, max_size.unwrap_or(num_elements + 1));
+
+    let mut sets = Vec::new();
+    let mut groups = Vec::new();
+    for _ in 0..num_sets {
+        let c = rng.gen_range(0, communities);
+        let size = range.ind_sample(rng);
+        let outside = (0..size).filter(|_| rng.gen::<f64>() < mixing).count();
+        let others = (0..num_elements).filter(|&e| labels[e] != c).collect::<Vec<_>>();
+        let mut set = sample(rng, &members[c], (size - outside).min(members[c].len()))
+            .into_iter()
+            .cloned()
+            .collect::<BTreeSet<_>>();
+        set.extend(sample(rng, &others, outside.min(others.len())).into_iter().cloned());
+        sets.push(set);
+        groups.push(c);
+    }
+
+    Instance {
+        labels: Some(labels),
+        groups: Some(groups),
+        ..Instance::new((0..num_elements).collect(), sets)
+    }
+}
+
+/// Generates a random instance of `num_elements` points on a line and `num_sets` intervals
+/// of them, tagged with `Structure::Interval`. Each interval has a uniformly random length
+/// as the sizes of `generate`, and a uniformly random start among those that keep it on the
+/// line. Sets may be the same. Panics if `num_elements` is 0, since intervals are not empty.
+pub fn generate_intervals<R: Rng>(num_elements: usize,
+                                  num_sets: usize,
+                                  max_size: Option<usize>,
+                                  rng: &mut R)
+                                  -> Instance {
+    let range = Range::new(1, max_size.unwrap_or(num_elements + 1).min(num_elements + 1));
+    let sets = (0..num_sets)
+        .map(|_| {
+            let length = range.ind_sample(rng);
+            let start = rng.gen_range(0, num_elements - length + 1);
+            (start..start + length).collect()
+        })
+        .collect();
+
+    Instance {
+        structure: Some(Structure::Interval),
+        ..Instance::new((0..num_elements).collect(), sets)
+    }
+}
+
+/// Generates a random instance of nested, heavily overlapping sets, which makes for many
+/// symmetric selections and greedy ties. Set `i` is a root with probability `1 / (i + 1)`,
+/// with a uniformly random size as in `generate`, and is otherwise a child of a uniformly
+/// random earlier set, keeping each of its elements with probability `keep`, and at least
+/// one. The sets thus form a forest, of about `ln num_sets` trees, in which every set is a
+/// subset of its parent. Sets may be the same.
+pub fn generate_nested<R: Rng>(num_elements: usize,
+                               num_sets: usize,
+                               max_size: Option<usize>,
+                               keep: f64,
+                               rng: &mut R)
+                               -> Instance {
+    assert!((0.0..=1.0).contains(&keep), "keep must be in [0, 1], not {}", keep);
+    let ground: BTreeSet<usize> = (0..num_elements).collect();
+    let range = Range::new(1, max_size.unwrap_or(num_elements + 1));
+
+    let mut sets: Vec<BTreeSet<usize>> = Vec::new();
+    for i in 0..num_sets {
+        let parent = rng.gen_range(0, i + 1);
+        let set = if parent == i {
+            let size = range.ind_sample(rng);
+            sample(rng, &ground, size).into_iter().cloned().collect()
+        } else {
+            let mut set = sets[parent]
+                .iter()
+                .cloned()
+                .filter(|_| rng.gen::<f64>() < keep)
+                .collect::<BTreeSet<_>>();
+            if set.is_empty() {
+                set.extend(sample(rng, &sets[parent], 1).into_iter().cloned());
+            }
+            set
+        };
+        sets.push(set);
+    }
+
+    Instance::new(ground, sets)
+}
+
+/// Plants `k` disjoint, nonempty sets that together are the ground set of `inst`, at random
+/// positions among its sets, and returns their positions in increasing order. Selecting them
+/// covers every element, so they are an optimal solution for any `k` of at least this many.
+pub fn plant<R: Rng>(inst: &mut Instance, k: usize, rng: &mut R) -> Vec<usize> {
+    let n = inst.ground.len();
+    assert!(k > 0 && k <= n, "cannot plant {} disjoint sets in {} elements", k, n);
+    let mut elements = inst.ground.iter().cloned().collect::<Vec<_>>();
+    rng.shuffle(&mut elements);
+    // blocks end at k - 1 distinct cuts between the elements, and at the last element
+    let mut cuts = sample(rng, 1..n, k - 1);
+    cuts.sort();
+    cuts.push(n);
+
+    let mut sets = inst.sets.drain(..).map(|set| (set, false)).collect::<Vec<_>>();
+    let mut begin = 0;
+    for end in cuts {
+        sets.push((elements[begin..end].iter().cloned().collect(), true));
+        begin = end;
+    }
+    rng.shuffle(&mut sets);
+    let planted = (0..sets.len()).filter(|&i| sets[i].1).collect();
+    inst.sets = sets.into_iter().map(|(set, _)| set).collect();
+    planted
+}
+
+/// The classic instance on which greedy covers only 1 - (1 - 1/k)^k of the optimum, which
+/// tends to 1 - 1/e. Element `j` of row `i`, numbered `i * k + j`, weighs
+/// `(k - 1)^i k^(k - 1 - i)` for the rows `i < k`, and `(k - 1)^k` in the last row, `k`.
+/// Sets `0..k` are the rows but the last, which greedy selects, and sets `k..2k` the
+/// columns, which are optimal, each weighing `k^k`. Each row weighs as much as what is left
+/// of a column when greedy comes to it, so greedy only selects the rows as it breaks ties
+/// by the lower set, as `greedy` and `lazy_greedy` do. The weights are integers, so that
+/// the ties are exact, which limits `k` to 13.
+pub fn generate_adversarial(k: usize) -> Instance {
+    assert!(k > 0 && k <= 13, "k must be from 1 to 13, not {}", k);
+    let weights = (0..k + 1)
+        .flat_map(|i| {
+            let w = if i < k {
+                (k as f64 - 1.0).powi(i as i32) * (k as f64).powi((k - 1 - i) as i32)
+            } else {
+                (k as f64 - 1.0).powi(k as i32)
+            };
+            iter::repeat_n(w, k)
+        })
+        .collect::<Vec<_>>();
+    let rows = (0..k).map(|i| (i * k..(i + 1) * k).collect());
+    let columns = (0..k).map(|j| (0..k + 1).map(|i| i * k + j).collect());
+
+    Instance {
+        weights: Some(weights),
+        ..Instance::new((0..k * (k + 1)).collect(), rows.chain(columns).collect())
+    }
+}
+
+/// A distribution of the random weights or costs of `sample_values`, each of which has a
+/// mean of about 1, like the unit weights and costs of instances without them.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Distribution {
+    /// Uniform over [0.5, 1.5).
+    #[serde(rename = "uniform")]
+    Uniform,
+    /// Log-normal, with a mean of 1 and the logarithm's standard deviation 1.
+    #[serde(rename = "lognormal")]
+    LogNormal,
+    /// Proportional to the size, taken as the size of each set for costs, and as the number
+    /// of sets containing each element for weights, over the mean size, times a uniform value
+    /// from [0.5, 1.5).
+    #[serde(rename = "size")]
+    Size,
+}
+
+impl FromStr for Distribution {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Distribution, String> {
+        match s {
+            "uniform" => Ok(Distribution::Uniform),
+            "lognormal" => Ok(Distribution::LogNormal),
+            "size" => Ok(Distribution::Size),
+            _ => Err(format!("unknown distribution: {}", s)),
+        }
+    }
+}
+
+/// Draws a value of `dist` for each of `sizes`, which only `Distribution::Size` uses.
+pub fn sample_values<R: Rng>(dist: Distribution, sizes: &[usize], rng: &mut R) -> Vec<f64> {
+    let mean = sizes.iter().sum::<usize>() as f64 / sizes.len().max(1) as f64;
+    let lognormal = LogNormal::new(-0.5, 1.0);
+    sizes.iter()
+        .map(|&size| match dist {
+            Distribution::Uniform => rng.gen_range(0.5, 1.5),
+            Distribution::LogNormal => lognormal.ind_sample(rng),
+            Distribution::Size if mean > 0.0 => size as f64 / mean * rng.gen_range(0.5, 1.5),
+            Distribution::Size => 0.0,
+        })
+        .collect()
+}
+
+/// Samples `n` weight scenarios for `inst`. In each, every element keeps its weight with
+/// probability `presence` and weighs 0 otherwise, like customers that may or may not turn
+/// up.
+pub fn sample_scenarios<R: Rng>(inst: &Instance,
+                                n: usize,
+                                presence: f64,
+                                rng: &mut R)
+                                -> Vec<Vec<f64>> {
+    let weights = inst.weights();
+    (0..n)
+        .map(|_| {
+            weights.iter()
+                .map(|&w| if rng.gen::<f64>() < presence { w } else { 0.0 })
+                .collect()
+        })
+        .collect()
+}
+
+pub fn write(inst: &Instance, fname: &str) -> Result<(), serde_json::Error> {
+    let mut f = File::create(fname)?;
+    serde_json::to_writer(&mut f, inst)
+}
+
+pub fn read(fname: &str) -> Result<Instance, serde_json::Error> {
+    let f = File::open(fname)?;
+    serde_json::from_reader(&f)
+}
+
+/// How `solve` orders the set variables for branching.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BranchPriority {
+    /// Leave the order to the solver.
+    None,
+    /// Larger sets first.
+    Size,
+    /// The sets of the greedy solution first, in the order greedy picks them.
+    Greedy,
+}
+
+impl FromStr for BranchPriority {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<BranchPriority, String> {
+        match s {
+            "none" => Ok(BranchPriority::None),
+            "size" => Ok(BranchPriority::Size),
+            "greedy" => Ok(BranchPriority::Greedy),
+            _ => Err(format!("unknown branch priority: {}", s)),
+        }
+    }
+}
+
+/// What `cover solve` optimizes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Objective {
+    /// The weight of the elements covered by at most `k` sets.
+    MaxCoverage,
+    /// The cost of the sets needed to cover every element.
+    SetCover,
+    /// The weight covered by any number of sets, less their cost and the penalties of the
+    /// elements left uncovered.
+    PrizeCollecting,
+}
+
+impl FromStr for Objective {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Objective, String> {
+        match s {
+            "max-coverage" => Ok(Objective::MaxCoverage),
+            "set-cover" => Ok(Objective::SetCover),
+            "prize-collecting" => Ok(Objective::PrizeCollecting),
+            _ => Err(format!("unknown objective: {}", s)),
+        }
+    }
+}
+
+/// How `solve` sets up the coverage model, beyond the backend's `Params`.
+#[derive(Debug, Clone)]
+pub struct IlpOptions {
+    /// Sets to warm start from. Defaults to the greedy solution.
+    pub start: Option<Vec<usize>>,
+    pub priority: BranchPriority,
+    /// Leaves the cover rows out of the model and adds only those the solver's solutions
+    /// violate, re-solving until none are, which builds a much smaller model when most
+    /// elements' rows never matter.
+    pub lazy: bool,
+    /// Orders the variables of each group of `symmetric_groups`, so that the solver only
+    /// considers one of the equivalent ways to pick sets from a group.
+    pub symmetry: bool,
+    /// Bound on the total cost of the selected sets, on top of `k`.
+    pub budget: Option<f64>,
+}
+
+impl Default for IlpOptions {
+    fn default() -> IlpOptions {
+        IlpOptions {
+            start: None,
+            priority: BranchPriority::None,
+            lazy: false,
+            symmetry: false,
+            budget: None,
+        }
+    }
+}
+
+/// Writes `model` to `problem.lp`, as the ILP solves do before solving it.
+pub fn write_problem_lp(model: &backend::Model) -> Result<(), String> {
+    let mut f = File::create("problem.lp").map_err(|e| e.to_string())?;
+    model.write_lp(&mut f).map_err(|e| e.to_string())
+}
+
+/// Sets the bound and gap of `out`, the solution read from `sol`: the solution is optimal if
+/// the solver proved it without a gap tolerance, and otherwise only near-optimal, with the
+/// solver's own bound if it reports one.
+pub fn fill_bound(out: &mut Solution, sol: &backend::MipSolution, params: &backend::Params) {
+    if sol.optimal && params.gap.is_none() && params.abs_gap.is_none() {
+        out.bound = Some(out.objective);
+        out.gap = Some(0.0);
+    } else if let Some(bound) = sol.bound {
+        out.bound = Some(bound);
+        out.gap = Some(backend::relative_gap(out.objective, bound));
+    }
+}
+
+/// Solves `inst` to optimality for cardinality `k` with `backend`. The model is also written
+/// to `problem.lp`. If the backend stops early, e.g. on its time limit, this returns its
+/// incumbent along with the best bound and gap it reports.
+///
+/// The solver is warm started and its branching ordered as `options` say, for backends that
+/// accept a MIP start or priorities.
+///
+/// The objective is recomputed from the selected sets rather than taken from the solver, so
+/// that every backend reports exactly the same integral coverage.
+pub fn solve<B>(inst: &Instance,
+                k: usize,
+                backend: &mut B,
+                params: &backend::Params,
+                options: &IlpOptions)
+                -> Result<Solution, String>
+    where B: SolverBackend + ?Sized
+{
+    solve_pool(inst, k, backend, params, options, 1).map(|mut pool| pool.remove(0))
+}
+
+/// Solves `inst` for cardinality `k` again, after `solve` has loaded the coverage model into
+/// `backend` for another `k`, by changing the right-hand side of its cardinality row instead
+/// of building the model again. The solver is warm started from `options.start`, or else the
+/// greedy solution, and `options` are otherwise those of the solve that loaded the model,
+/// which may not be lazy. The solution is as for `solve`, and `problem.lp` is left as the
+/// loaded model was.
+///
+/// Backends that hand a whole model to the solver in one call, like the bundled ones, still
+/// load it into the solver anew, but the model is neither built, checked nor written again.
+pub fn resolve<B>(inst: &Instance,
+                  k: usize,
+                  backend: &mut B,
+                  params: &backend::Params,
+                  options: &IlpOptions)
+                  -> Result<Solution, String>
+    where B: SolverBackend + ?Sized
+{
+    if options.lazy {
+        return Err("lazy models cannot be solved again for another k".to_string());
+    }
+    let start = match options.start {
+        Some(ref start) => start.clone(),
+        None => solvers::greedy::lazy_greedy(inst, k).sol,
+    };
+    if start.len() > k {
+        return Err(format!("warm start selects {} sets, more than k = {}", start.len(), k));
+    }
+    let groups = if options.symmetry {
+        symmetric_groups(inst)
+    } else {
+        Vec::new()
+    };
+    let start = canonical_selection(&start, &groups);
+    backend.set_rhs("cardinality", k as f64)?;
+    backend.set_start(backend::coverage_values(inst, &start))?;
+    params.apply(backend)?;
+    backend.solve()?;
+    let sol = backend.extract_solution()?;
+
+    let selected = sol.sets.clone().ok_or_else(|| "the loaded model selects no sets".to_string())?;
+    let objective = Coverage::from_sets(&Index::new(inst), &selected).value();
+    let mut out = Solution::new(objective, selected);
+    fill_bound(&mut out, &sol, params);
+    Ok(out)
+}
+
+/// Solves like `solve`, then keeps solving with each solution found so far cut off, to
+/// collect the `n` best distinct solutions, best first. Fewer are returned if there are not
+/// `n` ways to select at most `k` sets. Each solve has the full time limit, and its bound and
+/// gap are relative to the solutions that remain. An interrupt stops the collection after
+/// the current solve.
+///
+/// With `options.lazy` each of these solves is a loop of solves that adds the violated cover
+/// rows and warm starts from the last selection, and each of those has the full time limit.
+/// The rows stay in the model for later solves. `problem.lp` is the model before any are
+/// added.
+///
+/// With `options.symmetry` the pool only holds one of the selections that differ just in
+/// which sets of a symmetric group they pick.
+pub fn solve_pool<B>(inst: &Instance,
+                     k: usize,
+                     backend: &mut B,
+                     params: &backend::Params,
+                     options: &IlpOptions,
+                     n: usize)
+                     -> Result<Vec<Solution>, String>
+    where B: SolverBackend + ?Sized
+{
+    let (mut model, set_vars) = if options.lazy {
+        backend::lazy_coverage_model(inst, k, VarType::Binary)
+    } else {
+        backend::coverage_model(inst, k, VarType::Binary)
+    };
+    let greedy = match options.budget {
+        Some(budget) => solvers::greedy::budgeted_greedy(inst, k, budget),
+        None if inst.has_prerequisites() => solvers::greedy::precedence_greedy(inst, k),
+        // an unfair start would be rejected
+        None if inst.min_coverage.is_some() => {
+            solvers::greedy::fair_greedy(inst, k).unwrap_or_else(|_| {
+                solvers::greedy::lazy_greedy(inst, k)
+            })
+        }
+        None => solvers::greedy::lazy_greedy(inst, k),
+    };
+    let start = options.start.as_ref().unwrap_or(&greedy.sol);
+    if start.len() > k {
+        return Err(format!("warm start selects {} sets, more than k = {}", start.len(), k));
+    }
+    inst.check_selection(start).map_err(|e| format!("warm start: {}", e))?;
+    if let Some(budget) = options.budget {
+        let cost = inst.cost(start);
+        if cost > budget {
+            return Err(format!("warm start costs {}, more than the budget {}", cost, budget));
+        }
+        let terms = set_vars.iter().cloned().zip(inst.costs()).collect();
+        model.add_row("budget".to_string(), terms, Sense::LessEq, budget);
+    }
+    let groups = if options.symmetry {
+        symmetric_groups(inst)
+    } else {
+        Vec::new()
+    };
+    for (g, group) in groups.iter().enumerate() {
+        // a set of the group may only be selected if the one before it is
+        for (t, pair) in group.windows(2).enumerate() {
+            let terms = vec![(set_vars[pair[0]], 1.0), (set_vars[pair[1]], -1.0)];
+            model.add_row(format!("sym{}_{}", g, t), terms, Sense::GreaterEq, 0.0);
+        }
+    }
+    let start = canonical_selection(start, &groups);
+    model.start = Some(backend::coverage_values(inst, &start));
+    match options.priority {
+        BranchPriority::None => {}
+        BranchPriority::Size => {
+            for (set, &j) in inst.sets.iter().zip(&set_vars) {
+                model.vars[j].priority = set.len() as u32;
+            }
+        }
+        BranchPriority::Greedy => {
+            for (rank, &i) in greedy.sol.iter().enumerate() {
+                model.vars[set_vars[i]].priority = (greedy.sol.len() - rank) as u32;
+            }
+        }
+    }
+    write_problem_lp(&model)?;
+
+    params.apply(backend)?;
+    let index = Index::new(inst);
+    let n = num_selections(inst.sets.len(), &groups, k, n);
+    let mut pool = Vec::with_capacity(n);
+    while pool.len() < n && (pool.is_empty() || !interrupt::interrupted()) {
+        let (sol, chosen) = loop {
+            backend.build_model(model.clone())?;
+            backend.solve()?;
+            let mut sol = backend.extract_solution()?;
+            let chosen = set_vars.iter().map(|&j| sol.values[j] > 0.5).collect::<Vec<_>>();
+            if !options.lazy {
+                break (sol, chosen);
+            }
+            let mut count = HashMap::new();
+            for i in (0..set_vars.len()).filter(|&i| chosen[i]) {
+                for &x in &inst.sets[i] {
+                    *count.entry(x).or_insert(0) += 1;
+                }
+            }
+            // element variables are laid out in ground set order
+            let violated = inst.ground
+                .iter()
+                .zip(inst.demands())
+                .enumerate()
+                .filter(|&(j, (x, r))| sol.values[j] > 0.5 && count.get(x).map_or(0, |&c| c) < r)
+                .map(|(j, (&x, _))| (x, j))
+                .collect::<Vec<_>>();
+            if violated.is_empty() {
+                break (sol, chosen);
+            }
+            // the selection is still feasible, just worth less than the solver thinks, while
+            // the bound holds for the full model too
+            sol.optimal = false;
+            backend::add_cover_rows(&mut model, inst, &set_vars, &violated);
+            let selected = (0..set_vars.len()).filter(|&i| chosen[i]).collect::<Vec<_>>();
+            model.start = Some(backend::coverage_values(inst, &selected));
+            if interrupt::interrupted() {
+                break (sol, chosen);
+            }
+        };
+        let selected = (0..set_vars.len()).filter(|&i| chosen[i]).collect::<Vec<_>>();
+        let objective = Coverage::from_sets(&index, &selected).value();
+
+        // no-good cut: the selected sets may not all be chosen again without any other
+        let terms = set_vars.iter()
+            .zip(&chosen)
+            .map(|(&j, &c)| (j, if c { 1.0 } else { -1.0 }))
+            .collect();
+        let rhs = selected.len() as f64 - 1.0;
+        model.add_row(format!("nogood{}", pool.len()), terms, Sense::LessEq, rhs);
+        // the start is cut off now
+        model.start = None;
+
+        let mut out = Solution::new(objective, selected);
+        fill_bound(&mut out, &sol, params);
+        pool.push(out);
+    }
+    Ok(pool)
+}
+
+/// Groups of interchangeable sets, each in increasing order and with at least two sets:
+/// identical sets, and sets of the same weight whose elements are in no other set, where the
+/// sets of a group also cost the same, share a selection group, conflict with no set, are
+/// neither required nor forbidden, are in no prerequisite and, under a red limit, contain no
+/// red elements.
+/// Swapping a selected set for an unselected one of its
+/// group never changes the coverage or the cost.
+pub fn symmetric_groups(inst: &Instance) -> Vec<Vec<usize>> {
+    let mut count = HashMap::new();
+    for set in &inst.sets {
+        for &x in set {
+            *count.entry(x).or_insert(0) += 1;
+        }
+    }
+    // an element in only one set can only be covered if its demand is 1
+    let weights = inst.ground
+        .iter()
+        .cloned()
+        .zip(inst.weights().into_iter().zip(inst.demands()))
+        .filter(|&(_, (_, r))| r == 1)
+        .map(|(x, (w, _))| (x, w))
+        .collect::<HashMap<_, _>>();
+    let costs = inst.costs();
+    let labels = inst.groups();
+    let mut constrained = inst.conflicts().iter().flat_map(|c| c.clone()).collect::<HashSet<_>>();
+    let fixed = inst.fixed();
+    constrained.extend((0..fixed.len()).filter(|&i| fixed[i].is_some()));
+    constrained.extend(inst.prerequisites().into_iter().flat_map(|(a, b)| vec![a, b]));
+    if inst.red_limit.is_some() {
+        let red = inst.red();
+        let has_red = |i: &usize| red.iter().any(|x| inst.sets[*i].contains(x));
+        constrained.extend((0..inst.sets.len()).filter(has_red));
+    }
+
+    // floats are keyed by their bits, so only exactly equal weights and costs match
+    let mut identical = HashMap::new();
+    let mut private = HashMap::new();
+    for (i, set) in inst.sets.iter().enumerate() {
+        if constrained.contains(&i) {
+            continue;
+        }
+        if !set.is_empty() && set.iter().all(|x| count[x] == 1) {
+            let weight = set.iter().filter_map(|x| weights.get(x)).sum::<f64>();
+            let key = (weight.to_bits(), costs[i].to_bits(), labels[i]);
+            private.entry(key).or_insert_with(Vec::new).push(i);
+        } else {
+            identical.entry((set, costs[i].to_bits(), labels[i])).or_insert_with(Vec::new).push(i);
+        }
+    }
+
+    let mut groups = identical.into_values()
+        .chain(private.into_values())
+        .filter(|group| group.len() > 1)
+        .collect::<Vec<_>>();
+    groups.sort();
+    groups
+}
+
+/// `sets` with its picks from each of `groups` moved to the group's first sets, which leaves
+/// its coverage unchanged.
+fn canonical_selection(sets: &[usize], groups: &[Vec<usize>]) -> Vec<usize> {
+    let mut selected = sets.iter().cloned().collect::<BTreeSet<_>>();
+    for group in groups {
+        let n = group.iter().filter(|i| selected.contains(i)).count();
+        for (t, i) in group.iter().enumerate() {
+            if t < n {
+                selected.insert(*i);
+            } else {
+                selected.remove(i);
+            }
+        }
+    }
+    selected.into_iter().collect()
+}
+
+/// Solves the minimum set cover problem on `inst` with `backend`, finding the sets of least
+/// total cost that cover the ground set, which is the solution's objective. The bound and gap
+/// are as for `solve`, and the model is also written to `problem.lp`. The solver is warm
+/// started from `start`, which must be a cover, or else from greedy set cover.
+///
+/// With a `target` below 1 the sets only have to cover that fraction of the elements'
+/// total weight, as in `set_cover_model`.
+pub fn solve_set_cover<B>(inst: &Instance,
+                          backend: &mut B,
+                          params: &backend::Params,
+                          start: Option<&[usize]>,
+                          target: f64)
+                          -> Result<Solution, String>
+    where B: SolverBackend + ?Sized
+{
+    let greedy = solvers::greedy::greedy_set_cover(inst, target)?;
+    let start = start.unwrap_or(&greedy.sol);
+    inst.check_selection(start).map_err(|e| format!("warm start: {}", e))?;
+    let index = Index::new(inst);
+    let required = target * index.weights.iter().sum::<f64>();
+    let covered = Coverage::from_sets(&index, start);
+    if target >= 1.0 {
+        if let Some(e) = (0..index.num_elements).find(|&e| !covered.covered(e)) {
+            let x = inst.ground.iter().nth(e).unwrap();
+            return Err(format!("warm start does not cover element {}", x));
+        }
+    } else if covered.value() < required - 1e-9 {
+        return Err(format!("warm start covers {}, short of the target {}",
+                           covered.value(),
+                           required));
+    }
+
+    let mut model = backend::set_cover_model(inst, VarType::Binary, target);
+    // the partial cover model has element variables before the sets'
+    let offset = if target < 1.0 { inst.ground.len() } else { 0 };
+    let mut values = vec![0.0; offset + inst.sets.len()];
+    for e in 0..offset {
+        values[e] = if covered.covered(e) { 1.0 } else { 0.0 };
+    }
+    for &i in start {
+        values[offset + i] = 1.0;
+    }
+    values.extend(backend::selection_values(inst, start));
+    model.start = Some(values);
+    write_problem_lp(&model)?;
+
+    params.apply(backend)?;
+    backend.build_model(model)?;
+    backend.solve()?;
+    let sol = backend.extract_solution()?;
+    let selected = (0..inst.sets.len())
+        .filter(|&i| sol.values[offset + i] > 0.5)
+        .collect::<Vec<_>>();
+    let objective = inst.cost(&selected);
+
+    let mut out = Solution::new(objective, selected);
+    fill_bound(&mut out, &sol, params);
+    Ok(out)
+}
+
+/// The least of `n` and the number of ways to select at most `k` of `m` sets, where the
+/// selections that differ only in which sets of one of the disjoint `groups` they pick count
+/// once.
+fn num_selections(m: usize, groups: &[Vec<usize>], k: usize, n: usize) -> usize {
+    let grouped = groups.iter().map(|group| group.len()).sum::<usize>();
+    let sizes = groups.iter().map(|group| group.len()).chain(iter::repeat_n(1, m - grouped));
+    // the number of selections of each size from the groups and sets so far, which only
+    // grows as more are added
+    let mut ways = vec![0usize; cmp::min(m, k) + 1];
+    ways[0] = 1;
+    let total = |ways: &[usize]| ways.iter().fold(0usize, |total, &w| total.saturating_add(w));
+    for size in sizes {
+        if total(&ways) >= n {
+            break;
+        }
+        for j in (1..ways.len()).rev() {
+            for t in 1..cmp::min(size, j) + 1 {
+                ways[j] = ways[j].saturating_add(ways[j - t]);
+            }
+        }
+    }
+    cmp::min(n, total(&ways))
+}
+
+/// Solves the prize-collecting problem on `inst` with `backend`, finding at most `k` sets
+/// that maximize `Instance::prize`, which is the solution's objective. The model is
+/// `coverage_model` of `Instance::prize_instance` with each set's cost taken off its
+/// variable, so its objective exceeds the prize by the total penalty. The bound and gap are
+/// as for `solve`, and the model is also written to `problem.lp`. The solver is warm started
+/// from `start`, or else from prize-collecting greedy.
+pub fn solve_prize_collecting<B>(inst: &Instance,
+                                 k: usize,
+                                 backend: &mut B,
+                                 params: &backend::Params,
+                                 start: Option<&[usize]>)
+                                 -> Result<Solution, String>
+    where B: SolverBackend + ?Sized
+{
+    let greedy = solvers::greedy::prize_collecting_greedy(inst, k);
+    let start = start.unwrap_or(&greedy.sol);
+    if start.len() > k {
+        return Err(format!("warm start selects {} sets, more than k = {}", start.len(), k));
+    }
+    inst.check_selection(start).map_err(|e| format!("warm start: {}", e))?;
+
+    let prizes = inst.prize_instance();
+    let (mut model, set_vars) = backend::coverage_model(&prizes, k, VarType::Binary);
+    for (&j, cost) in set_vars.iter().zip(inst.costs()) {
+        model.vars[j].obj = -cost;
+    }
+    model.start = Some(backend::coverage_values(&prizes, start));
+    write_problem_lp(&model)?;
+
+    params.apply(backend)?;
+    backend.build_model(model)?;
+    backend.solve()?;
+    let sol = backend.extract_solution()?;
+    let selected = (0..set_vars.len())
+        .filter(|&i| sol.values[set_vars[i]] > 0.5)
+        .collect::<Vec<_>>();
+    let objective = inst.prize(&selected);
+
+    // the model's bound exceeds the prize by the total penalty
+    let sol = backend::MipSolution {
+        bound: sol.bound.map(|bound| bound - inst.penalties().iter().sum::<f64>()),
+        ..sol
+    };
+    let mut out = Solution::new(objective, selected);
+    fill_bound(&mut out, &sol, params);
+    Ok(out)
+}
+
+/// Solves the robust problem on `inst` with `backend`, finding at most `k` sets that maximize
+/// `Instance::worst_case`, the least weight covered in any scenario, which is the solution's
+/// objective. The model is `coverage_model` with an auxiliary variable `z` after the sets'
+/// that is the objective, and a row per scenario that bounds it by the scenario's covered
+/// weight. The bound and gap are as for `solve`, and the model is also written to
+/// `problem.lp`. The solver is warm started from `start`, or else from robust greedy.
+pub fn solve_robust<B>(inst: &Instance,
+                       k: usize,
+                       backend: &mut B,
+                       params: &backend::Params,
+                       start: Option<&[usize]>)
+                       -> Result<Solution, String>
+    where B: SolverBackend + ?Sized
+{
+    let greedy = solvers::greedy::robust_greedy(inst, k);
+    let start = start.unwrap_or(&greedy.sol);
+    if start.len() > k {
+        return Err(format!("warm start selects {} sets, more than k = {}", start.len(), k));
+    }
+    inst.check_selection(start).map_err(|e| format!("warm start: {}", e))?;
+
+    let scenarios = inst.scenarios();
+    let (mut model, set_vars) = backend::coverage_model(inst, k, VarType::Binary);
+    for var in &mut model.vars {
+        var.obj = 0.0;
+    }
+    let most = scenarios.iter().map(|w| w.iter().sum::<f64>()).fold(f64::INFINITY, f64::min);
+    let z = model.add_var("z".to_string(), VarType::Continuous, 1.0, 0.0, most);
+    for (s, weights) in scenarios.iter().enumerate() {
+        // element j has variable j
+        let mut terms = vec![(z, 1.0)];
+        terms.extend(weights.iter()
+            .enumerate()
+            .filter(|&(_, &w)| w != 0.0)
+            .map(|(j, &w)| (j, -w)));
+        model.add_row(format!("scenario{}", s), terms, Sense::LessEq, 0.0);
+    }
+    let mut values = backend::coverage_values(inst, start);
+    values.push(inst.worst_case(start));
+    model.start = Some(values);
+    write_problem_lp(&model)?;
+
+    params.apply(backend)?;
+    backend.build_model(model)?;
+    backend.solve()?;
+    let sol = backend.extract_solution()?;
+    let selected = (0..set_vars.len())
+        .filter(|&i| sol.values[set_vars[i]] > 0.5)
+        .collect::<Vec<_>>();
+    let objective = inst.worst_case(&selected);
+
+    let mut out = Solution::new(objective, selected);
+    fill_bound(&mut out, &sol, params);
+    Ok(out)
+}
+
+/// Solves the overlap-penalized problem on `inst` with `backend`, finding at most `k` sets
+/// that maximize `Instance::overlap_penalized`, which is the solution's objective. The model
+/// is `coverage_model` with an integer variable after the others for each element that two
+/// or more sets contain, which is at least the number of selected sets containing it less
+/// one and costs `penalty` each. The bound and gap are as for `solve`, and the model is also
+/// written to `problem.lp`. The solver is warm started from `start`, or else from
+/// overlap-penalized greedy.
+pub fn solve_overlap<B>(inst: &Instance,
+                        k: usize,
+                        penalty: f64,
+                        backend: &mut B,
+                        params: &backend::Params,
+                        start: Option<&[usize]>)
+                        -> Result<Solution, String>
+    where B: SolverBackend + ?Sized
+{
+    if inst.has_demands() {
+        return Err("the overlap penalty does not support demands".to_string());
+    }
+    let greedy = solvers::greedy::overlap_greedy(inst, k, penalty);
+    let start = start.unwrap_or(&greedy.sol);
+    if start.len() > k {
+        return Err(format!("warm start selects {} sets, more than k = {}", start.len(), k));
+    }
+    inst.check_selection(start).map_err(|e| format!("warm start: {}", e))?;
+
+    let index = Index::new(inst);
+    let (mut model, set_vars) = backend::coverage_model(inst, k, VarType::Binary);
+    let mut values = backend::coverage_values(inst, start);
+    let cov = Coverage::from_sets(&index, start);
+    for (e, &x) in inst.ground.iter().enumerate() {
+        let containing = &index.containing[e];
+        if containing.len() < 2 {
+            continue;
+        }
+        let most = (containing.len() - 1) as f64;
+        let o = model.add_var(format!("o{}", x), VarType::Integer, -penalty, 0.0, most);
+        let mut terms = vec![(o, 1.0)];
+        terms.extend(containing.iter().map(|&i| (set_vars[i], -1.0)));
+        model.add_row(format!("overlap{}", x), terms, Sense::GreaterEq, -1.0);
+        let count = containing.iter().filter(|&&i| cov.contains(i)).count();
+        values.push(count.saturating_sub(1) as f64);
+    }
+    model.start = Some(values);
+    write_problem_lp(&model)?;
+
+    params.apply(backend)?;
+    backend.build_model(model)?;
+    backend.solve()?;
+    let sol = backend.extract_solution()?;
+    let selected = (0..set_vars.len())
+        .filter(|&i| sol.values[set_vars[i]] > 0.5)
+        .collect::<Vec<_>>();
+    let objective = inst.overlap_penalized(&selected, penalty);
+
+    let mut out = Solution::new(objective, selected);
+    fill_bound(&mut out, &sol, params);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use backend::{BruteForce, Staged};
+
+    fn instance(n: usize, sets: Vec<Vec<usize>>) -> Instance {
+        Instance::new((0..n).collect(),
+                      sets.into_iter().map(|s| s.into_iter().collect()).collect())
+    }
+
+    #[test]
+    fn pool_holds_the_best_distinct_selections() {
+        let inst = instance(4, vec![vec![0, 1, 2], vec![2, 3], vec![3]]);
+        let mut backend = Staged::new(BruteForce);
+        let params = backend::Params::default();
+        let pool = solve_pool(&inst, 1, &mut backend, &params, &IlpOptions::default(), 3)
+            .unwrap();
+        let sets = pool.iter().map(|sol| sol.sol.clone()).collect::<Vec<_>>();
+        assert_eq!(sets, vec![vec![0], vec![1], vec![2]]);
+        let objectives = pool.iter().map(|sol| sol.objective).collect::<Vec<_>>();
+        assert_eq!(objectives, vec![3.0, 2.0, 1.0]);
+        assert_eq!(pool[0].bound, Some(3.0));
+    }
+
+    #[test]
+    fn pool_stops_at_the_number_of_selections() {
+        assert_eq!(num_selections(3, &[], 1, 10), 4);
+        assert_eq!(num_selections(3, &[], 5, 10), 8);
+        assert_eq!(num_selections(64, &[], 64, 100), 100);
+        let inst = instance(2, vec![vec![0], vec![1]]);
+        let mut backend = Staged::new(BruteForce);
+        let params = backend::Params::default();
+        let pool = solve_pool(&inst, 1, &mut backend, &params, &IlpOptions::default(), 10)
+            .unwrap();
+        assert_eq!(pool.len(), 3);
+        assert_eq!(pool[2].sol, Vec::<usize>::new());
+    }
+
+    #[test]
+    fn symmetric_groups_find_interchangeable_sets() {
+        let inst = Instance {
+            weights: Some(vec![1.0, 1.0, 1.0, 1.0, 2.0]),
+            forbidden: Some(vec![5]),
+            ..instance(5, vec![vec![0, 1], vec![2], vec![0, 1], vec![3], vec![4], vec![0, 1]])
+        };
+        assert_eq!(symmetric_groups(&inst), vec![vec![0, 2], vec![1, 3]]);
+        assert_eq!(canonical_selection(&[2, 3, 4], &symmetric_groups(&inst)), vec![0, 1, 4]);
+    }
+
+    #[test]
+    fn symmetry_keeps_one_selection_per_orbit() {
+        let inst = instance(3, vec![vec![0], vec![1], vec![2]]);
+        let mut backend = Staged::new(BruteForce);
+        let params = backend::Params::default();
+        let options = IlpOptions { symmetry: true, ..Default::default() };
+        let pool = solve_pool(&inst, 2, &mut backend, &params, &options, 10).unwrap();
+        let sets = pool.iter().map(|sol| sol.sol.clone()).collect::<Vec<_>>();
+        assert_eq!(sets, vec![vec![0, 1], vec![0], vec![]]);
+        assert_eq!(num_selections(4, &[vec![0, 1, 2]], 2, 10), 5);
+    }
+
+    #[test]
+    fn warm_starts_must_be_feasible() {
+        let inst = Instance {
+            conflicts: Some(vec![vec![0, 1]]),
+            ..instance(4, vec![vec![0, 1], vec![1, 2], vec![2, 3]])
+        };
+        let mut backend = Staged::new(BruteForce);
+        let params = backend::Params::default();
+        let too_many = IlpOptions { start: Some(vec![0, 2]), ..Default::default() };
+        assert!(solve(&inst, 1, &mut backend, &params, &too_many).is_err());
+        let conflicting = IlpOptions { start: Some(vec![0, 1]), ..Default::default() };
+        assert!(solve(&inst, 2, &mut backend, &params, &conflicting).is_err());
+        let start = IlpOptions { start: Some(vec![1]), ..Default::default() };
+        assert_eq!(solve(&inst, 2, &mut backend, &params, &start).unwrap().sol, vec![0, 2]);
+    }
+
+    #[test]
+    fn lazy_rows_reach_the_full_model_optimum() {
+        let inst = instance(5, vec![vec![0, 1], vec![1, 2, 3], vec![3, 4], vec![0, 4]]);
+        let mut backend = Staged::new(BruteForce);
+        let params = backend::Params::default();
+        let lazy = IlpOptions { lazy: true, ..Default::default() };
+        for k in 1..4 {
+            let full = solve(&inst, k, &mut backend, &params, &IlpOptions::default()).unwrap();
+            let sol = solve(&inst, k, &mut backend, &params, &lazy).unwrap();
+            assert_eq!(sol.objective, full.objective);
+            assert_eq!(sol.objective, Coverage::from_sets(&Index::new(&inst), &sol.sol).value());
+        }
+    }
+
+    #[test]
+    fn budget_bounds_the_cost_of_the_selection() {
+        let inst = Instance {
+            costs: Some(vec![3.0, 1.0, 1.0]),
+            ..instance(4, vec![vec![0, 1, 2], vec![0, 1], vec![3]])
+        };
+        let mut backend = Staged::new(BruteForce);
+        let params = backend::Params::default();
+        let options = IlpOptions { budget: Some(2.0), ..Default::default() };
+        let sol = solve(&inst, 2, &mut backend, &params, &options).unwrap();
+        assert_eq!(sol.sol, vec![1, 2]);
+        assert_eq!(sol.objective, 3.0);
+    }
+
+    #[test]
+    fn set_cover_finds_the_cheapest_cover() {
+        // greedy takes the big set, and then needs both others too
+        let inst = instance(6, vec![vec![0, 1, 2, 3], vec![0, 1, 4], vec![2, 3, 5]]);
+        assert_eq!(solvers::greedy::greedy_set_cover(&inst, 1.0).unwrap().objective, 3.0);
+        let mut backend = Staged::new(BruteForce);
+        let params = backend::Params::default();
+        let sol = solve_set_cover(&inst, &mut backend, &params, None, 1.0).unwrap();
+        assert_eq!(sol.sol, vec![1, 2]);
+        assert_eq!(sol.objective, 2.0);
+    }
+
+    #[test]
+    fn prize_collecting_weighs_penalties_against_costs() {
+        let inst = Instance {
+            costs: Some(vec![0.5, 3.0, 2.0]),
+            penalties: Some(vec![0.0, 0.0, 4.0]),
+            ..instance(3, vec![vec![0, 1], vec![2], vec![1, 2]])
+        };
+        let mut backend = Staged::new(BruteForce);
+        let params = backend::Params::default();
+        let sol = solve_prize_collecting(&inst, 2, &mut backend, &params, None).unwrap();
+        assert_eq!(sol.sol, vec![0, 2]);
+        assert_eq!(sol.objective, inst.prize(&[0, 2]));
+    }
+
+    #[test]
+    fn overlap_model_charges_repeated_coverage() {
+        let inst = instance(4, vec![vec![0, 1, 2], vec![1, 2, 3], vec![3]]);
+        let mut backend = Staged::new(BruteForce);
+        let params = backend::Params::default();
+        let sol = solve_overlap(&inst, 2, 1.0, &mut backend, &params, None).unwrap();
+        assert_eq!(sol.sol, vec![0, 2]);
+        assert_eq!(sol.objective, 4.0);
+        let sol = solve_overlap(&inst, 2, 0.0, &mut backend, &params, None).unwrap();
+        assert_eq!(sol.objective, 4.0);
+    }
+
+    #[test]
+    fn resolve_matches_a_fresh_solve() {
+        let inst = instance(6, vec![vec![0, 1, 2], vec![2, 3], vec![3, 4, 5], vec![0, 5]]);
+        let mut backend = Staged::new(BruteForce);
+        let params = backend::Params::default();
+        let options = IlpOptions::default();
+        solve(&inst, 1, &mut backend, &params, &options).unwrap();
+        for k in 2..4 {
+            let again = resolve(&inst, k, &mut backend, &params, &options).unwrap();
+            let mut fresh = Staged::new(BruteForce);
+            let sol = solve(&inst, k, &mut fresh, &params, &options).unwrap();
+            assert_eq!(again.objective, sol.objective);
+            assert_eq!(again.bound, Some(sol.objective));
+        }
+        let lazy = IlpOptions { lazy: true, ..Default::default() };
+        assert!(resolve(&inst, 2, &mut backend, &params, &lazy).is_err());
+    }
+
+    #[test]
+    fn greedy_selects_the_rows_of_the_adversarial_instance() {
+        let inst = generate_adversarial(3);
+        assert_eq!(inst.sets.len(), 6);
+        let mut sol = solvers::greedy::greedy(&inst, 3);
+        sol.sol.sort();
+        assert_eq!(sol.sol, vec![0, 1, 2]);
+        assert_eq!(sol.objective, 27.0 + 18.0 + 12.0);
+        let columns = inst.sets[3..].iter().flat_map(|s| s.iter().cloned()).collect();
+        assert_eq!(inst.ground, columns);
+        assert_eq!(inst.weights.unwrap().iter().sum::<f64>(), 3.0 * 27.0);
+    }
+}
@@ -1,149 +1,1246 @@
-#[macro_use]
-extern crate serde_derive;
+// Field names are written out and consts spell out their `'static` lifetimes, as the crate
+// always has.
+#![allow(clippy::redundant_field_names, clippy::redundant_static_lifetimes)]
+
 extern crate serde_json;
 extern crate docopt;
 extern crate rustc_serialize;
 extern crate rand;
-#[macro_use]
-extern crate rplex;
+extern crate maxcover;
 
-use std::collections::{BTreeSet, BTreeMap};
-use std::fs::File;
+use std::cell::RefCell;
+use std::collections::BTreeSet;
+use std::env;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::process;
+use std::iter;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
 use docopt::Docopt;
-use rand::{thread_rng, sample, Rng};
-use rand::distributions::{Range, IndependentSample};
-use rplex::*;
+use rand::{Rng, SeedableRng, StdRng, thread_rng};
+use maxcover::solvers::{self, Method};
+use maxcover::solvers::variant::Variant;
+use maxcover::solvers::{aco, anneal, beam, bnb, capacitated, cem, colgen, continuous, enumerate,
+                        ga, generalized, grasp, greedi, greedy, hillclimb, interdiction,
+                        lagrangian, maxsat, pareto, portfolio, probabilistic, stochastic, sweep,
+                        tabu, temporal, variant};
+#[cfg(feature = "cplex")]
+use maxcover::solvers::{hybrid, kernel, lns, lp};
+use maxcover::backend::{self, Progress, Registry, SolverBackend, FEATURE_BACKENDS};
+use maxcover::maxsat::Encoding;
+use maxcover::checkpoint::Checkpoint;
+use maxcover::perturb::Perturbation;
+use maxcover::suite::Suite;
+use maxcover::coverage::{Coverage, Index};
+use maxcover::{corpus, influence, interrupt, opb, reductions, stream, transactions, Distribution,
+               Generator, IlpOptions, Objective, Sensor, Solution};
 
 const USAGE: &'static str = "
 Constructs and (optimally) solves Maximum k-Coverage instances.
 
+An instance may constrain which sets may be selected together with its fields
+    groups, group_limits  a group per set, and the most sets selected from
+                          each group; k may then be left out
+    conflicts             cliques of sets of which at most one is selected
+    prerequisites         pairs [a, b] of sets where a is only selected with b
+    required, forbidden   sets that must or must not be selected
+    red, red_limit        elements outside the ground set, and the most of
+                          them the selection may cover; k may then be left out
+Only ilp, benders, greedy, lazy-greedy, stochastic-greedy, threshold-greedy,
+anneal, tabu, grasp and hillclimb keep to these, as does --polish. Only ilp,
+benders, and precedence greedy with greedy or lazy-greedy, which selects each
+set along with those it needs, keep to prerequisites.
+
+An instance may also give the probability that each set covers each of its
+elements, in its probabilities field, one list per set. solve then maximizes
+the expected weight covered, by exact greedy with greedy or lazy-greedy, or
+approximately by ilp over --samples samples of which sets cover which
+elements.
+
+An instance may give each set a capacity, the most elements it may serve, in
+its capacities field. An element then only counts if it is assigned to a
+selected set containing it, and solve maximizes the weight assigned, with ilp
+by an assignment variable per set and element, or by capacitated greedy with
+greedy or lazy-greedy. The solution's assignment field has the elements each
+selected set serves.
+
+An instance may give the profit of covering each element by each set, in its
+profits field, one list per set. An element is then worth the most profit of
+the selected sets containing it, which solve maximizes in total, with ilp by a
+variable per set and element that credits the element to the set, or by
+generalized greedy with greedy or lazy-greedy. The solution's assignment field
+has the elements credited to each selected set.
+
+An instance may give the time windows each set is active in, in its windows
+field, one list of windows 0, 1, ... per set; a set only covers its elements
+in its windows. An element then counts if it is covered in every window, or
+with --window-rule some in any of them. The same k sets serve every window,
+or with --per-window k sets are selected for each window, as in the
+solution's schedule field. Solved by ilp with a row per element and window,
+and by temporal greedy with greedy or lazy-greedy.
+
+An instance may label its elements, such as by demographic group, in its
+labels field, and ask in its min_coverage field for the least fraction of
+each label's weight that must be covered. ilp keeps to these with a row per
+label, and greedy and lazy-greedy by fair greedy, which first reaches every
+label's share and then continues as greedy.
+
+On Ctrl-C, solve stops the method, outputs the best solution found so far with
+status \"interrupted\" and exits with status 130. A second Ctrl-C quits at once.
+The in-process ilp backends, cplex, cbc, highs, scip and cpsat, cannot be
+interrupted and finish their solve first; bound them with --time-limit instead.
+
+influence picks k seed nodes of the graph in an edge list, one \"source target
+[probability]\" per line, to maximize their expected spread under the
+independent cascade model. It samples --rr-sets reverse-reachable sets, each
+the nodes that reach a random node over randomly live edges, and solves the
+max coverage of them by the nodes with --method: ilp, greedy, lazy-greedy or
+stochastic-greedy. The objective is the estimated spread. Edges without a
+probability get 1 over the in-degree of their target.
+
 Usage:
-    cover generate <output> <elements> <sets> [--max-size <size>]
-    cover solve <input> <k> [--threads <t>] [--write <name>]
+    cover generate <output> <elements> <sets> [options]
+    cover generate <output> --adversarial <k> [options]
+    cover generate <output> --model <m> --n <n> [options]
+    cover generate <output> --grid <size> <sets> --radius <r> [options]
+    cover generate-suite <config> <outdir>
+    cover solve <input> [<k>] [options] [--cplex-param <p>]... [--cut <c>]...
+    cover export <input> <k> <output> [--format <f>]
+    cover stream <input> <k> [--epsilon <e>] [--write <name>]
+    cover pareto <input> [options]
+    cover sweep <input> --k <range> [options]
+    cover influence <graph> <k> [options]
+    cover from-graph <graph> <output> [--problem <p>]
+    cover from-csv <file> <output> [--set-col <c>] [--element-col <c>] [--no-header]
+    cover from-text <dir> <output> [--stop-words <file>]
+    cover perturb <input> <output> [options]
+    cover resume <dir>
+    cover backends
     cover (-h | --help)
     cover --version
 
 Options:
     -h --help           Show this screen.
     --version           Show version.
+    --method <m>        Solution method: ilp, greedy, lazy-greedy,
+                        stochastic-greedy, anneal, ga, tabu, grasp,
+                        lp-pipage, lp-round, lagrangian, bnb,
+                        exact-smallk, maxsat, portfolio, auto, hybrid,
+                        lns, kernel, beam, threshold-greedy, greedi,
+                        reverse-greedy, aco, cem, hillclimb,
+                        continuous-greedy, benders or colgen. lp-pipage, lp-round,
+                        hybrid, lns and kernel need the cplex feature.
+                        benders solves a master problem over the sets with
+                        --backend, adding a coverage cut per solution; it
+                        suits instances with far more elements than sets.
+                        colgen starts ilp from the k largest sets and adds
+                        those the Lagrangian multipliers price highest, for
+                        --iterations subgradient steps a round, then solves
+                        with --backend.
+                        [default: ilp]
+    --backend <b>       MIP solver used by ilp, benders and colgen: cplex,
+                        gurobi, glpk, cbc, highs, scip, cpsat or external.
+                        gurobi and glpk run gurobi_cl and glpsol, which must
+                        be on the PATH; cplex, cbc, highs, scip and cpsat
+                        need the features of the same name; external runs
+                        --solver-cmd. If the backend is unusable here, ilp
+                        warns and falls back to the first usable one listed
+                        by `cover backends`. cpsat cannot solve benders'
+                        master problem, which has a continuous variable.
+                        [default: cplex]
+    --objective <o>     What solve optimizes: max-coverage, the elements
+                        covered by k sets, or set-cover, the number or cost
+                        of the sets that cover every element, for which k
+                        may be left out. set-cover is solved by ilp and by
+                        greedy set cover with greedy or lazy-greedy.
+                        prize-collecting maximizes the weight covered by any
+                        number of sets, up to k if given, less the costs of
+                        the sets and the penalties of the elements left
+                        uncovered, from the instance's costs and penalties
+                        fields. It is solved by ilp and by prize-collecting
+                        greedy with greedy or lazy-greedy.
+                        [default: max-coverage]
+    --coverage-target <f>  Only cover this fraction of the elements' weight,
+                        e.g. 0.9, with set-cover, which it implies.
+    --budget <b>        Bound on the total cost of the selected sets, from the
+                        costs of the instance or 1 per set, on top of k,
+                        which may then be left out. Supported by ilp, greedy
+                        and lazy-greedy, the latter two by budgeted greedy.
+    --require <ids>     Comma-separated sets every solution must include, on
+                        top of the instance's required sets.
+    --forbid <ids>      Comma-separated sets no solution may include, on top
+                        of the instance's forbidden sets.
+    --red-limit <n>     Most red elements the solution may cover, instead of
+                        the instance's red_limit.
+    --warm-start <f>    Solution file written by --write that ilp starts from.
+                        Defaults to the greedy solution.
+    --branch-priority <p>  Branching order of ilp's set variables: greedy
+                        (greedy's picks first), size (larger sets first) or
+                        none. Only the gurobi backend supports it; cplex
+                        rejects it. [default: none]
+    --pool <n>          Collect the n best distinct solutions with ilp, by
+                        re-solving with each found solution cut off. All of
+                        them are written to the pool field of the solution.
+    --lazy              Build ilp's model without the row of each element that
+                        ties it to the sets covering it, and add only the rows
+                        that the solutions violate, re-solving until none are.
+                        Much smaller models for instances with many elements.
+    --symmetry          Break the symmetry of interchangeable sets in ilp: of
+                        identical sets, and of same-size sets whose elements
+                        no other set has, a set is only selected after those
+                        before it.
+    --recourse <k2>     Two-stage stochastic coverage: select k sets before the
+                        scenario is known and k2 more once it is, maximizing
+                        the expected coverage over the equally likely
+                        scenarios of the instance's scenarios field. Solved by
+                        ilp with the deterministic equivalent, and by
+                        two-stage greedy with greedy or lazy-greedy. The
+                        solution's recourse field has each scenario's sets.
+    --robust            Maximize the least weight covered in any of the weight
+                        scenarios of the instance's scenarios field, with ilp
+                        and by robust greedy with greedy or lazy-greedy.
+    --interdict <b>     Max coverage under interdiction: an adversary then
+                        deletes up to b of the selected sets, and solve
+                        maximizes the coverage left. Only ilp solves it, by
+                        cutting planes over the adversary's attacks, each
+                        found by trying every b of the selected sets. The
+                        solution's attack field has the deleted sets.
+    --overlap-penalty <l>  Take l off the objective each time an element is
+                        covered beyond the first, favoring disjoint sets.
+                        Solved by ilp with an integer variable per element
+                        for its extra coverage, and by overlap-penalized
+                        greedy with greedy or lazy-greedy.
+    --window-rule <r>   When elements of an instance with windows count as
+                        covered: every or some window. [default: every]
+    --per-window        Select k sets for each window of an instance with
+                        windows, instead of the same k for all of them.
+    --cplex-param <p>   Set a parameter of the cplex backend, given as NAME=VALUE
+                        with its full name, e.g. CPXPARAM_Emphasis_MIP=1.
+                        May be repeated.
+    --workers <n>       CP-SAT search workers. Defaults to --threads.
+    --epsilon <e>       Accuracy of stochastic-greedy and threshold-greedy.
+                        [default: 0.1]
+    --iterations <n>    Iteration budget of iterative methods. Defaults to
+                        100000 for anneal, 1000 for tabu and lagrangian,
+                        100 for aco and cem and 50 for lns.
+    --schedule <s>      Annealing schedule: geometric or linear. [default: geometric]
+    --t0 <t>            Initial annealing temperature. [default: 1.0]
+    --cooling <c>       Geometric cooling factor. [default: 0.9999]
+    --population <n>    Genetic algorithm population size. [default: 50]
+    --mutation <p>      Per-gene mutation probability. [default: 0.05]
+    --generations <n>   Number of generations. [default: 200]
+    --tenure <t>        Tabu tenure in iterations. [default: 7]
+    --aspiration <a>    Tabu aspiration criterion: best or none. [default: best]
+    --alpha <a>         GRASP candidate list parameter in [0, 1]. [default: 0.2]
+    --restarts <n>      Number of restarts. [default: 10]
+    --trials <n>        Number of randomized LP roundings. [default: 100]
+    --free <m>          Greedy sets hybrid leaves free for the MIP. [default: 2]
+    --destroy <f>       Fraction of sets lns frees per iteration. [default: 0.3]
+    --repair-time <s>   CPLEX time limit for each restricted MIP of lns and
+                        kernel. [default: 10]
+    --bucket <b>        Kernel search bucket size. [default: 20]
+    --width <w>         Beam width. [default: 10]
+    --partitions <p>    Number of greedi partitions. [default: 4]
+    --ants <n>          Ants per aco iteration. [default: 10]
+    --evaporation <r>   Pheromone evaporation rate. [default: 0.1]
+    --samples <n>       Samples per cem iteration, per continuous-greedy
+                        estimate and of ilp's sample average approximation of
+                        probabilistic instances. [default: 100]
+    --rr-sets <n>       Reverse-reachable sets sampled by influence.
+                        [default: 10000]
+    --elite <f>         Elite fraction of cem samples. [default: 0.1]
+    --smoothing <a>     Cem probability smoothing weight. [default: 0.7]
+    --step <d>          Continuous-greedy step size. [default: 0.01]
+    --seed <s>          Seed for the randomized methods and the ilp backend,
+                        for reproducible runs. hillclimb uses it as the base
+                        seed of its restarts and chooses and prints one if
+                        it is omitted. generate records it in the instance,
+                        along with its parameters, and chooses one if it is
+                        omitted. perturb chooses and prints one if it is
+                        omitted.
+    --solver-cmd <exe>  External solver command used by maxsat and by the
+                        external backend, where {lp} and {sol} stand for
+                        the model and solution files.
+    --polish <p>        Improve the solution afterwards. Currently only swap.
     --threads <t>       Set number of threads used.
+    --time-limit <s>    Wall-clock limit in seconds for portfolio and ilp. On
+                        the limit ilp reports its incumbent, with the best
+                        bound and gap where the backend provides them; the
+                        cplex backend cannot, and warns that it has none.
+    --gap <g>           Relative MIP gap at which ilp stops, e.g. 0.01.
+    --abs-gap <g>       Absolute MIP gap at which ilp stops. The solution
+                        records the best bound and the gap achieved, where
+                        the backend reports them.
+    --mem-limit <mb>    Memory in megabytes ilp may use for its search tree.
+                        With a disk node file CPLEX and Gurobi write nodes
+                        to disk beyond it; SCIP, GLPK and Gurobi otherwise
+                        stop there instead.
+    --node-file <f>     Where ilp keeps nodes beyond the memory limit: off,
+                        memory (compressed), disk or disk-compressed. Only
+                        used by CPLEX and Gurobi.
+    --node-dir <dir>    Directory for node files on disk.
+    --emphasis <e>      What ilp's search concentrates on: balanced,
+                        feasibility, optimality or bound. Used by CPLEX and
+                        Gurobi.
+    --cuts <l>          Cut level of ilp: off, auto, moderate or aggressive.
+    --cut <c>           Cut level of one family, overriding --cuts, as
+                        FAMILY=LEVEL. Families are clique, cover, flow-cover,
+                        gomory, gub-cover, implied, lift-project, mir and
+                        zero-half; backends skip those they lack. May be
+                        repeated.
+    --solution-limit <n>  Stop ilp after n improving solutions, the warm start
+                        included.
+    --first-feasible    Stop ilp at the first solution, i.e. --solution-limit 1.
+    --progress <f>      Append each new ilp incumbent to <f> as a line of JSON
+                        with its time, objective, bound and gap. Incumbents
+                        are printed either way, by backends that report
+                        them: gurobi and glpk.
+    --anytime <f>       Keep the best ilp incumbent so far in <f>, replacing it
+                        atomically as it improves, for backends that report
+                        incumbents with their solution (gurobi). The final
+                        solution of any method is written there too.
+    --checkpoint <dir>  Save the state of the solve to <dir> periodically and
+                        at the end, for `cover resume <dir>`. Resuming ilp
+                        starts from the saved incumbent with the rest of the
+                        time limit. The state of the other methods is not
+                        saved: a resumed heuristic restarts from its seed,
+                        repeating its work with the rest of the time limit,
+                        and keeps the saved incumbent if it is better.
+    --checkpoint-interval <s>  Seconds between checkpoints. [default: 60]
     --max-size <size>   Maximum set size.
-    --write <name>      Write solution to <name>.
+    --density <p>       Generate sets that contain each element independently
+                        with probability p, rather than distinct sets of
+                        uniformly random sizes.
+    --power-law <a>     Generate sets whose sizes x follow a power law, with
+                        probability proportional to x^-a, up to --max-size,
+                        and whose elements are drawn by popularity.
+    --zipf <s>          Popularity of the elements with --power-law: element
+                        r, from 0, is drawn in proportion to (r + 1)^-s.
+                        Defaults to 1.
+    --planted <k>       Plant k disjoint sets that cover every element among
+                        the generated sets, in place of k of them, and write
+                        this optimal solution to <output>.planted.json.
+    --radius <r>        Generate the elements as random points in the unit
+                        square, and the sets as the points within r of
+                        random centers, saving the coordinates of the points
+                        and centers in the instance.
+    --grid <size>       Generate the elements as the points of a grid of size
+                        <rows>x<cols>, and the sets as sensors at random
+                        points that cover the points within --radius, saving
+                        the coordinates of the points and sensors in the
+                        instance.
+    --sensor <s>        Shape of the area that each sensor of --grid covers:
+                        ball, or rect, a rectangle around the sensor whose
+                        half-width and half-height are random, up to
+                        --radius. [default: ball]
+    --communities <c>   Split the elements into c communities, and generate
+                        sets that each draw their elements mostly from one
+                        of them, saving the community of each element in
+                        the instance's labels and of each set in its groups.
+    --mixing <p>        Probability that each element of a set with
+                        --communities comes from outside its community.
+                        [default: 0.1]
+    --nested <p>        Generate each set as a random subset of a random
+                        earlier set, keeping each of its elements with
+                        probability p, or now and then as a new random set,
+                        for hierarchies of heavily overlapping sets.
+    --intervals         Generate the elements as points on a line, in order,
+                        and the sets as random intervals of them, tagging
+                        the instance's structure as interval.
+    --model <m>         What to generate instead of random sets: graph, the
+                        closed neighborhoods of the nodes of a random graph
+                        on --n nodes, as dominating set instances have.
+    --graph-model <g>   Random graph model of --model graph: er, Erdos-Renyi,
+                        or ba, Barabasi-Albert. [default: er]
+    --n <n>             Number of nodes of --model graph.
+    --degree <d>        Average degree of --model graph. [default: 4]
+    --adversarial <k>   Generate the instance on which greedy with k sets
+                        covers only 1 - (1 - 1/k)^k of the optimum, for k up
+                        to 13, writing the optimum as --planted does.
+    --weights <d>       Draw a random weight for each element from d: uniform,
+                        over [0.5, 1.5), lognormal, with mean 1, or size,
+                        in proportion to the number of sets containing the
+                        element, times a uniform value.
+    --costs <d>         Draw a random cost for each set from d, as for
+                        --weights, where size is the size of the set.
+    --scenarios <n>     Sample n weight scenarios into the generated instance,
+                        for --robust and --recourse.
+    --presence <p>      Probability that an element keeps its weight in each
+                        sampled scenario, and weighs 0 otherwise.
+                        [default: 0.5]
+    --tradeoff <t>      What pareto trades coverage against: cost, the total
+                        cost of the sets from the instance's costs, or k,
+                        their number. Each point of the frontier is an ilp
+                        solve with --backend, within a budget, or k, just
+                        below the cost of the last. [default: cost]
+    --resolution <d>    How far below the last point's cost pareto sets the
+                        next budget. [default: 0.0001]
+    --k <range>         The k sweep solves for, as a..b, from a to b, or b,
+                        from 1 to b. Each point is an ilp solve with
+                        --backend, warm started from the last, of the same
+                        model with only k changed unless --lazy, or with
+                        greedy or lazy-greedy the greedy selection's first k
+                        sets, from a single run.
+    --frontier <f>      Format of pareto's frontier and sweep's curve: json, a
+                        list of points with their cost or k, coverage and
+                        sets, or csv. [default: json]
+    --write <name>      Write solution, pareto's frontier or sweep's curve to
+                        <name>.
+    --format <f>        Export format: wcnf, opb or sets (the line-based
+                        format read by stream). [default: wcnf]
+    --problem <p>       Graph problem from-graph writes the covering instance
+                        of, with a set per node: dominating-set, covering the
+                        nodes with closed neighborhoods, or vertex-cover,
+                        covering the edges with those at each node. Solve it
+                        with --objective set-cover for the least such nodes.
+                        [default: dominating-set]
+    --set-col <c>       Column of the set labels of from-csv, by its name in
+                        the header or its number from 0. [default: 0]
+    --element-col <c>   Column of the element labels of from-csv, by its name
+                        in the header or its number from 0. [default: 1]
+    --no-header         The first row of from-csv's file is data, not a
+                        header.
+    --stop-words <file>  Words, separated by whitespace, that from-text leaves
+                        out of the vocabulary.
+    --add-noise <p>     Probability that perturb adds each element to each set
+                        without it. [default: 0]
+    --drop <q>          Probability that perturb removes each element from
+                        each set. [default: 0]
+    --drop-sets <r>     Probability that perturb removes each set, but for the
+                        required sets. [default: 0]
+    --add-sets <n>      Number of random sets that perturb adds. [default: 0]
 ";
 
 #[derive(Debug, RustcDecodable)]
+#[cfg_attr(not(feature = "cplex"), allow(dead_code))]
 struct Args {
     cmd_generate: bool,
+    cmd_generate_suite: bool,
     cmd_solve: bool,
+    cmd_export: bool,
+    cmd_stream: bool,
+    cmd_pareto: bool,
+    cmd_sweep: bool,
+    cmd_influence: bool,
+    cmd_from_graph: bool,
+    cmd_from_csv: bool,
+    cmd_from_text: bool,
+    cmd_perturb: bool,
+    cmd_resume: bool,
+    cmd_backends: bool,
     arg_elements: Option<usize>,
     arg_sets: Option<usize>,
-    // arg_density: Option<f32>,
     arg_output: Option<String>,
     arg_input: Option<String>,
+    arg_graph: Option<String>,
+    arg_file: Option<String>,
+    arg_config: Option<String>,
+    arg_outdir: Option<String>,
     arg_k: Option<usize>,
+    arg_dir: Option<String>,
+    flag_method: String,
+    flag_backend: String,
+    flag_objective: String,
+    flag_coverage_target: Option<f64>,
+    flag_budget: Option<f64>,
+    flag_warm_start: Option<String>,
+    flag_branch_priority: String,
+    flag_pool: Option<usize>,
+    flag_lazy: bool,
+    flag_symmetry: bool,
+    flag_robust: bool,
+    flag_recourse: Option<usize>,
+    flag_interdict: Option<usize>,
+    flag_overlap_penalty: Option<f64>,
+    flag_window_rule: String,
+    flag_per_window: bool,
+    flag_cplex_param: Vec<String>,
+    flag_workers: Option<usize>,
+    flag_epsilon: f64,
+    flag_iterations: Option<usize>,
+    flag_schedule: String,
+    flag_t0: f64,
+    flag_cooling: f64,
+    flag_population: usize,
+    flag_mutation: f64,
+    flag_generations: usize,
+    flag_tenure: usize,
+    flag_aspiration: String,
+    flag_alpha: f64,
+    flag_restarts: usize,
+    flag_trials: usize,
+    flag_free: usize,
+    flag_destroy: f64,
+    flag_repair_time: f64,
+    flag_bucket: usize,
+    flag_width: usize,
+    flag_partitions: usize,
+    flag_ants: usize,
+    flag_evaporation: f64,
+    flag_samples: usize,
+    flag_rr_sets: usize,
+    flag_elite: f64,
+    flag_smoothing: f64,
+    flag_step: f64,
+    flag_seed: Option<usize>,
+    flag_solver_cmd: Option<String>,
+    flag_polish: Option<String>,
+    flag_require: Option<String>,
+    flag_forbid: Option<String>,
+    flag_red_limit: Option<usize>,
     flag_threads: Option<usize>,
+    flag_time_limit: Option<f64>,
+    flag_gap: Option<f64>,
+    flag_abs_gap: Option<f64>,
+    flag_mem_limit: Option<f64>,
+    flag_node_file: Option<String>,
+    flag_node_dir: Option<String>,
+    flag_emphasis: Option<String>,
+    flag_cuts: Option<String>,
+    flag_cut: Vec<String>,
+    flag_solution_limit: Option<usize>,
+    flag_first_feasible: bool,
+    flag_progress: Option<String>,
+    flag_anytime: Option<String>,
+    flag_checkpoint: Option<String>,
+    flag_checkpoint_interval: f64,
     flag_max_size: Option<usize>,
+    flag_density: Option<f64>,
+    flag_power_law: Option<f64>,
+    flag_zipf: Option<f64>,
+    flag_planted: Option<usize>,
+    flag_adversarial: Option<usize>,
+    flag_radius: Option<f64>,
+    flag_grid: Option<String>,
+    flag_sensor: String,
+    flag_communities: Option<usize>,
+    flag_mixing: f64,
+    flag_nested: Option<f64>,
+    flag_intervals: bool,
+    flag_model: Option<String>,
+    flag_graph_model: String,
+    flag_n: Option<usize>,
+    flag_degree: f64,
+    flag_weights: Option<String>,
+    flag_costs: Option<String>,
+    flag_scenarios: Option<usize>,
+    flag_presence: f64,
+    flag_tradeoff: String,
+    flag_resolution: f64,
+    flag_k: Option<String>,
+    flag_frontier: String,
     flag_write: Option<String>,
+    flag_format: String,
+    flag_problem: String,
+    flag_set_col: String,
+    flag_element_col: String,
+    flag_no_header: bool,
+    flag_stop_words: Option<String>,
+    flag_add_noise: f64,
+    flag_drop: f64,
+    flag_drop_sets: f64,
+    flag_add_sets: usize,
+}
+
+/// Picks the most preferred usable backend after the requested one failed for `reason`.
+fn fall_back(registry: &Registry, reason: String) -> Box<dyn SolverBackend> {
+    let solver = registry.first_available()
+        .unwrap_or_else(|| panic!("{}, and no other backend is available", reason));
+    eprintln!("warning: {}; falling back to {}", reason, solver.name());
+    solver
+}
+
+/// The ilp backend parameters that `args` give.
+fn ilp_params(args: &Args) -> backend::Params {
+    backend::Params {
+        threads: args.flag_threads,
+        time_limit: args.flag_time_limit,
+        gap: args.flag_gap,
+        abs_gap: args.flag_abs_gap,
+        seed: args.flag_seed,
+        mem_limit: args.flag_mem_limit,
+        node_file: args.flag_node_file.as_ref().map(|f| f.parse().unwrap()),
+        node_dir: args.flag_node_dir.clone(),
+        emphasis: args.flag_emphasis.as_ref().map(|e| e.parse().unwrap()),
+        cuts: args.flag_cuts.as_ref().map(|l| l.parse().unwrap()),
+        cut_families: args.flag_cut
+            .iter()
+            .map(|cut| backend::parse_cut(cut).unwrap())
+            .collect(),
+        solution_limit: if args.flag_first_feasible {
+            Some(1)
+        } else {
+            args.flag_solution_limit
+        },
+    }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
-struct Instance {
-    ground: BTreeSet<usize>,
-    sets: Vec<BTreeSet<usize>>,
+/// The backend `--backend` names, set up by `args`, or the first usable one if it is not
+/// usable here.
+fn create_backend(args: &Args) -> Box<dyn SolverBackend> {
+    let registry = Registry::default();
+    let mut solver = registry.create(&args.flag_backend)
+        .unwrap_or_else(|reason| fall_back(&registry, reason));
+    if let Some(workers) = args.flag_workers {
+        solver.set_param("workers", &workers.to_string()).unwrap();
+    }
+    if let (Some(cmd), "external") = (args.flag_solver_cmd.as_ref(), solver.name()) {
+        solver.set_param("cmd", cmd).unwrap();
+    }
+    if let Err(reason) = solver.available() {
+        solver = fall_back(&registry, reason);
+    }
+    solver
+}
+
+/// Writes `sol` to `fname` by way of a temporary file, so that the file always holds a whole
+/// solution.
+fn write_atomic(fname: &str, sol: &Solution) {
+    let tmp = format!("{}.tmp", fname);
+    serde_json::to_writer_pretty(&mut File::create(&tmp).unwrap(), sol).unwrap();
+    fs::rename(&tmp, fname).unwrap();
+}
+
+/// Parses the comma-separated set indices given to `flag`.
+fn parse_sets(flag: &str, ids: &str) -> Vec<usize> {
+    ids.split(',')
+        .map(|id| id.trim().parse().unwrap_or_else(|_| panic!("bad set in {}: {}", flag, id)))
+        .collect()
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
-struct Solution {
-    objective: f64,
-    sol: Vec<usize>,
+/// Parses the `<rows>x<cols>` size of a grid.
+fn parse_grid(size: &str) -> (usize, usize) {
+    let bad = || panic!("bad grid size: {}", size);
+    let dims = size.split('x').map(|d| d.parse().unwrap_or_else(|_| bad())).collect::<Vec<_>>();
+    if dims.len() != 2 {
+        bad();
+    }
+    (dims[0], dims[1])
 }
 
-fn do_generate(num_elements: usize, num_sets: usize, max_size: Option<usize>) -> Instance {
-    let ground: BTreeSet<usize> = (0..num_elements).collect();
-    let mut rng = thread_rng();
-    let range = Range::new(1, max_size.unwrap_or(num_elements + 1));
-
-    let mut sets = BTreeSet::new();
-
-    for _ in 0..num_sets {
-        loop {
-            let size = range.ind_sample(&mut rng);
-            let set: BTreeSet<usize> =
-                sample(&mut rng, &ground, size).into_iter().map(|&x| x).collect();
-            if !sets.contains(&set) {
-                sets.insert(set);
-                break;
+/// Runs `cover solve`, continuing from `resume` if given.
+fn solve(mut args: Args, resume: Option<Checkpoint>) {
+    interrupt::install_interrupt("interrupted; stopping with the best solution so far");
+    if let Some(ref checkpoint) = resume {
+        if checkpoint.finished {
+            if let Some(ref sol) = checkpoint.incumbent {
+                println!("{:?}", sol);
             }
+            return;
         }
+        let elapsed = checkpoint.elapsed;
+        args.flag_time_limit = args.flag_time_limit.map(|limit| (limit - elapsed).max(0.0));
+    }
+    let mut inst = maxcover::read(args.arg_input.as_ref().unwrap()).unwrap();
+    if let Some(ref ids) = args.flag_require {
+        inst.required.get_or_insert_with(Vec::new).extend(parse_sets("--require", ids));
+    }
+    if let Some(ref ids) = args.flag_forbid {
+        inst.forbidden.get_or_insert_with(Vec::new).extend(parse_sets("--forbid", ids));
+    }
+    if args.flag_red_limit.is_some() {
+        inst.red_limit = args.flag_red_limit;
+    }
+    let required = inst.required();
+    if let Err(e) = inst.check_selection(&required) {
+        panic!("the required sets are infeasible: {}", e);
+    }
+    let target = args.flag_coverage_target.unwrap_or(1.0);
+    if !(target > 0.0 && target <= 1.0) {
+        panic!("--coverage-target must be in (0, 1], not {}", target);
+    }
+    let objective = match args.flag_coverage_target {
+        Some(_) => Objective::SetCover,
+        None => args.flag_objective.parse::<Objective>().unwrap(),
+    };
+    let robust = args.flag_robust;
+    let recourse = args.flag_recourse;
+    let probabilistic = inst.probabilities.is_some();
+    let fair = inst.min_coverage.is_some();
+    let overlap = args.flag_overlap_penalty;
+    let capacitated = inst.capacities.is_some();
+    let generalized = inst.profits.is_some();
+    let interdict = args.flag_interdict;
+    let temporal = inst.windows.is_some();
+    let rule = args.flag_window_rule.parse::<temporal::Rule>().unwrap();
+    let per_window = args.flag_per_window;
+    let k = match args.arg_k {
+        Some(k) => k,
+        None if objective != Objective::MaxCoverage || args.flag_budget.is_some() ||
+                inst.group_limits.is_some() || inst.red_limit.is_some() => inst.sets.len(),
+        None => panic!("k is required without --budget, group limits or a red limit"),
+    };
+    if required.len() > k {
+        panic!("{} sets are required, more than k = {}", required.len(), k);
+    }
+    let method = match args.flag_method.parse::<Method>().unwrap() {
+        Method::Auto => {
+            let stats = solvers::Stats::new(&inst);
+            let method = solvers::auto(&stats);
+            eprintln!("auto: {} elements, {} sets, density {:.4}; using {:?}",
+                      stats.elements,
+                      stats.sets,
+                      stats.density,
+                      method);
+            method
+        }
+        method => method,
+    };
+    let variant = Variant {
+        objective: objective,
+        robust: robust,
+        recourse: recourse,
+        overlap: overlap,
+        interdict: interdict,
+        per_window: per_window,
+        budget: args.flag_budget.is_some(),
+        pool: args.flag_pool.is_some(),
+        polish: args.flag_polish.is_some(),
+        lazy: args.flag_lazy,
+        symmetry: args.flag_symmetry,
+        warm_start: args.flag_warm_start.is_some(),
+        checkpoint: args.flag_checkpoint.is_some(),
+    };
+    if let Err(e) = variant::check(&inst, &variant, method) {
+        panic!("{}", e);
+    }
+    // checkpointed runs record their seed, so that resuming replays the randomized methods
+    let seed_given = args.flag_seed.is_some();
+    if args.flag_checkpoint.is_some() && !seed_given {
+        args.flag_seed = Some(thread_rng().gen());
+    }
+    let elapsed = resume.as_ref().map_or(0.0, |checkpoint| checkpoint.elapsed);
+    let started = Instant::now();
+    let seed = args.flag_seed;
+    let interval = Duration::from_millis((args.flag_checkpoint_interval * 1000.0) as u64);
+    let checkpoint = args.flag_checkpoint.clone().map(|dir| {
+        let checkpoint = resume.clone().unwrap_or_else(|| {
+            let mut cmd = env::args().skip(1).collect::<Vec<_>>();
+            if !seed_given {
+                cmd.push("--seed".to_string());
+                cmd.push(seed.unwrap().to_string());
+            }
+            Checkpoint::new(cmd)
+        });
+        let state = Arc::new(Mutex::new(checkpoint));
+        let saved = state.clone();
+        let saved_dir = dir.clone();
+        thread::spawn(move || loop {
+            thread::sleep(interval);
+            let mut checkpoint = saved.lock().unwrap();
+            checkpoint.elapsed = elapsed + backend::seconds_since(started);
+            if let Err(e) = checkpoint.save(&saved_dir) {
+                eprintln!("warning: {}", e);
+            }
+        });
+        (dir, state)
+    });
+    let mut rng = match args.flag_seed {
+        Some(seed) => StdRng::from_seed(&[seed][..]),
+        None => StdRng::new().unwrap(),
+    };
+    let sol = match method {
+        Method::Ilp | Method::Benders | Method::ColumnGeneration => {
+            let params = ilp_params(&args);
+            let mut solver = create_backend(&args);
+            let mut progress_file = args.flag_progress.as_ref().map(|fname| {
+                let f = OpenOptions::new().create(true).append(true).open(fname).unwrap();
+                BufWriter::new(f)
+            });
+            let anytime = args.flag_anytime.clone();
+            // the prize-collecting model's objective counts the penalties of every element
+            let shift = match objective {
+                Objective::PrizeCollecting => inst.penalties().iter().sum::<f64>(),
+                _ => 0.0,
+            };
+            // colgen's incumbents select from its restricted instance, so they are not kept
+            let keep_incumbents = method != Method::ColumnGeneration;
+            let last = Rc::new(RefCell::new(None));
+            let last_reported = last.clone();
+            let checkpoint_reported = checkpoint.as_ref().map(|(_, state)| state.clone());
+            solver.set_progress(Box::new(move |p: &Progress| {
+                eprintln!("incumbent: {:.2}s objective {} bound {} gap {}",
+                          p.time,
+                          p.objective - shift,
+                          p.bound.map_or("-".to_string(), |b| (b - shift).to_string()),
+                          p.gap.map_or("-".to_string(), |g| format!("{:.4}", g)));
+                if let Some(ref mut f) = progress_file {
+                    serde_json::to_writer(&mut *f, p).unwrap();
+                    writeln!(f).unwrap();
+                    f.flush().unwrap();
+                }
+                if let (Some(sets), true) = (p.sets.clone(), keep_incumbents) {
+                    let mut sol = Solution::new(p.objective - shift, sets);
+                    sol.bound = p.bound.map(|b| b - shift);
+                    sol.gap = p.gap;
+                    if let Some(ref fname) = anytime {
+                        write_atomic(fname, &sol);
+                    }
+                    if let Some(ref state) = checkpoint_reported {
+                        state.lock().unwrap().offer(&sol);
+                    }
+                    *last_reported.borrow_mut() = Some(sol);
+                }
+            }));
+            if !args.flag_cplex_param.is_empty() && solver.name() != "cplex" {
+                eprintln!("warning: ignoring --cplex-param with the {} backend", solver.name());
+            } else {
+                for param in &args.flag_cplex_param {
+                    let mut parts = param.splitn(2, '=');
+                    match (parts.next(), parts.next()) {
+                        (Some(name), Some(value)) => solver.set_param(name, value).unwrap(),
+                        _ => panic!("--cplex-param must be NAME=VALUE, not {}", param),
+                    }
+                }
+            }
+            let warm_start = args.flag_warm_start.as_ref().map(|fname| {
+                let f = BufReader::new(File::open(fname).unwrap());
+                serde_json::from_reader::<_, Solution>(f).unwrap().sol
+            });
+            let resumed = resume.as_ref().and_then(|checkpoint| checkpoint.incumbent.as_ref());
+            let options = IlpOptions {
+                start: resumed.map(|sol| sol.sol.clone()).or(warm_start),
+                priority: args.flag_branch_priority.parse().unwrap(),
+                lazy: args.flag_lazy,
+                symmetry: args.flag_symmetry,
+                budget: args.flag_budget,
+            };
+            let result = match args.flag_pool {
+                _ if objective == Objective::SetCover => {
+                    let start = options.start.as_ref().map(|sets| &sets[..]);
+                    maxcover::solve_set_cover(&inst, &mut *solver, &params, start, target)
+                }
+                _ if objective == Objective::PrizeCollecting => {
+                    let start = options.start.as_ref().map(|sets| &sets[..]);
+                    maxcover::solve_prize_collecting(&inst, k, &mut *solver, &params, start)
+                }
+                _ if robust => {
+                    let start = options.start.as_ref().map(|sets| &sets[..]);
+                    maxcover::solve_robust(&inst, k, &mut *solver, &params, start)
+                }
+                _ if overlap.is_some() => {
+                    let start = options.start.as_ref().map(|sets| &sets[..]);
+                    let penalty = overlap.unwrap();
+                    maxcover::solve_overlap(&inst, k, penalty, &mut *solver, &params, start)
+                }
+                _ if recourse.is_some() => {
+                    let k2 = recourse.unwrap();
+                    stochastic::two_stage(&inst, k, k2, &mut *solver, &params)
+                }
+                _ if interdict.is_some() => {
+                    let b = interdict.unwrap();
+                    interdiction::interdiction(&inst, k, b, &mut *solver, &params)
+                }
+                _ if capacitated => capacitated::capacitated(&inst, k, &mut *solver, &params),
+                _ if generalized => generalized::generalized(&inst, k, &mut *solver, &params),
+                _ if temporal => {
+                    temporal::temporal(&inst, k, rule, per_window, &mut *solver, &params)
+                }
+                _ if probabilistic => {
+                    probabilistic::sample_average(&inst,
+                                                  k,
+                                                  args.flag_samples,
+                                                  &mut *solver,
+                                                  &params,
+                                                  &mut rng)
+                }
+                _ if method == Method::Benders => {
+                    let start = options.start.as_ref().map(|sets| &sets[..]);
+                    solvers::benders::benders(&inst, k, &mut *solver, &params, start)
+                }
+                _ if method == Method::ColumnGeneration => {
+                    let mut colgen_params = colgen::Params::default();
+                    if let Some(iterations) = args.flag_iterations {
+                        colgen_params.lagrangian.iterations = iterations;
+                    }
+                    colgen::colgen_list(&inst, k, &mut *solver, &colgen_params, &params)
+                }
+                Some(0) => panic!("--pool must be at least 1"),
+                Some(n) => {
+                    maxcover::solve_pool(&inst, k, &mut *solver, &params, &options, n)
+                        .map(|pool| Solution { pool: Some(pool.clone()), ..pool[0].clone() })
+                }
+                None => maxcover::solve(&inst, k, &mut *solver, &params, &options),
+            };
+            match result {
+                Ok(sol) => sol,
+                // an interrupted solver may exit without a solution, so fall back to the
+                // last incumbent it reported, or else to the warm start
+                Err(e) if interrupt::interrupted() => {
+                    eprintln!("warning: {}", e);
+                    let last = last.borrow_mut().take();
+                    last.unwrap_or_else(|| if objective == Objective::SetCover {
+                        greedy::greedy_set_cover(&inst, target).unwrap()
+                    } else if objective == Objective::PrizeCollecting {
+                        greedy::prize_collecting_greedy(&inst, k)
+                    } else if robust {
+                        greedy::robust_greedy(&inst, k)
+                    } else if let Some(penalty) = overlap {
+                        greedy::overlap_greedy(&inst, k, penalty)
+                    } else if let Some(k2) = recourse {
+                        stochastic::two_stage_greedy(&inst, k, k2)
+                    } else if probabilistic {
+                        probabilistic::probabilistic_greedy(&inst, k)
+                    } else if let Some(b) = interdict {
+                        let start = greedy::lazy_greedy(&inst, k).sol;
+                        let (objective, attack) =
+                            interdiction::worst_attack(&Index::new(&inst), &start, b);
+                        let mut sol = Solution::new(objective, start);
+                        sol.attack = Some(attack);
+                        sol
+                    } else if capacitated {
+                        capacitated::capacitated_greedy(&inst, k)
+                    } else if generalized {
+                        generalized::generalized_greedy(&inst, k)
+                    } else if temporal {
+                        temporal::temporal_greedy(&inst, k, rule, per_window)
+                    } else {
+                        let budget = options.budget;
+                        let start = options.start.unwrap_or_else(|| match budget {
+                            Some(budget) => greedy::budgeted_greedy(&inst, k, budget).sol,
+                            None if inst.has_prerequisites() => {
+                                greedy::precedence_greedy(&inst, k).sol
+                            }
+                            None if fair => {
+                                greedy::fair_greedy(&inst, k)
+                                    .unwrap_or_else(|_| greedy::lazy_greedy(&inst, k))
+                                    .sol
+                            }
+                            None => greedy::lazy_greedy(&inst, k).sol,
+                        });
+                        let objective = Coverage::from_sets(&Index::new(&inst), &start).value();
+                        Solution::new(objective, start)
+                    })
+                }
+                Err(e) => panic!("{}", e),
+            }
+        }
+        Method::Greedy | Method::LazyGreedy if objective == Objective::SetCover => {
+            greedy::greedy_set_cover(&inst, target).unwrap()
+        }
+        Method::Greedy | Method::LazyGreedy if objective == Objective::PrizeCollecting => {
+            greedy::prize_collecting_greedy(&inst, k)
+        }
+        Method::Greedy | Method::LazyGreedy if robust => greedy::robust_greedy(&inst, k),
+        Method::Greedy | Method::LazyGreedy if overlap.is_some() => {
+            greedy::overlap_greedy(&inst, k, overlap.unwrap())
+        }
+        Method::Greedy | Method::LazyGreedy if fair => greedy::fair_greedy(&inst, k).unwrap(),
+        Method::Greedy | Method::LazyGreedy if inst.has_prerequisites() => {
+            greedy::precedence_greedy(&inst, k)
+        }
+        Method::Greedy | Method::LazyGreedy if probabilistic => {
+            probabilistic::probabilistic_greedy(&inst, k)
+        }
+        Method::Greedy | Method::LazyGreedy if capacitated => {
+            capacitated::capacitated_greedy(&inst, k)
+        }
+        Method::Greedy | Method::LazyGreedy if generalized => {
+            generalized::generalized_greedy(&inst, k)
+        }
+        Method::Greedy | Method::LazyGreedy if temporal => {
+            temporal::temporal_greedy(&inst, k, rule, per_window)
+        }
+        Method::Greedy | Method::LazyGreedy if recourse.is_some() => {
+            stochastic::two_stage_greedy(&inst, k, recourse.unwrap())
+        }
+        Method::Greedy | Method::LazyGreedy if args.flag_budget.is_some() => {
+            greedy::budgeted_greedy(&inst, k, args.flag_budget.unwrap())
+        }
+        Method::Greedy => greedy::greedy(&inst, k),
+        Method::LazyGreedy => greedy::lazy_greedy(&inst, k),
+        Method::StochasticGreedy => {
+            greedy::stochastic_greedy(&inst, k, args.flag_epsilon, &mut rng)
+        }
+        Method::Anneal => {
+            let params = anneal::Params {
+                iterations: args.flag_iterations.unwrap_or(100000),
+                schedule: anneal::Schedule::new(&args.flag_schedule,
+                                                args.flag_t0,
+                                                args.flag_cooling)
+                    .unwrap(),
+            };
+            anneal::anneal(&inst, k, &params, &mut rng)
+        }
+        Method::Genetic => {
+            let params = ga::Params {
+                population: args.flag_population,
+                mutation: args.flag_mutation,
+                generations: args.flag_generations,
+            };
+            ga::ga(&inst, k, &params, &mut rng)
+        }
+        Method::Tabu => {
+            let params = tabu::Params {
+                iterations: args.flag_iterations.unwrap_or(1000),
+                tenure: args.flag_tenure,
+                aspiration: tabu::Aspiration::new(&args.flag_aspiration).unwrap(),
+            };
+            tabu::tabu(&inst, k, &params, &mut rng)
+        }
+        Method::Grasp => {
+            let params = grasp::Params {
+                alpha: args.flag_alpha,
+                restarts: args.flag_restarts,
+            };
+            grasp::grasp(&inst, k, &params, &mut rng)
+        }
+        #[cfg(feature = "cplex")]
+        Method::LpPipage => lp::lp_pipage(&inst, k, args.flag_threads).unwrap(),
+        #[cfg(feature = "cplex")]
+        Method::LpRound => {
+            lp::lp_round(&inst, k, args.flag_trials, args.flag_threads, &mut rng)
+                .unwrap()
+        }
+        Method::Lagrangian => {
+            let mut params = lagrangian::Params::default();
+            if let Some(iterations) = args.flag_iterations {
+                params.iterations = iterations;
+            }
+            lagrangian::lagrangian(&inst, k, &params)
+        }
+        Method::BranchAndBound => bnb::bnb(&inst, k),
+        Method::ExactSmallK => enumerate::exact_small_k(&inst, k),
+        Method::MaxSat => {
+            let cmd = args.flag_solver_cmd.expect("maxsat requires --solver-cmd");
+            maxsat::maxsat(&inst, k, &cmd).unwrap()
+        }
+        Method::Portfolio => {
+            portfolio::portfolio(&inst, k, args.flag_threads, args.flag_time_limit).unwrap()
+        }
+        Method::Auto => unreachable!(),
+        #[cfg(feature = "cplex")]
+        Method::Hybrid => hybrid::hybrid(&inst, k, args.flag_free, args.flag_threads).unwrap(),
+        #[cfg(feature = "cplex")]
+        Method::Lns => {
+            let params = lns::Params {
+                iterations: args.flag_iterations.unwrap_or(50),
+                destroy: args.flag_destroy,
+                repair_time: args.flag_repair_time,
+            };
+            lns::lns(&inst, k, &params, args.flag_threads, &mut rng).unwrap()
+        }
+        #[cfg(feature = "cplex")]
+        Method::Kernel => {
+            let params = kernel::Params {
+                bucket: args.flag_bucket,
+                time_limit: args.flag_repair_time,
+            };
+            kernel::kernel(&inst, k, &params, args.flag_threads).unwrap()
+        }
+        #[cfg(not(feature = "cplex"))]
+        Method::LpPipage | Method::LpRound | Method::Hybrid | Method::Lns | Method::Kernel => {
+            panic!("{:?} needs CPLEX; rebuild with the cplex feature", method)
+        }
+        Method::Beam => beam::beam(&inst, k, args.flag_width),
+        Method::ThresholdGreedy => greedy::threshold_greedy(&inst, k, args.flag_epsilon),
+        Method::Greedi => greedi::greedi(&inst, k, args.flag_partitions, &mut rng),
+        Method::ReverseGreedy => greedy::reverse_greedy(&inst, k),
+        Method::AntColony => {
+            let params = aco::Params {
+                iterations: args.flag_iterations.unwrap_or(100),
+                ants: args.flag_ants,
+                evaporation: args.flag_evaporation,
+                ..aco::Params::default()
+            };
+            aco::aco(&inst, k, &params, &mut rng)
+        }
+        Method::CrossEntropy => {
+            let params = cem::Params {
+                iterations: args.flag_iterations.unwrap_or(100),
+                samples: args.flag_samples,
+                elite: args.flag_elite,
+                smoothing: args.flag_smoothing,
+            };
+            cem::cem(&inst, k, &params, &mut rng)
+        }
+        Method::HillClimb => {
+            let seed = args.flag_seed.unwrap_or_else(|| {
+                let seed = thread_rng().gen();
+                eprintln!("seed: {}", seed);
+                seed
+            });
+            let params = hillclimb::Params {
+                restarts: args.flag_restarts,
+                seed: seed,
+            };
+            hillclimb::hillclimb(&inst, k, &params)
+        }
+        Method::ContinuousGreedy => {
+            let params = continuous::Params {
+                samples: args.flag_samples,
+                step: args.flag_step,
+            };
+            continuous::continuous_greedy(&inst, k, &params, &mut rng)
+        }
+    };
+    let mut sol = match args.flag_polish.as_ref().map(|p| &p[..]) {
+        Some("swap") => sol.polish_swap(&inst),
+        Some(p) => panic!("unknown polish: {}", p),
+        None => sol,
+    };
+    // a resumed heuristic starts over, so keep the checkpointed solution if it is better
+    if let Some(resumed) = resume.and_then(|checkpoint| checkpoint.incumbent) {
+        if resumed.objective > sol.objective {
+            sol = resumed;
+        }
+    }
+    let interrupted = interrupt::interrupted();
+    if interrupted {
+        sol.status = Some("interrupted".to_string());
+    } else {
+        sol.status = None;
+    }
+    if let Some((ref dir, ref state)) = checkpoint {
+        let mut checkpoint = state.lock().unwrap();
+        checkpoint.offer(&sol);
+        checkpoint.elapsed = elapsed + backend::seconds_since(started);
+        checkpoint.finished = !interrupted;
+        checkpoint.save(dir).unwrap();
     }
 
-    Instance {
-        ground: ground,
-        sets: sets.into_iter().collect(),
+    println!("{:?}", sol);
+    if let Some(ref fname) = args.flag_anytime {
+        write_atomic(fname, &sol);
+    }
+    if let Some(fname) = args.flag_write {
+        serde_json::to_writer_pretty(&mut File::create(fname).unwrap(), &sol).unwrap();
+    }
+    if interrupted {
+        process::exit(interrupt::EXIT_STATUS);
     }
 }
 
-fn write(inst: Instance, fname: &str) {
-    let mut f = File::create(fname).unwrap();
-    serde_json::to_writer(&mut f, &inst).unwrap();
-}
+/// `cover pareto`: sweeps the tradeoff and prints the frontier, or writes it to `--write`.
+fn pareto(args: Args) {
+    interrupt::install_interrupt("interrupted; stopping with the frontier so far");
+    let inst = maxcover::read(args.arg_input.as_ref().unwrap()).unwrap();
+    let modes = [("probabilities", inst.probabilities.is_some()),
+                 ("profits", inst.profits.is_some()),
+                 ("capacities", inst.capacities.is_some()),
+                 ("windows", inst.windows.is_some())];
+    if let Some(&(field, _)) = modes.iter().find(|&&(_, given)| given) {
+        panic!("pareto does not support {}", field);
+    }
+    let tradeoff = args.flag_tradeoff.parse::<pareto::Tradeoff>().unwrap();
+    let params = ilp_params(&args);
+    let mut solver = create_backend(&args);
+    let frontier = pareto::pareto(&inst, tradeoff, args.flag_resolution, &mut *solver, &params)
+        .unwrap();
 
-fn read(fname: &str) -> Result<Instance, serde_json::Error> {
-    let f = File::open(fname).unwrap();
-    serde_json::from_reader(&f)
+    let mut out: Box<dyn Write> = match args.flag_write {
+        Some(ref fname) => Box::new(BufWriter::new(File::create(fname).unwrap())),
+        None => Box::new(io::stdout()),
+    };
+    match &args.flag_frontier[..] {
+        "json" => {
+            serde_json::to_writer_pretty(&mut out, &frontier).unwrap();
+            writeln!(out).unwrap();
+        }
+        "csv" => pareto::write_csv(&frontier, &mut out).unwrap(),
+        format => panic!("unknown frontier format: {}", format),
+    }
+    if interrupt::interrupted() {
+        process::exit(interrupt::EXIT_STATUS);
+    }
 }
 
-fn do_solve(inst: Instance, k: usize, threads: Option<usize>, write: Option<String>) {
-    let mut env = Env::new().unwrap();
-    env.set_param(EnvParam::Threads(threads.unwrap_or(1) as u64)).unwrap();
-    env.set_param(EnvParam::ScreenOutput(true)).unwrap();
-    let mut prob = Problem::new(&env, "maxcover").unwrap();
-    let mut containment = BTreeMap::new();
-
-    prob.set_objective_type(ObjectiveType::Maximize).unwrap();
+/// `cover influence`: solves the max coverage of sampled RR sets and prints the seeds, or
+/// writes them to `--write`.
+fn influence(args: Args) {
+    interrupt::install_interrupt("interrupted; stopping with the best seeds so far");
+    let f = BufReader::new(File::open(args.arg_graph.as_ref().unwrap()).unwrap());
+    let graph = influence::Graph::read(f).unwrap();
+    let k = args.arg_k.unwrap();
+    let mut rng = match args.flag_seed {
+        Some(seed) => StdRng::from_seed(&[seed][..]),
+        None => StdRng::new().unwrap(),
+    };
+    let inst = influence::rr_instance(&graph, args.flag_rr_sets, &mut rng);
 
-    let element_vars = inst.ground
-        .iter()
-        .map(|&x| {
-            let name = format!("e{}", x);
-            prob.add_variable(var!(name -> 1.0 as Binary)).unwrap()
-        })
-        .collect::<Vec<_>>();
-
-    let set_vars = inst.sets
-        .iter()
-        .enumerate()
-        .map(|(i, set)| {
-            let name = format!("s{}", i);
-            for element in set.iter() {
-                containment.entry(element).or_insert_with(Vec::new).push(i);
+    let sol = match args.flag_method.parse::<Method>().unwrap() {
+        Method::Ilp => {
+            let params = ilp_params(&args);
+            let mut solver = create_backend(&args);
+            match maxcover::solve(&inst, k, &mut *solver, &params, &IlpOptions::default()) {
+                Ok(sol) => sol,
+                Err(e) if interrupt::interrupted() => {
+                    eprintln!("warning: {}", e);
+                    greedy::lazy_greedy(&inst, k)
+                }
+                Err(e) => panic!("{}", e),
             }
-            prob.add_variable(var!(name -> 0.0 as Binary)).unwrap()
-        })
-        .collect::<Vec<_>>();
+        }
+        Method::Greedy => greedy::greedy(&inst, k),
+        Method::LazyGreedy => greedy::lazy_greedy(&inst, k),
+        Method::StochasticGreedy => {
+            greedy::stochastic_greedy(&inst, k, args.flag_epsilon, &mut rng)
+        }
+        method => panic!("influence is not supported by {:?}", method),
+    };
 
-    for (&var, element) in element_vars.iter().zip(inst.ground.iter()) {
-        let name = format!("cover{}", element);
-        let mut con = con!(name: 0.0 <= sum containment.entry(element).or_insert_with(Vec::new).iter().map(|&i| &set_vars[i]));
-        con.add_wvar(WeightedVariable::new_idx(var, -1.0));
-        prob.add_constraint(con).unwrap();
+    println!("{:?}", sol);
+    if let Some(fname) = args.flag_write {
+        serde_json::to_writer_pretty(&mut File::create(fname).unwrap(), &sol).unwrap();
     }
-    prob.add_constraint(con!("cardinality": (k as f64) >= sum set_vars.iter())).unwrap();
-
-    prob.write("problem.lp").unwrap();
-    let sol = prob.solve().unwrap();
-    let sol_sets = set_vars.into_iter()
-        .filter(|&var| sol.variables[var] == VariableValue::Binary(true))
-        .collect::<Vec<_>>();
+    if interrupt::interrupted() {
+        process::exit(interrupt::EXIT_STATUS);
+    }
+}
 
-    let out_sol = Solution {
-        objective: sol.objective,
-        sol: sol_sets,
+/// `cover sweep`: solves for each k of the range and prints the coverage curve, or writes it
+/// to `--write`.
+fn sweep(args: Args) {
+    interrupt::install_interrupt("interrupted; stopping with the curve so far");
+    let inst = maxcover::read(args.arg_input.as_ref().unwrap()).unwrap();
+    let modes = [("probabilities", inst.probabilities.is_some()),
+                 ("profits", inst.profits.is_some()),
+                 ("capacities", inst.capacities.is_some()),
+                 ("windows", inst.windows.is_some())];
+    if let Some(&(field, _)) = modes.iter().find(|&&(_, given)| given) {
+        panic!("sweep does not support {}", field);
+    }
+    let (first, last) = sweep::parse_range(args.flag_k.as_ref().unwrap()).unwrap();
+    let curve = match args.flag_method.parse::<Method>().unwrap() {
+        Method::Ilp => {
+            let params = ilp_params(&args);
+            let mut solver = create_backend(&args);
+            let options = IlpOptions {
+                start: None,
+                priority: args.flag_branch_priority.parse().unwrap(),
+                lazy: args.flag_lazy,
+                symmetry: args.flag_symmetry,
+                budget: args.flag_budget,
+            };
+            sweep::ilp_sweep(&inst, first, last, &mut *solver, &params, &options).unwrap()
+        }
+        Method::Greedy | Method::LazyGreedy if inst.has_prerequisites() => {
+            panic!("greedy sweeps do not keep to prerequisites")
+        }
+        Method::Greedy | Method::LazyGreedy if args.flag_budget.is_some() => {
+            panic!("greedy sweeps do not keep to --budget")
+        }
+        Method::Greedy | Method::LazyGreedy => sweep::greedy_sweep(&inst, first, last),
+        method => panic!("sweep is not supported by {:?}", method),
     };
 
-    println!("{:?}", out_sol);
-    if let Some(fname) = write {
-        serde_json::to_writer_pretty(&mut File::create(fname).unwrap(), &out_sol).unwrap();
+    let mut out: Box<dyn Write> = match args.flag_write {
+        Some(ref fname) => Box::new(BufWriter::new(File::create(fname).unwrap())),
+        None => Box::new(io::stdout()),
+    };
+    match &args.flag_frontier[..] {
+        "json" => {
+            serde_json::to_writer_pretty(&mut out, &curve).unwrap();
+            writeln!(out).unwrap();
+        }
+        "csv" => sweep::write_csv(&curve, &mut out).unwrap(),
+        format => panic!("unknown frontier format: {}", format),
+    }
+    if interrupt::interrupted() {
+        process::exit(interrupt::EXIT_STATUS);
     }
 }
 
@@ -156,16 +1253,140 @@ fn main() {
         .unwrap_or_else(|e| e.exit());
 
     if args.cmd_generate {
-        let inst = do_generate(args.arg_elements.unwrap(),
-                               args.arg_sets.unwrap(),
-                               args.flag_max_size);
-        write(inst, &args.arg_output.unwrap());
+        let graph = match args.flag_model.as_ref().map(|m| &m[..]) {
+            Some("graph") => Some(args.flag_graph_model.parse::<reductions::GraphModel>().unwrap()),
+            Some(model) => panic!("unknown instance model: {}", model),
+            None => None,
+        };
+        let grid = args.flag_grid.as_ref().map(|size| parse_grid(size));
+        let (elements, sets) = match (args.flag_adversarial, args.flag_n, grid) {
+            (Some(k), _, _) => (k * (k + 1), 2 * k),
+            (None, Some(n), _) => (n, n),
+            (None, None, Some((rows, cols))) => (rows * cols, args.arg_sets.unwrap()),
+            (None, None, None) => (args.arg_elements.unwrap(), args.arg_sets.unwrap()),
+        };
+        let generator = Generator {
+            seed: args.flag_seed.unwrap_or_else(|| thread_rng().gen()),
+            elements: elements,
+            sets: sets,
+            max_size: args.flag_max_size,
+            density: args.flag_density,
+            power_law: args.flag_power_law,
+            zipf: args.flag_zipf,
+            planted: args.flag_planted,
+            adversarial: args.flag_adversarial,
+            radius: args.flag_radius,
+            grid: grid,
+            sensor: grid.map(|_| args.flag_sensor.parse::<Sensor>().unwrap()),
+            communities: args.flag_communities,
+            mixing: args.flag_communities.map(|_| args.flag_mixing),
+            nested: args.flag_nested,
+            intervals: if args.flag_intervals { Some(true) } else { None },
+            graph: graph,
+            degree: graph.map(|_| args.flag_degree),
+            weights: args.flag_weights.as_ref().map(|d| d.parse::<Distribution>().unwrap()),
+            costs: args.flag_costs.as_ref().map(|d| d.parse::<Distribution>().unwrap()),
+            scenarios: args.flag_scenarios,
+            presence: args.flag_scenarios.map(|_| args.flag_presence),
+        };
+        if let Err(e) = generator.validate() {
+            panic!("{}", e);
+        }
+        let output = args.arg_output.unwrap();
+        let (inst, planted) = generator.generate();
+        maxcover::write(&inst, &output).unwrap();
+        if let Some(sol) = planted {
+            let fname = format!("{}.planted.json", output);
+            serde_json::to_writer_pretty(&mut File::create(fname).unwrap(), &sol).unwrap();
+        }
+    } else if args.cmd_generate_suite {
+        let mut config = String::new();
+        File::open(args.arg_config.unwrap()).unwrap().read_to_string(&mut config).unwrap();
+        let suite = Suite::parse(&config).unwrap();
+        let manifest = suite.generate(args.arg_outdir.unwrap()).unwrap();
+        println!("generated {} instances", manifest.len());
     } else if args.cmd_solve {
-        let inst = read(&args.arg_input.unwrap()).unwrap();
-        do_solve(inst,
-                 args.arg_k.unwrap(),
-                 args.flag_threads,
-                 args.flag_write);
+        solve(args, None);
+    } else if args.cmd_resume {
+        let dir = args.arg_dir.unwrap();
+        let checkpoint = Checkpoint::load(&dir).unwrap();
+        let argv = iter::once("cover".to_string()).chain(checkpoint.args.iter().cloned());
+        let args: Args = Docopt::new(USAGE)
+            .and_then(|d| d.argv(argv).decode())
+            .unwrap_or_else(|e| e.exit());
+        solve(args, Some(checkpoint));
+    } else if args.cmd_export {
+        let inst = maxcover::read(&args.arg_input.unwrap()).unwrap();
+        let k = args.arg_k.unwrap();
+        let mut f = BufWriter::new(File::create(args.arg_output.unwrap()).unwrap());
+        match &args.flag_format[..] {
+            "wcnf" => Encoding::new(&inst, k).write_wcnf(&mut f).unwrap(),
+            "opb" => opb::write_opb(&inst, k, &mut f).unwrap(),
+            "sets" => stream::write_sets(&inst, &mut f).unwrap(),
+            format => panic!("unknown export format: {}", format),
+        }
+    } else if args.cmd_stream {
+        let f = BufReader::new(File::open(args.arg_input.unwrap()).unwrap());
+        let sol = stream::sieve_stream(f, args.arg_k.unwrap(), args.flag_epsilon).unwrap();
+
+        println!("{:?}", sol);
+        if let Some(fname) = args.flag_write {
+            serde_json::to_writer_pretty(&mut File::create(fname).unwrap(), &sol).unwrap();
+        }
+    } else if args.cmd_pareto {
+        pareto(args);
+    } else if args.cmd_sweep {
+        sweep(args);
+    } else if args.cmd_influence {
+        influence(args);
+    } else if args.cmd_from_graph {
+        let f = BufReader::new(File::open(args.arg_graph.unwrap()).unwrap());
+        let graph = reductions::Graph::read(f).unwrap();
+        let inst = graph.instance(args.flag_problem.parse().unwrap());
+        maxcover::write(&inst, &args.arg_output.unwrap()).unwrap();
+    } else if args.cmd_from_csv {
+        let f = BufReader::new(File::open(args.arg_file.unwrap()).unwrap());
+        let header = !args.flag_no_header;
+        let inst = transactions::read(f, &args.flag_set_col, &args.flag_element_col, header)
+            .unwrap();
+        maxcover::write(&inst, &args.arg_output.unwrap()).unwrap();
+    } else if args.cmd_from_text {
+        let stop_words = match args.flag_stop_words {
+            Some(fname) => {
+                corpus::read_stop_words(BufReader::new(File::open(fname).unwrap())).unwrap()
+            }
+            None => BTreeSet::new(),
+        };
+        let inst = corpus::read_dir(args.arg_dir.unwrap(), &stop_words).unwrap();
+        maxcover::write(&inst, &args.arg_output.unwrap()).unwrap();
+    } else if args.cmd_perturb {
+        let inst = maxcover::read(&args.arg_input.unwrap()).unwrap();
+        let perturbation = Perturbation {
+            add_noise: args.flag_add_noise,
+            drop: args.flag_drop,
+            drop_sets: args.flag_drop_sets,
+            add_sets: args.flag_add_sets,
+        };
+        let seed = args.flag_seed.unwrap_or_else(|| {
+            let seed = thread_rng().gen();
+            eprintln!("seed: {}", seed);
+            seed
+        });
+        let mut rng = StdRng::from_seed(&[seed][..]);
+        let perturbed = perturbation.apply(&inst, &mut rng).unwrap();
+        maxcover::write(&perturbed, &args.arg_output.unwrap()).unwrap();
+    } else if args.cmd_backends {
+        let registry = Registry::default();
+        let names = registry.names();
+        for &name in &names {
+            match registry.create(name).unwrap().available() {
+                Ok(()) => println!("{:10} available", name),
+                Err(e) => println!("{:10} unavailable: {}", name, e),
+            }
+        }
+        for name in FEATURE_BACKENDS.iter().filter(|name| !names.contains(name)) {
+            println!("{:10} not built; needs the {} feature", name, name);
+        }
     } else {
         panic!("no command given");
     }
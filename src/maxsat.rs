@@ -0,0 +1,194 @@
+//! Weighted partial MaxSAT encoding of max k-coverage.
+//!
+//! Set `i` is variable `i + 1` and the `j`-th ground element is variable `num_sets + j + 1`.
+//! Hard clauses require an element variable to imply one of its sets and bound the number of
+//! true set variables by `k` with a sequential counter (Sinz, 2005); each element is a unit
+//! soft clause of its weight, rounded to an integer as MaxSAT weights must be. Elements of
+//! weight 0 have none.
+
+use std::io::{self, Write};
+use Instance;
+use coverage::Index;
+
+pub struct Encoding {
+    pub num_vars: usize,
+    pub hard: Vec<Vec<i64>>,
+    pub soft: Vec<(u64, Vec<i64>)>,
+}
+
+impl Encoding {
+    /// Encodes `inst`, which must not have demands.
+    pub fn new(inst: &Instance, k: usize) -> Encoding {
+        assert!(!inst.has_demands(), "the MaxSAT encoding does not support demands");
+        let index = Index::new(inst);
+        let n = index.num_sets();
+        let set_var = |i: usize| (i + 1) as i64;
+        let element_var = |e: usize| (n + e + 1) as i64;
+
+        let mut hard = Vec::new();
+        let mut soft = Vec::new();
+        for e in 0..index.num_elements {
+            let mut clause = vec![-element_var(e)];
+            clause.extend(index.containing[e].iter().map(|&i| set_var(i)));
+            hard.push(clause);
+            let weight = index.weights[e].round() as u64;
+            if weight > 0 {
+                soft.push((weight, vec![element_var(e)]));
+            }
+        }
+
+        let mut num_vars = n + index.num_elements;
+        if k == 0 {
+            hard.extend((0..n).map(|i| vec![-set_var(i)]));
+        } else if k < n {
+            // s(i, j): at least j of the first i + 1 sets are selected, for i < n - 1
+            let base = num_vars as i64;
+            let s = |i: usize, j: usize| base + (i * k + j) as i64 + 1;
+            num_vars += (n - 1) * k;
+
+            hard.push(vec![-set_var(0), s(0, 0)]);
+            for j in 1..k {
+                hard.push(vec![-s(0, j)]);
+            }
+            for i in 1..n - 1 {
+                hard.push(vec![-set_var(i), s(i, 0)]);
+                hard.push(vec![-s(i - 1, 0), s(i, 0)]);
+                for j in 1..k {
+                    hard.push(vec![-set_var(i), -s(i - 1, j - 1), s(i, j)]);
+                    hard.push(vec![-s(i - 1, j), s(i, j)]);
+                }
+                hard.push(vec![-set_var(i), -s(i - 1, k - 1)]);
+            }
+            hard.push(vec![-set_var(n - 1), -s(n - 2, k - 1)]);
+        }
+
+        Encoding {
+            num_vars: num_vars,
+            hard: hard,
+            soft: soft,
+        }
+    }
+
+    /// Writes the encoding in the classic `p wcnf` format, with hard clauses weighted `top`.
+    pub fn write_wcnf<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        let top = self.soft.iter().map(|&(w, _)| w).sum::<u64>() + 1;
+        writeln!(out,
+                 "p wcnf {} {} {}",
+                 self.num_vars,
+                 self.hard.len() + self.soft.len(),
+                 top)?;
+        for clause in &self.hard {
+            write_clause(out, top, clause)?;
+        }
+        for &(w, ref clause) in &self.soft {
+            write_clause(out, w, clause)?;
+        }
+        Ok(())
+    }
+}
+
+fn write_clause<W: Write>(out: &mut W, weight: u64, clause: &[i64]) -> io::Result<()> {
+    write!(out, "{}", weight)?;
+    for lit in clause {
+        write!(out, " {}", lit)?;
+    }
+    writeln!(out, " 0")
+}
+
+/// Result of a MaxSAT solver run in the MaxSAT Evaluation output format.
+#[derive(Debug, Clone)]
+pub struct Model {
+    pub optimal: bool,
+    /// Truth value of each variable, indexed from 0.
+    pub values: Vec<bool>,
+}
+
+/// Parses solver output. Accepts both `v` lines listing signed literals and the newer single
+/// `v` line of 0/1 characters.
+pub fn parse_output(output: &str, num_vars: usize) -> Result<Model, String> {
+    let mut optimal = false;
+    let mut values = vec![false; num_vars];
+    let mut found = false;
+
+    for line in output.lines() {
+        let line = line.trim();
+        if let Some(status) = line.strip_prefix("s ") {
+            let status = status.trim();
+            if status == "UNSATISFIABLE" {
+                return Err("MaxSAT solver reported the hard clauses unsatisfiable".to_string());
+            }
+            optimal = status == "OPTIMUM FOUND";
+        } else if let Some(body) = line.strip_prefix("v ") {
+            found = true;
+            let body = body.trim();
+            if !body.is_empty() && body.chars().all(|c| c == '0' || c == '1') {
+                for (i, c) in body.chars().enumerate().take(num_vars) {
+                    values[i] = c == '1';
+                }
+            } else {
+                for tok in body.split_whitespace() {
+                    let lit = tok.parse::<i64>().map_err(|e| format!("bad literal {}: {}", tok, e))?;
+                    if lit > 0 && (lit as usize) <= num_vars {
+                        values[lit as usize - 1] = true;
+                    }
+                }
+            }
+        }
+    }
+
+    if !found {
+        return Err("MaxSAT solver output contains no model".to_string());
+    }
+    Ok(Model {
+        optimal: optimal,
+        values: values,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solvers::bnb::bnb;
+
+    fn instance(n: usize, sets: Vec<Vec<usize>>) -> Instance {
+        Instance::new((0..n).collect(),
+                      sets.into_iter().map(|s| s.into_iter().collect()).collect())
+    }
+
+    /// The most soft weight of any assignment that satisfies the hard clauses.
+    fn brute_force(enc: &Encoding) -> u64 {
+        let holds = |clause: &[i64], mask: usize| {
+            clause.iter().any(|&lit| (mask >> (lit.abs() - 1) & 1 == 1) == (lit > 0))
+        };
+        (0..1usize << enc.num_vars)
+            .filter(|&mask| enc.hard.iter().all(|clause| holds(clause, mask)))
+            .map(|mask| {
+                enc.soft.iter().filter(|&(_, c)| holds(c, mask)).map(|&(w, _)| w).sum()
+            })
+            .max()
+            .unwrap()
+    }
+
+    #[test]
+    fn optimum_matches_the_coverage_optimum() {
+        let inst = Instance {
+            weights: Some(vec![1.0, 2.0, 1.0, 3.0, 0.0]),
+            ..instance(5, vec![vec![0, 1], vec![1, 2], vec![2, 3], vec![3, 4]])
+        };
+        for k in 0..5 {
+            assert_eq!(brute_force(&Encoding::new(&inst, k)) as f64, bnb(&inst, k).objective);
+        }
+    }
+
+    #[test]
+    fn parses_both_model_formats() {
+        let model = parse_output("c comment\ns OPTIMUM FOUND\nv 1 -2 3\n", 3).unwrap();
+        assert!(model.optimal);
+        assert_eq!(model.values, vec![true, false, true]);
+        let model = parse_output("s SATISFIABLE\nv 011\n", 3).unwrap();
+        assert!(!model.optimal);
+        assert_eq!(model.values, vec![false, true, true]);
+        assert!(parse_output("s UNSATISFIABLE\n", 3).is_err());
+        assert!(parse_output("s OPTIMUM FOUND\n", 3).is_err());
+    }
+}
@@ -0,0 +1,63 @@
+//! Pseudo-Boolean (OPB) encoding of max k-coverage.
+//!
+//! Variables are numbered as in the MaxSAT encoding: set `i` is `x{i + 1}` and the `j`-th
+//! ground element is `x{num_sets + j + 1}`. OPB only minimizes, so the objective is the
+//! negated weight of the covered elements, rounded to integers.
+
+use std::io::{self, Write};
+use Instance;
+use coverage::Index;
+
+pub fn write_opb<W: Write>(inst: &Instance, k: usize, out: &mut W) -> io::Result<()> {
+    let index = Index::new(inst);
+    let n = index.num_sets();
+    let element_var = |e: usize| n + e + 1;
+
+    writeln!(out,
+             "* #variable= {} #constraint= {}",
+             n + index.num_elements,
+             index.num_elements + 1)?;
+
+    write!(out, "min:")?;
+    for e in 0..index.num_elements {
+        write!(out, " -{} x{}", index.weights[e].round() as u64, element_var(e))?;
+    }
+    writeln!(out, " ;")?;
+
+    // an element only counts if as many of its sets as its demand are selected
+    for e in 0..index.num_elements {
+        for &i in &index.containing[e] {
+            write!(out, "+1 x{} ", i + 1)?;
+        }
+        writeln!(out, "-{} x{} >= 0 ;", index.demands[e], element_var(e))?;
+    }
+
+    for i in 0..n {
+        write!(out, "-1 x{} ", i + 1)?;
+    }
+    writeln!(out, ">= -{} ;", k)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_the_model() {
+        let sets = vec![vec![0, 1], vec![1]];
+        let inst = Instance {
+            weights: Some(vec![1.0, 2.4]),
+            demands: Some(vec![1, 2]),
+            ..Instance::new((0..2).collect(),
+                            sets.into_iter().map(|s| s.into_iter().collect()).collect())
+        };
+        let mut out = Vec::new();
+        write_opb(&inst, 1, &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(),
+                   "* #variable= 4 #constraint= 3\n\
+                    min: -1 x3 -2 x4 ;\n\
+                    +1 x1 -1 x3 >= 0 ;\n\
+                    +1 x1 +1 x2 -2 x4 >= 0 ;\n\
+                    -1 x1 -1 x2 >= -1 ;\n");
+    }
+}
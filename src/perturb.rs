@@ -0,0 +1,190 @@
+//! Random perturbations of instances, for robustness and sensitivity experiments.
+
+use std::collections::BTreeSet;
+use rand::{sample, Rng};
+use rand::distributions::{IndependentSample, Range};
+use Instance;
+
+/// How `Perturbation::apply` perturbs an instance. The default leaves it as it is.
+#[derive(Debug, Clone, Default)]
+pub struct Perturbation {
+    /// Probability of adding each ground element to each set that lacks it.
+    pub add_noise: f64,
+    /// Probability of removing each element from each set.
+    pub drop: f64,
+    /// Probability of removing each set, but for the required sets.
+    pub drop_sets: f64,
+    /// Number of random sets to add, of uniformly random sizes as in `generate`.
+    pub add_sets: usize,
+}
+
+/// `inst` without the sets not in `keep`, in increasing order, with the fields of the sets
+/// kept along with them. Sets whose prerequisite is removed become forbidden, as they can no
+/// longer be selected.
+fn keep_sets(inst: &Instance, keep: &[usize]) -> Instance {
+    let mut index = vec![None; inst.sets.len()];
+    for (j, &i) in keep.iter().enumerate() {
+        index[i] = Some(j);
+    }
+    fn select<T: Clone>(values: &Option<Vec<T>>, keep: &[usize]) -> Option<Vec<T>> {
+        values.as_ref().map(|values| keep.iter().map(|&i| values[i].clone()).collect())
+    }
+    let remap = |sets: &[usize]| sets.iter().filter_map(|&i| index[i]).collect::<Vec<_>>();
+
+    let mut forbidden = inst.forbidden.as_ref().map_or(Vec::new(), |f| remap(f));
+    let mut prerequisites = Vec::new();
+    for (a, b) in inst.prerequisites() {
+        match (index[a], index[b]) {
+            (Some(a), Some(b)) => prerequisites.push((a, b)),
+            (Some(a), None) => forbidden.push(a),
+            _ => {}
+        }
+    }
+    forbidden.sort();
+    forbidden.dedup();
+    Instance {
+        sets: keep.iter().map(|&i| inst.sets[i].clone()).collect(),
+        probabilities: select(&inst.probabilities, keep),
+        profits: select(&inst.profits, keep),
+        costs: select(&inst.costs, keep),
+        capacities: select(&inst.capacities, keep),
+        windows: select(&inst.windows, keep),
+        groups: select(&inst.groups, keep),
+        conflicts: inst.conflicts.as_ref().map(|conflicts| {
+            conflicts.iter().map(|c| remap(c)).filter(|c| c.len() > 1).collect()
+        }),
+        prerequisites: inst.prerequisites.as_ref().map(|_| prerequisites),
+        required: inst.required.as_ref().map(|r| remap(r)),
+        forbidden: if inst.forbidden.is_some() || !forbidden.is_empty() {
+            Some(forbidden)
+        } else {
+            None
+        },
+        centers: select(&inst.centers, keep),
+        set_names: select(&inst.set_names, keep),
+        ..inst.clone()
+    }
+}
+
+impl Perturbation {
+    /// A perturbed copy of `inst`. The sets are removed first, then each remaining set gains
+    /// and loses elements, choosing those it gains among the elements it lacked before, and
+    /// finally the new sets are added at the end. With probabilities or profits no elements
+    /// may be added, and new sets may only be added to instances whose sets have no fields but
+    /// costs, for which they cost 1, and names. The perturbed instance has no structure or
+    /// generator, as it no longer has the one or comes from the other.
+    pub fn apply<R: Rng>(&self, inst: &Instance, rng: &mut R) -> Result<Instance, String> {
+        for &(name, p) in &[("noise", self.add_noise), ("drop", self.drop),
+                            ("set drop", self.drop_sets)] {
+            if !(0.0..=1.0).contains(&p) {
+                return Err(format!("{} probability must be in [0, 1], not {}", name, p));
+            }
+        }
+        if (inst.probabilities.is_some() || inst.profits.is_some()) &&
+           (self.add_noise > 0.0 || self.add_sets > 0) {
+            return Err("cannot add elements to the sets of an instance with probabilities or \
+                        profits"
+                .to_string());
+        }
+        if self.add_sets > 0 &&
+           [inst.capacities.is_some(),
+            inst.windows.is_some(),
+            inst.groups.is_some(),
+            inst.centers.is_some()]
+            .iter()
+            .any(|&given| given) {
+            return Err("cannot add sets to an instance with capacities, windows, groups or \
+                        centers"
+                .to_string());
+        }
+
+        let required = inst.required().into_iter().collect::<BTreeSet<_>>();
+        let keep = (0..inst.sets.len())
+            .filter(|i| required.contains(i) || rng.gen::<f64>() >= self.drop_sets)
+            .collect::<Vec<_>>();
+        let mut out = keep_sets(inst, &keep);
+
+        for i in 0..out.sets.len() {
+            let added = out.ground
+                .iter()
+                .filter(|x| !out.sets[i].contains(x) && rng.gen::<f64>() < self.add_noise)
+                .cloned()
+                .collect::<Vec<_>>();
+            let kept = out.sets[i]
+                .iter()
+                .map(|_| rng.gen::<f64>() >= self.drop)
+                .collect::<Vec<_>>();
+            for values in out.probabilities.iter_mut().chain(out.profits.iter_mut()) {
+                let v = values[i].iter().zip(&kept).filter(|&(_, &k)| k);
+                values[i] = v.map(|(&v, _)| v).collect();
+            }
+            let set = out.sets[i].iter().zip(&kept).filter(|&(_, &k)| k).map(|(&x, _)| x);
+            out.sets[i] = set.chain(added).collect();
+        }
+
+        if self.add_sets > 0 {
+            let range = Range::new(1, out.ground.len() + 1);
+            for _ in 0..self.add_sets {
+                let size = range.ind_sample(rng);
+                let set = sample(rng, &out.ground, size).into_iter().cloned().collect();
+                out.sets.push(set);
+                if let Some(ref mut costs) = out.costs {
+                    costs.push(1.0);
+                }
+                let j = out.sets.len() - 1;
+                if let Some(ref mut names) = out.set_names {
+                    names.push(format!("added{}", j));
+                }
+            }
+        }
+
+        out.structure = None;
+        out.generator = None;
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keep_sets_remaps_every_set_field() {
+        let sets = vec![vec![0], vec![1], vec![2], vec![3], vec![0, 1]];
+        let mut inst = Instance::new((0..4).collect(),
+                                     sets.into_iter().map(|s| s.into_iter().collect()).collect());
+        inst.probabilities = Some(vec![vec![0.1], vec![0.2], vec![0.3], vec![0.4], vec![0.5, 0.6]]);
+        inst.profits = Some(vec![vec![1.0], vec![2.0], vec![3.0], vec![4.0], vec![5.0, 6.0]]);
+        inst.costs = Some(vec![1.0, 2.0, 3.0, 4.0, 5.0]);
+        inst.capacities = Some(vec![1, 2, 3, 4, 5]);
+        inst.windows = Some(vec![vec![0], vec![1], vec![0, 1], vec![1], vec![0]]);
+        inst.groups = Some(vec![0, 1, 0, 1, 0]);
+        inst.conflicts = Some(vec![vec![0, 1, 2], vec![1, 3], vec![3, 4]]);
+        inst.prerequisites = Some(vec![(2, 0), (3, 1)]);
+        inst.required = Some(vec![0, 2]);
+        inst.forbidden = Some(vec![4]);
+        inst.centers = Some((0..5).map(|i| (i as f64, i as f64)).collect());
+        inst.set_names = Some(["a", "b", "c", "d", "e"].iter().map(|s| s.to_string()).collect());
+
+        let out = keep_sets(&inst, &[0, 2, 3]);
+        let sets = vec![vec![0], vec![2], vec![3]];
+        assert_eq!(out.sets,
+                   sets.into_iter().map(|s| s.into_iter().collect()).collect::<Vec<_>>());
+        assert_eq!(out.probabilities, Some(vec![vec![0.1], vec![0.3], vec![0.4]]));
+        assert_eq!(out.profits, Some(vec![vec![1.0], vec![3.0], vec![4.0]]));
+        assert_eq!(out.costs, Some(vec![1.0, 3.0, 4.0]));
+        assert_eq!(out.capacities, Some(vec![1, 3, 4]));
+        assert_eq!(out.windows, Some(vec![vec![0], vec![0, 1], vec![1]]));
+        assert_eq!(out.groups, Some(vec![0, 0, 1]));
+        // cliques left with one set no longer conflict
+        assert_eq!(out.conflicts, Some(vec![vec![0, 1]]));
+        assert_eq!(out.prerequisites, Some(vec![(1, 0)]));
+        assert_eq!(out.required, Some(vec![0, 1]));
+        // the third kept set's prerequisite was removed
+        assert_eq!(out.forbidden, Some(vec![2]));
+        assert_eq!(out.centers, Some(vec![(0.0, 0.0), (2.0, 2.0), (3.0, 3.0)]));
+        assert_eq!(out.set_names,
+                   Some(vec!["a".to_string(), "c".to_string(), "d".to_string()]));
+        assert_eq!(out.ground, inst.ground);
+    }
+}
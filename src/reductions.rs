@@ -0,0 +1,204 @@
+//! Covering instances of graph problems.
+//!
+//! Graphs are read as undirected edge lists, with one edge per line given as its two nodes
+//! separated by whitespace; further fields, like the probabilities of `influence`, are
+//! ignored. Nodes are numbered from 0, blank lines and lines starting with `#` are ignored.
+//! Random graphs are also generated, by `Graph::random`.
+
+use std::collections::BTreeSet;
+use std::io::BufRead;
+use std::str::FromStr;
+use rand::Rng;
+use Instance;
+
+/// The graph problem to reduce to a covering instance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Problem {
+    /// Nodes dominating every node: the ground set is the nodes, with the closed
+    /// neighborhood of each node as its set.
+    DominatingSet,
+    /// Nodes touching every edge: the ground set is the edges, with the edges at each node as
+    /// its set.
+    VertexCover,
+}
+
+impl FromStr for Problem {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Problem, String> {
+        match s {
+            "dominating-set" => Ok(Problem::DominatingSet),
+            "vertex-cover" => Ok(Problem::VertexCover),
+            _ => Err(format!("unknown graph problem: {}", s)),
+        }
+    }
+}
+
+/// The random graph model of `Graph::random`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphModel {
+    /// Erdos-Renyi: each pair of nodes is an edge independently.
+    #[serde(rename = "er")]
+    ErdosRenyi,
+    /// Barabasi-Albert: the nodes arrive one at a time, each with edges to earlier nodes
+    /// chosen in proportion to their degrees, giving a power-law degree distribution.
+    #[serde(rename = "ba")]
+    BarabasiAlbert,
+}
+
+impl FromStr for GraphModel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<GraphModel, String> {
+        match s {
+            "er" => Ok(GraphModel::ErdosRenyi),
+            "ba" => Ok(GraphModel::BarabasiAlbert),
+            _ => Err(format!("unknown graph model: {}", s)),
+        }
+    }
+}
+
+/// An undirected graph on the nodes `0..num_nodes`.
+#[derive(Debug, Clone)]
+pub struct Graph {
+    pub num_nodes: usize,
+    /// The distinct edges, each with its smaller node first, in increasing order.
+    pub edges: Vec<(usize, usize)>,
+}
+
+impl Graph {
+    /// Reads a graph in the edge list format.
+    pub fn read<R: BufRead>(reader: R) -> Result<Graph, String> {
+        let mut edges = BTreeSet::new();
+        for line in reader.lines() {
+            let line = line.map_err(|e| e.to_string())?;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let nodes = line.split_whitespace()
+                .take(2)
+                .map(|tok| tok.parse::<usize>().map_err(|e| format!("bad node {}: {}", tok, e)))
+                .collect::<Result<Vec<_>, _>>()?;
+            if nodes.len() != 2 {
+                return Err(format!("bad edge: {}", line));
+            }
+            edges.insert((nodes[0].min(nodes[1]), nodes[0].max(nodes[1])));
+        }
+        let num_nodes = edges.iter().map(|&(_, v)| v + 1).max().unwrap_or(0);
+        Ok(Graph {
+            num_nodes: num_nodes,
+            edges: edges.into_iter().collect(),
+        })
+    }
+
+    /// A random graph of `model` on `num_nodes` nodes with an average degree of about
+    /// `degree`. Erdos-Renyi graphs have each edge with probability `degree / (num_nodes -
+    /// 1)`. Barabasi-Albert graphs start from a clique of `m + 1` nodes, for `m` the half of
+    /// `degree` rounded and at least 1, and every later node has edges to `m` distinct earlier
+    /// nodes, each drawn with probability proportional to its degree.
+    pub fn random<R: Rng>(model: GraphModel, num_nodes: usize, degree: f64, rng: &mut R) -> Graph {
+        assert!(degree >= 0.0, "degree must be at least 0, not {}", degree);
+        let mut edges = BTreeSet::new();
+        match model {
+            GraphModel::ErdosRenyi => {
+                let p = degree / (num_nodes.max(2) - 1) as f64;
+                for v in 0..num_nodes {
+                    for u in 0..v {
+                        if rng.gen::<f64>() < p {
+                            edges.insert((u, v));
+                        }
+                    }
+                }
+            }
+            GraphModel::BarabasiAlbert => {
+                let m = ((degree / 2.0).round() as usize).max(1);
+                // each node once per edge at it, to draw nodes by degree
+                let mut ends = Vec::new();
+                for v in 0..num_nodes {
+                    let targets = if v <= m {
+                        (0..v).collect()
+                    } else {
+                        let mut targets = BTreeSet::new();
+                        while targets.len() < m {
+                            targets.insert(ends[rng.gen_range(0, ends.len())]);
+                        }
+                        targets
+                    };
+                    for u in targets {
+                        edges.insert((u, v));
+                        ends.push(u);
+                        ends.push(v);
+                    }
+                }
+            }
+        }
+        Graph {
+            num_nodes: num_nodes,
+            edges: edges.into_iter().collect(),
+        }
+    }
+
+    /// The covering instance of `problem` on the graph. Its sets are the nodes, so that
+    /// selections are sets of nodes: solved as set cover it gives the least dominating set or
+    /// vertex cover, and as max coverage the `k` nodes that dominate the most nodes or touch
+    /// the most edges. Vertex cover numbers the edges as in `edges`.
+    pub fn instance(&self, problem: Problem) -> Instance {
+        let mut sets = vec![BTreeSet::new(); self.num_nodes];
+        let ground = match problem {
+            Problem::DominatingSet => {
+                for v in 0..self.num_nodes {
+                    sets[v].insert(v);
+                }
+                for &(u, v) in &self.edges {
+                    sets[u].insert(v);
+                    sets[v].insert(u);
+                }
+                (0..self.num_nodes).collect()
+            }
+            Problem::VertexCover => {
+                for (e, &(u, v)) in self.edges.iter().enumerate() {
+                    sets[u].insert(e);
+                    sets[v].insert(e);
+                }
+                (0..self.edges.len()).collect()
+            }
+        };
+
+        Instance::new(ground, sets)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path() -> Graph {
+        Graph::read("# a path\n0 1\n2 1 0.5\n\n1 2\n2 3\n".as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn reads_edge_lists() {
+        let graph = path();
+        assert_eq!(graph.num_nodes, 4);
+        assert_eq!(graph.edges, vec![(0, 1), (1, 2), (2, 3)]);
+        assert!(Graph::read("0\n".as_bytes()).is_err());
+        assert!(Graph::read("0 x\n".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn dominating_sets_are_closed_neighborhoods() {
+        let inst = path().instance(Problem::DominatingSet);
+        assert_eq!(inst.ground, (0..4).collect());
+        assert_eq!(inst.sets[0], vec![0, 1].into_iter().collect());
+        assert_eq!(inst.sets[2], vec![1, 2, 3].into_iter().collect());
+    }
+
+    #[test]
+    fn vertex_covers_cover_edges() {
+        let inst = path().instance(Problem::VertexCover);
+        assert_eq!(inst.ground, (0..3).collect());
+        assert_eq!(inst.sets[1], vec![0, 1].into_iter().collect());
+        assert_eq!(inst.sets[3], vec![2].into_iter().collect());
+    }
+}
@@ -0,0 +1,102 @@
+use rand::Rng;
+use {Instance, Solution};
+use interrupt::interrupted;
+use coverage::{Index, Coverage};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Params {
+    pub iterations: usize,
+    pub ants: usize,
+    /// Fraction of pheromone that evaporates after each iteration.
+    pub evaporation: f64,
+    /// Weight of the pheromone trail in the transition rule.
+    pub alpha: f64,
+    /// Weight of the marginal gain in the transition rule.
+    pub beta: f64,
+}
+
+impl Default for Params {
+    fn default() -> Params {
+        Params {
+            iterations: 100,
+            ants: 10,
+            evaporation: 0.1,
+            alpha: 1.0,
+            beta: 2.0,
+        }
+    }
+}
+
+/// Lets one ant build a selection: each step picks an unselected set with probability
+/// proportional to `tau^alpha * gain^beta`.
+fn construct<'a, R: Rng>(index: &'a Index,
+                         k: usize,
+                         tau: &[f64],
+                         params: &Params,
+                         rng: &mut R)
+                         -> Coverage<'a> {
+    let mut cov = Coverage::new(index);
+    for _ in 0..k {
+        let weights = (0..index.num_sets())
+            .map(|s| {
+                let gain = if cov.contains(s) { 0.0 } else { cov.gain(s) };
+                if gain > 0.0 { tau[s].powf(params.alpha) * gain.powf(params.beta) } else { 0.0 }
+            })
+            .collect::<Vec<_>>();
+        let total = weights.iter().sum::<f64>();
+        if total <= 0.0 {
+            break;
+        }
+
+        let mut target = rng.gen::<f64>() * total;
+        let mut pick = None;
+        for (s, &w) in weights.iter().enumerate() {
+            if w > 0.0 {
+                pick = Some(s);
+                if target < w {
+                    break;
+                }
+                target -= w;
+            }
+        }
+        cov.add(pick.unwrap());
+    }
+    cov
+}
+
+/// Ant colony optimization. Pheromone trails live on sets and start at 1; after every
+/// iteration they evaporate and the iteration's best ant deposits its coverage fraction on
+/// the sets it chose.
+pub fn aco<R: Rng>(inst: &Instance, k: usize, params: &Params, rng: &mut R) -> Solution {
+    let index = Index::new(inst);
+    let mut tau = vec![1.0; index.num_sets()];
+    let mut best = (0.0, Vec::new());
+    let scale = index.num_elements.max(1) as f64;
+
+    for _ in 0..params.iterations {
+        if interrupted() {
+            break;
+        }
+        let mut iteration_best: Option<(f64, Vec<usize>)> = None;
+        for _ in 0..params.ants.max(1) {
+            let cov = construct(&index, k, &tau, params, rng);
+            if iteration_best.as_ref().is_none_or(|&(v, _)| cov.value() > v) {
+                iteration_best = Some((cov.value(), cov.selection()));
+            }
+        }
+        let (value, sets) = iteration_best.unwrap();
+
+        for t in tau.iter_mut() {
+            *t *= 1.0 - params.evaporation;
+        }
+        for &s in &sets {
+            tau[s] += value / scale;
+        }
+
+        if value > best.0 {
+            best = (value, sets);
+        }
+    }
+
+    Solution::new(best.0, best.1)
+}
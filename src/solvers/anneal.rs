@@ -0,0 +1,86 @@
+use rand::Rng;
+use {Instance, Solution};
+use interrupt::interrupted;
+use coverage::{Index, Coverage};
+use solvers::greedy::lazy_greedy;
+use solvers::local::fill_random;
+
+/// Temperature schedule for simulated annealing.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Schedule {
+    /// `T_i = t0 * alpha^i`
+    Geometric { t0: f64, alpha: f64 },
+    /// `T_i` falls linearly from `t0` to 0 over the iteration budget.
+    Linear { t0: f64 },
+}
+
+impl Schedule {
+    /// Parses `geometric` or `linear` with the given starting temperature and cooling factor.
+    pub fn new(name: &str, t0: f64, alpha: f64) -> Result<Schedule, String> {
+        match name {
+            "geometric" => Ok(Schedule::Geometric { t0: t0, alpha: alpha }),
+            "linear" => Ok(Schedule::Linear { t0: t0 }),
+            _ => Err(format!("unknown schedule: {}", name)),
+        }
+    }
+
+    pub fn temperature(&self, iteration: usize, iterations: usize) -> f64 {
+        match *self {
+            Schedule::Geometric { t0, alpha } => t0 * alpha.powf(iteration as f64),
+            Schedule::Linear { t0 } => t0 * (1.0 - iteration as f64 / iterations as f64),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Params {
+    pub iterations: usize,
+    pub schedule: Schedule,
+}
+
+/// Simulated annealing over swap moves, starting from the greedy solution. A move swaps a
+/// random selected set for a random unselected one, if the rest of the selection allows it,
+/// and is accepted with probability `exp(delta / T)` when it decreases coverage.
+pub fn anneal<R: Rng>(inst: &Instance, k: usize, params: &Params, rng: &mut R) -> Solution {
+    let index = Index::new(inst);
+    let start = lazy_greedy(inst, k);
+    let mut cov = Coverage::from_sets(&index, &start.sol);
+    fill_random(&mut cov, k, rng);
+
+    let mut selected = cov.selection();
+    let mut unselected = (0..index.num_sets()).filter(|&s| !cov.contains(s)).collect::<Vec<_>>();
+    let mut best = (cov.value(), selected.clone());
+
+    if selected.is_empty() || unselected.is_empty() {
+        return Solution::new(best.0, best.1);
+    }
+
+    for i in 0..params.iterations {
+        if interrupted() {
+            break;
+        }
+        let temp = params.schedule.temperature(i, params.iterations);
+        let si = rng.gen_range(0, selected.len());
+        let ti = rng.gen_range(0, unselected.len());
+        let (s, t) = (selected[si], unselected[ti]);
+
+        let loss = cov.loss(s);
+        cov.remove(s);
+        let delta = cov.gain(t) - loss;
+
+        let accept = delta >= 0.0 || (temp > 0.0 && rng.gen::<f64>() < (delta / temp).exp());
+        if cov.removable(s) && cov.allows(t) && accept {
+            cov.add(t);
+            selected[si] = t;
+            unselected[ti] = s;
+            if cov.value() > best.0 {
+                best = (cov.value(), selected.clone());
+            }
+        } else {
+            cov.add(s);
+        }
+    }
+
+    best.1.sort();
+    Solution::new(best.0, best.1)
+}
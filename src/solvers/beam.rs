@@ -0,0 +1,72 @@
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+use {Instance, Solution};
+use coverage::{Index, Coverage};
+
+/// Beam search: extends partial solutions one set at a time, keeping the `width` distinct
+/// partial selections with the largest coverage at each level. Each state only proposes its
+/// `width` best extensions, so a level costs `width` full gain scans. A width of 1 is greedy.
+pub fn beam(inst: &Instance, k: usize, width: usize) -> Solution {
+    let index = Index::new(inst);
+    let width = width.max(1);
+    let mut states: Vec<(f64, Vec<usize>)> = vec![(0.0, Vec::new())];
+
+    for _ in 0..k {
+        let mut candidates = Vec::new();
+        for &(value, ref sel) in &states {
+            let cov = Coverage::from_sets(&index, sel);
+            let mut gains = (0..index.num_sets())
+                .filter(|&s| !cov.contains(s))
+                .map(|s| (s, cov.gain(s)))
+                .filter(|&(_, g)| g > 0.0)
+                .collect::<Vec<_>>();
+            gains.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(Ordering::Equal));
+
+            for (s, gain) in gains.into_iter().take(width) {
+                let mut next = sel.clone();
+                next.push(s);
+                next.sort();
+                candidates.push((value + gain, next));
+            }
+        }
+
+        if candidates.is_empty() {
+            break;
+        }
+
+        candidates.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(Ordering::Equal));
+        let mut seen = BTreeSet::new();
+        states = candidates.into_iter()
+            .filter(|(_, sel)| seen.insert(sel.clone()))
+            .take(width)
+            .collect();
+    }
+
+    let (objective, sol) = states.into_iter().next().unwrap();
+    Solution::new(objective, sol)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solvers::greedy::greedy;
+
+    fn instance(n: usize, sets: Vec<Vec<usize>>) -> Instance {
+        Instance::new((0..n).collect(),
+                      sets.into_iter().map(|s| s.into_iter().collect()).collect())
+    }
+
+    #[test]
+    fn width_one_is_greedy() {
+        let inst = instance(8, vec![vec![0, 1, 2, 3], vec![4, 5, 6, 7], vec![2, 3, 4, 5, 6]]);
+        assert_eq!(beam(&inst, 2, 1).objective, greedy(&inst, 2).objective);
+    }
+
+    #[test]
+    fn wider_beams_keep_the_runner_up() {
+        let inst = instance(8, vec![vec![0, 1, 2, 3], vec![4, 5, 6, 7], vec![2, 3, 4, 5, 6]]);
+        let sol = beam(&inst, 2, 2);
+        assert_eq!(sol.sol, vec![0, 1]);
+        assert_eq!(sol.objective, 8.0);
+    }
+}
@@ -0,0 +1,126 @@
+use std::collections::HashMap;
+use {Instance, Solution};
+use backend::{self, Model, Sense, SolverBackend, VarType};
+use coverage::{Index, Coverage};
+use solvers::greedy::{lazy_greedy, precedence_greedy};
+use solvers::master;
+
+/// Adds the cut of `selected` to the master, which bounds the coverage variable `theta` by
+/// a function of the set variables that is tight at `selected`. An element covered by two
+/// or more selected sets counts its weight, and any other element its weight times the
+/// number of selected sets containing it, which is at least its coverage by any selection.
+fn add_cut(model: &mut Model,
+           inst: &Instance,
+           weights: &HashMap<usize, f64>,
+           theta: usize,
+           set_vars: &[usize],
+           selected: &[usize]) {
+    let mut count = HashMap::new();
+    for &i in selected {
+        for &x in &inst.sets[i] {
+            *count.entry(x).or_insert(0) += 1;
+        }
+    }
+    let multiple = count.iter()
+        .filter(|&(_, &c)| c > 1)
+        .filter_map(|(x, _)| weights.get(x))
+        .sum::<f64>();
+
+    let mut terms = vec![(theta, 1.0)];
+    for (i, set) in inst.sets.iter().enumerate() {
+        let c = set.iter()
+            .filter(|x| count.get(x).is_none_or(|&c| c <= 1))
+            .filter_map(|x| weights.get(x))
+            .sum::<f64>();
+        if c > 0.0 {
+            terms.push((set_vars[i], -c));
+        }
+    }
+    let name = format!("cut{}", model.rows.len());
+    model.add_row(name, terms, Sense::LessEq, multiple);
+}
+
+/// Benders decomposition: a master problem over the set variables alone, plus one variable
+/// `theta` for the coverage, which cuts bound from above. Each master solution is evaluated
+/// exactly and its cut added, until the best selection found reaches the master's bound.
+/// The master has `m + 1` variables however many elements there are, with `theta` first.
+///
+/// The master is warm started from `start`, or from lazy greedy, or precedence greedy if
+/// some set needs another, and from the best
+/// selection after that. Every master solve has the full time limit; the decomposition
+/// stops when one does not finish, at `params.gap`, or on an interrupt, with the best
+/// selection and the last bound.
+pub fn benders<B>(inst: &Instance,
+                  k: usize,
+                  backend: &mut B,
+                  params: &backend::Params,
+                  start: Option<&[usize]>)
+                  -> Result<Solution, String>
+    where B: SolverBackend + ?Sized
+{
+    // the cuts count an element as soon as one selected set contains it
+    if inst.has_demands() {
+        return Err("benders does not support demands".to_string());
+    }
+    let index = Index::new(inst);
+    let greedy = if inst.has_prerequisites() {
+        precedence_greedy(inst, k)
+    } else {
+        lazy_greedy(inst, k)
+    };
+    let start = start.unwrap_or(&greedy.sol);
+    if start.len() > k {
+        return Err(format!("warm start selects {} sets, more than k = {}", start.len(), k));
+    }
+    inst.check_selection(start).map_err(|e| format!("warm start: {}", e))?;
+
+    let weights = inst.ground.iter().cloned().zip(inst.weights()).collect::<HashMap<_, _>>();
+    let total = weights.values().sum::<f64>();
+    let mut model = Model::new("benders", true);
+    let theta = model.add_var("theta".to_string(), VarType::Continuous, 1.0, 0.0, total);
+    let set_vars = (0..inst.sets.len())
+        .map(|i| model.add_var(format!("s{}", i), VarType::Binary, 0.0, 0.0, 1.0))
+        .collect::<Vec<_>>();
+    model.selection = Some(1..model.vars.len());
+    let cardinality = set_vars.iter().map(|&j| (j, 1.0)).collect();
+    model.add_row("cardinality".to_string(), cardinality, Sense::LessEq, k as f64);
+    let first = model.vars.len();
+    backend::add_selection_rows(&mut model, inst, &set_vars);
+    let selection_vars = first..model.vars.len();
+
+    let mut best = Solution::new(Coverage::from_sets(&index, start).value(), start.to_vec());
+    add_cut(&mut model, inst, &weights, theta, &set_vars, &best.sol);
+
+    params.apply(backend)?;
+    let mut bound = total;
+    loop {
+        let mut values = master::start(inst,
+                                       model.vars.len(),
+                                       &set_vars,
+                                       selection_vars.clone(),
+                                       &best.sol);
+        values[theta] = best.objective;
+        model.start = Some(values);
+
+        backend.build_model(model.clone())?;
+        backend.solve()?;
+        let sol = backend.extract_solution()?;
+        let selected = (0..set_vars.len())
+            .filter(|&i| sol.values[set_vars[i]] > 0.5)
+            .collect::<Vec<_>>();
+        let objective = Coverage::from_sets(&index, &selected).value();
+        if objective > best.objective {
+            best = Solution::new(objective, selected.clone());
+        }
+        if let Some(b) = master::bound(&sol, params) {
+            bound = bound.min(b);
+        }
+        if master::should_stop(best.objective, bound, &sol, params) {
+            break;
+        }
+        add_cut(&mut model, inst, &weights, theta, &set_vars, &selected);
+    }
+
+    master::finish(&mut best, bound);
+    Ok(best)
+}
@@ -0,0 +1,155 @@
+use std::cmp::Ordering;
+use {Instance, Solution};
+use coverage::{Index, Coverage};
+use solvers::greedy::lazy_greedy;
+
+/// Minimum bound improvement over the incumbent for a node to be explored.
+const EPS: f64 = 1e-9;
+
+struct Search<'a> {
+    cov: Coverage<'a>,
+    forbidden: Vec<bool>,
+    k: usize,
+    best: (f64, Vec<usize>),
+}
+
+impl<'a> Search<'a> {
+    fn allowed(&self, s: usize) -> bool {
+        !self.forbidden[s] && !self.cov.contains(s)
+    }
+
+    /// Current value plus the `picks` largest marginal gains. By submodularity no completion
+    /// of the current selection can do better. With demands the gains are potentials, which
+    /// bound them in any completion.
+    fn bound(&self, picks: usize) -> f64 {
+        let mut gains = (0..self.cov.index().num_sets())
+            .filter(|&s| self.allowed(s))
+            .map(|s| self.cov.potential(s))
+            .filter(|&g| g > 0.0)
+            .collect::<Vec<_>>();
+        gains.sort_by(|a, b| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+        self.cov.value() + gains.into_iter().take(picks).sum::<f64>()
+    }
+
+    /// The uncovered element with the fewest allowed sets containing it, and those sets.
+    fn branching_element(&self) -> Option<Vec<usize>> {
+        let index = self.cov.index();
+        (0..index.num_elements)
+            .filter(|&e| !self.cov.covered(e))
+            .map(|e| index.containing[e].iter().cloned().filter(|&s| self.allowed(s)).collect::<Vec<_>>())
+            .filter(|cands| !cands.is_empty())
+            .min_by_key(|cands| cands.len())
+    }
+
+    /// Branches on an uncovered element `e`: either the j-th allowed set containing `e` is
+    /// selected and the ones before it are forbidden, or all of them are forbidden and `e`
+    /// stays uncovered. The branches partition the remaining search space.
+    fn branch(&mut self) {
+        if self.cov.value() > self.best.0 + EPS {
+            self.best = (self.cov.value(), self.cov.selection());
+        }
+
+        let picks = self.k - self.cov.size();
+        if picks == 0 || self.bound(picks) <= self.best.0 + EPS {
+            return;
+        }
+
+        let mut cands = match self.branching_element() {
+            Some(cands) => cands,
+            None => return,
+        };
+        {
+            let cov = &self.cov;
+            cands.sort_by(|&a, &b| cov.gain(b).partial_cmp(&cov.gain(a)).unwrap_or(Ordering::Equal));
+        }
+
+        for &c in &cands {
+            self.cov.add(c);
+            self.branch();
+            self.cov.remove(c);
+            self.forbidden[c] = true;
+        }
+        self.branch();
+        for &c in &cands {
+            self.forbidden[c] = false;
+        }
+    }
+}
+
+/// Exact branch and bound that needs no external solver. Starts from the lazy greedy
+/// incumbent, branches on elements and prunes with the submodular greedy bound. The search
+/// recurses once per branching element, so it is meant for small and medium instances.
+pub fn bnb(inst: &Instance, k: usize) -> Solution {
+    let index = Index::new(inst);
+    let greedy = lazy_greedy(inst, k);
+    let mut search = Search {
+        cov: Coverage::new(&index),
+        forbidden: vec![false; index.num_sets()],
+        k: k.min(index.num_sets()),
+        best: (greedy.objective, greedy.sol),
+    };
+    search.branch();
+
+    let (objective, mut sol) = search.best;
+    sol.sort();
+    let mut sol = Solution::new(objective, sol);
+    sol.bound = Some(objective);
+    sol
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn instance(n: usize, sets: Vec<Vec<usize>>) -> Instance {
+        Instance::new((0..n).collect(),
+                      sets.into_iter().map(|s| s.into_iter().collect()).collect())
+    }
+
+    /// The best coverage of at most `k` sets, by trying every selection.
+    fn brute_force(inst: &Instance, k: usize) -> f64 {
+        let index = Index::new(inst);
+        (0..1usize << inst.sets.len())
+            .filter(|mask| mask.count_ones() as usize <= k)
+            .map(|mask| {
+                let sets = (0..inst.sets.len()).filter(|&i| mask & 1 << i != 0).collect::<Vec<_>>();
+                Coverage::from_sets(&index, &sets).value()
+            })
+            .fold(0.0, f64::max)
+    }
+
+    #[test]
+    fn beats_greedy() {
+        let inst = instance(8, vec![vec![0, 1, 2, 3], vec![4, 5, 6, 7], vec![2, 3, 4, 5, 6]]);
+        let sol = bnb(&inst, 2);
+        assert_eq!(sol.sol, vec![0, 1]);
+        assert_eq!(sol.objective, 8.0);
+        assert_eq!(sol.bound, Some(8.0));
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        // sets drawn by a fixed linear congruential generator
+        let mut state = 12345u64;
+        let mut next = |n: u64| {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (state >> 33) % n
+        };
+        for _ in 0..20 {
+            let sets = (0..10)
+                .map(|_| (0..12).filter(|_| next(4) == 0).map(|x| x as usize).collect())
+                .collect();
+            let inst = Instance {
+                weights: Some((0..12).map(|_| 1.0 + next(5) as f64).collect()),
+                ..instance(12, sets)
+            };
+            for k in 1..4 {
+                let sol = bnb(&inst, k);
+                assert!(sol.sol.len() <= k);
+                assert_eq!(sol.objective, brute_force(&inst, k));
+                assert_eq!(sol.objective,
+                           Coverage::from_sets(&Index::new(&inst), &sol.sol).value());
+            }
+        }
+    }
+}
@@ -0,0 +1,198 @@
+use std::cmp::Ordering;
+use {Instance, Solution};
+use backend::{self, Model, Sense, SolverBackend, VarType};
+use coverage::{Index, Coverage};
+
+/// Tries to assign element `e` to one of the `selected` sets containing it, moving the
+/// elements already assigned to a full set along to other sets where they fit, as in
+/// Kuhn's augmenting path algorithm. Sets on the path are marked in `visited`.
+fn augment(index: &Index,
+           capacities: &[usize],
+           selected: &[bool],
+           assigned: &mut Vec<Vec<usize>>,
+           visited: &mut Vec<bool>,
+           e: usize)
+           -> bool {
+    for &s in &index.containing[e] {
+        if !selected[s] || visited[s] {
+            continue;
+        }
+        visited[s] = true;
+        if assigned[s].len() < capacities[s] {
+            assigned[s].push(e);
+            return true;
+        }
+        for t in 0..assigned[s].len() {
+            let f = assigned[s][t];
+            if augment(index, capacities, selected, assigned, visited, f) {
+                assigned[s][t] = e;
+                return true;
+            }
+        }
+    }
+    false
+}
+
+/// The most weight `sets` can serve within their capacities, along with the elements each of
+/// them serves, in the order of `sets`. The elements assignable together form a transversal
+/// matroid, so assigning them heaviest first, each whenever an augmenting path exists, is
+/// optimal.
+pub fn served(inst: &Instance, sets: &[usize]) -> (f64, Vec<Vec<usize>>) {
+    let index = Index::new(inst);
+    let capacities = inst.capacities();
+    let mut selected = vec![false; index.num_sets()];
+    for &i in sets {
+        selected[i] = true;
+    }
+    let mut elements = (0..index.num_elements)
+        .filter(|&e| index.weights[e] > 0.0)
+        .collect::<Vec<_>>();
+    elements.sort_by(|&a, &b| {
+        index.weights[b].partial_cmp(&index.weights[a]).unwrap_or(Ordering::Equal)
+    });
+
+    let mut assigned = vec![Vec::new(); index.num_sets()];
+    let mut value = 0.0;
+    for e in elements {
+        let mut visited = vec![false; index.num_sets()];
+        if augment(&index, &capacities, &selected, &mut assigned, &mut visited, e) {
+            value += index.weights[e];
+        }
+    }
+
+    let ground = inst.ground.iter().cloned().collect::<Vec<_>>();
+    let assignment = sets.iter()
+        .map(|&i| {
+            let mut served = assigned[i].iter().map(|&e| ground[e]).collect::<Vec<_>>();
+            served.sort();
+            served
+        })
+        .collect();
+    (value, assignment)
+}
+
+/// Capacitated greedy: repeatedly selects the set that most raises the weight `served`, until
+/// `k` are selected or no set raises it. Starts from the required sets and only adds the sets
+/// that the selection allows, like `greedy`. The solution's objective is the weight served,
+/// with the assignment in `assignment`.
+pub fn capacitated_greedy(inst: &Instance, k: usize) -> Solution {
+    assert!(!inst.has_demands(), "capacitated coverage does not support demands");
+    let index = Index::new(inst);
+    // tracks the selection for its constraints
+    let mut cov = Coverage::initial(&index);
+    let mut sol = cov.selection();
+    let mut value = served(inst, &sol).0;
+
+    while sol.len() < k {
+        let mut best = None;
+        for s in 0..index.num_sets() {
+            if cov.contains(s) || !cov.allows(s) {
+                continue;
+            }
+            sol.push(s);
+            let gain = served(inst, &sol).0 - value;
+            sol.pop();
+            if gain > 0.0 && best.is_none_or(|(_, g)| gain > g) {
+                best = Some((s, gain));
+            }
+        }
+
+        match best {
+            Some((s, gain)) => {
+                cov.add(s);
+                sol.push(s);
+                value += gain;
+            }
+            None => break,
+        }
+    }
+
+    let (value, assignment) = served(inst, &sol);
+    let mut out = Solution::new(value, sol);
+    out.assignment = Some(assignment);
+    out
+}
+
+/// Capacitated max coverage with `backend`: selects at most `k` sets and assigns each element
+/// to at most one selected set containing it, with no set serving more elements than its
+/// capacity, maximizing the weight assigned. The model has the set variables first, then an
+/// assignment variable per set and element of it. The solution's objective is the weight
+/// `served` by the selected sets, with the assignment in `assignment`.
+///
+/// The solver is warm started from `capacitated_greedy`, and the model is also written to
+/// `problem.lp`. The bound and gap are as for `solve`.
+pub fn capacitated<B>(inst: &Instance,
+                      k: usize,
+                      backend: &mut B,
+                      params: &backend::Params)
+                      -> Result<Solution, String>
+    where B: SolverBackend + ?Sized
+{
+    if inst.has_demands() {
+        return Err("capacitated coverage does not support demands".to_string());
+    }
+    let index = Index::new(inst);
+    let capacities = inst.capacities();
+    let mut model = Model::new("capacitated", true);
+    let set_vars = (0..index.num_sets())
+        .map(|i| model.add_var(format!("s{}", i), VarType::Binary, 0.0, 0.0, 1.0))
+        .collect::<Vec<_>>();
+    model.selection = Some(0..set_vars.len());
+    let cardinality = set_vars.iter().map(|&j| (j, 1.0)).collect();
+    model.add_row("cardinality".to_string(), cardinality, Sense::LessEq, k as f64);
+    backend::add_selection_rows(&mut model, inst, &set_vars);
+    let selection_vars = set_vars.len()..model.vars.len();
+
+    // the assignment variables of each set, in the order of its elements, and of each element
+    let ground = inst.ground.iter().cloned().collect::<Vec<_>>();
+    let mut assign_vars = Vec::with_capacity(index.num_sets());
+    let mut serving = vec![Vec::new(); index.num_elements];
+    for (i, set) in index.sets.iter().enumerate() {
+        let mut vars = Vec::with_capacity(set.len());
+        for &e in set {
+            let name = format!("a{}_{}", i, ground[e]);
+            let a = model.add_var(name, VarType::Binary, index.weights[e], 0.0, 1.0);
+            vars.push(a);
+            serving[e].push((a, 1.0));
+        }
+        let mut terms = vars.iter().map(|&a| (a, 1.0)).collect::<Vec<_>>();
+        terms.push((set_vars[i], -(capacities[i] as f64)));
+        model.add_row(format!("capacity{}", i), terms, Sense::LessEq, 0.0);
+        assign_vars.push(vars);
+    }
+    for (e, terms) in serving.into_iter().enumerate().filter(|(_, t)| !t.is_empty()) {
+        model.add_row(format!("serve{}", ground[e]), terms, Sense::LessEq, 1.0);
+    }
+
+    let greedy = capacitated_greedy(inst, k);
+    let mut values = vec![0.0; model.vars.len()];
+    for &i in &greedy.sol {
+        values[set_vars[i]] = 1.0;
+    }
+    for (j, value) in selection_vars.zip(backend::selection_values(inst, &greedy.sol)) {
+        values[j] = value;
+    }
+    for (&i, served) in greedy.sol.iter().zip(greedy.assignment.as_ref().unwrap()) {
+        for (t, &e) in index.sets[i].iter().enumerate() {
+            if served.contains(&ground[e]) {
+                values[assign_vars[i][t]] = 1.0;
+            }
+        }
+    }
+    model.start = Some(values);
+    ::write_problem_lp(&model)?;
+
+    params.apply(backend)?;
+    backend.build_model(model)?;
+    backend.solve()?;
+    let sol = backend.extract_solution()?;
+    let selected = (0..set_vars.len())
+        .filter(|&i| sol.values[set_vars[i]] > 0.5)
+        .collect::<Vec<_>>();
+    let (objective, assignment) = served(inst, &selected);
+
+    let mut out = Solution::new(objective, selected);
+    out.assignment = Some(assignment);
+    ::fill_bound(&mut out, &sol, params);
+    Ok(out)
+}
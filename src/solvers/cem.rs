@@ -0,0 +1,75 @@
+use std::cmp::Ordering;
+use rand::Rng;
+use {Instance, Solution};
+use interrupt::interrupted;
+use coverage::{Index, Coverage};
+
+/// Probabilities are kept at least this large so every set can still be sampled.
+const MIN_PROB: f64 = 1e-9;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Params {
+    pub iterations: usize,
+    /// k-subsets sampled per iteration.
+    pub samples: usize,
+    /// Fraction of the samples that forms the elite.
+    pub elite: f64,
+    /// Weight of the elite frequencies in the probability update.
+    pub smoothing: f64,
+}
+
+/// Samples `k` distinct sets with probability proportional to `p` (Efraimidis and Spirakis,
+/// 2006): each set gets the key `u^(1 / p_i)` and the `k` largest keys win.
+fn sample<R: Rng>(p: &[f64], k: usize, rng: &mut R) -> Vec<usize> {
+    let mut keys = p.iter()
+        .enumerate()
+        .map(|(i, &pi)| (rng.gen::<f64>().powf(1.0 / pi.max(MIN_PROB)), i))
+        .collect::<Vec<_>>();
+    keys.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(Ordering::Equal));
+    keys.into_iter().take(k).map(|(_, i)| i).collect()
+}
+
+/// Cross-entropy method. Keeps a selection probability per set, initially `k / n`; each
+/// iteration samples k-subsets from it and moves it towards the selection frequencies of the
+/// elite samples.
+pub fn cem<R: Rng>(inst: &Instance, k: usize, params: &Params, rng: &mut R) -> Solution {
+    let index = Index::new(inst);
+    let n = index.num_sets();
+    let k = k.min(n);
+    let mut p = vec![if n == 0 { 0.0 } else { k as f64 / n as f64 }; n];
+    let mut best = (0.0, Vec::new());
+
+    let samples = params.samples.max(1);
+    let elite = ((params.elite * samples as f64).ceil() as usize).max(1).min(samples);
+
+    for _ in 0..params.iterations {
+        if interrupted() {
+            break;
+        }
+        let mut scored = (0..samples)
+            .map(|_| {
+                let sets = sample(&p, k, rng);
+                (Coverage::from_sets(&index, &sets).value(), sets)
+            })
+            .collect::<Vec<_>>();
+        scored.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(Ordering::Equal));
+
+        let mut freq = vec![0.0; n];
+        for (_, sets) in &scored[..elite] {
+            for &s in sets {
+                freq[s] += 1.0 / elite as f64;
+            }
+        }
+        for i in 0..n {
+            p[i] = (1.0 - params.smoothing) * p[i] + params.smoothing * freq[i];
+        }
+
+        if scored[0].0 > best.0 {
+            best = scored.swap_remove(0);
+        }
+    }
+
+    let (objective, mut sol) = best;
+    sol.sort();
+    Solution::new(objective, sol)
+}
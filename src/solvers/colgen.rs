@@ -0,0 +1,194 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use {Instance, IlpOptions, Solution};
+use interrupt::interrupted;
+use backend::{self, SolverBackend};
+use solvers::lagrangian;
+
+/// Proposes sets for column generation, for instances whose sets are too many to list or are
+/// only defined implicitly, like all balls of some radius.
+pub trait SetOracle {
+    /// At most `n` sets whose elements' `prices` sum to more than `threshold`, best first,
+    /// each with the oracle's index of it, which must name the same set in every call.
+    /// Every element of the ground set has a price; other elements are worth nothing. Sets
+    /// the solve already has may be proposed again, and are skipped.
+    fn price(&mut self,
+             prices: &BTreeMap<usize, f64>,
+             threshold: f64,
+             n: usize)
+             -> Vec<(usize, BTreeSet<usize>)>;
+}
+
+/// Prices an explicit collection of sets by scanning it. The index of a set is its position.
+#[derive(Debug, Clone)]
+pub struct ListOracle {
+    pub sets: Vec<BTreeSet<usize>>,
+}
+
+impl SetOracle for ListOracle {
+    fn price(&mut self,
+             prices: &BTreeMap<usize, f64>,
+             threshold: f64,
+             n: usize)
+             -> Vec<(usize, BTreeSet<usize>)> {
+        let mut priced = self.sets
+            .iter()
+            .enumerate()
+            .map(|(i, set)| (set.iter().filter_map(|x| prices.get(x)).sum::<f64>(), i))
+            .filter(|&(price, _)| price > threshold)
+            .collect::<Vec<_>>();
+        priced.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(Ordering::Equal));
+        priced.into_iter().take(n).map(|(_, i)| (i, self.sets[i].clone())).collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Params {
+    /// Most pricing rounds before the restricted instance is solved as it is.
+    pub rounds: usize,
+    /// Most sets added per round.
+    pub columns: usize,
+    /// Subgradient optimization that prices the elements in each round.
+    pub lagrangian: lagrangian::Params,
+}
+
+impl Default for Params {
+    fn default() -> Params {
+        Params {
+            rounds: 100,
+            columns: 10,
+            lagrangian: Default::default(),
+        }
+    }
+}
+
+/// Column generation. Each round prices the elements with the Lagrangian multipliers of the
+/// restricted instance, the one with the sets of `inst`, whose indices in the oracle are
+/// `ids`, plus those generated so far, and asks
+/// `oracle` for sets that would enter the Lagrangian subproblem: those priced above the
+/// `k`-th best set so far, and above 0. Once it has none, the restricted instance is solved
+/// by `backend` as `solve` does.
+///
+/// Returns the restricted instance, the oracle's index of each of its sets, and its
+/// solution, whose sets index the restricted instance; the generated sets follow those of
+/// `inst`, and set costs are dropped. If the oracle ran out of
+/// sets, the solution's bound is the last Lagrangian bound, which then holds for every set
+/// the oracle knows; otherwise, e.g. after `params.rounds` rounds or on an interrupt, it has
+/// none.
+pub fn colgen<O, B>(inst: &Instance,
+                    ids: &[usize],
+                    k: usize,
+                    oracle: &mut O,
+                    backend: &mut B,
+                    params: &Params,
+                    ilp: &backend::Params)
+                    -> Result<(Instance, Vec<usize>, Solution), String>
+    where O: SetOracle + ?Sized,
+          B: SolverBackend + ?Sized
+{
+    // the oracle's sets have no groups, and the multipliers ignore what may be selected
+    if inst.has_selection_constraints() {
+        return Err("colgen does not support group limits or conflicts".to_string());
+    }
+    let mut restricted = inst.clone();
+    // the oracle's sets have no costs
+    restricted.costs = None;
+    restricted.groups = None;
+    let mut ids = ids.to_vec();
+    let mut known = ids.iter().cloned().collect::<HashSet<_>>();
+    let mut bound = None;
+
+    for _ in 0..params.rounds {
+        if interrupted() {
+            break;
+        }
+        let (relaxed, u) = lagrangian::multipliers(&restricted, k, &params.lagrangian);
+        let prices = restricted.ground.iter().cloned().zip(u).collect::<BTreeMap<_, _>>();
+
+        let mut reduced = restricted.sets
+            .iter()
+            .map(|set| set.iter().filter_map(|x| prices.get(x)).sum::<f64>())
+            .collect::<Vec<_>>();
+        reduced.sort_by(|a, b| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+        let threshold = reduced.get(k.saturating_sub(1)).map_or(0.0, |&r| r.max(0.0));
+
+        let columns = oracle.price(&prices, threshold, params.columns)
+            .into_iter()
+            .filter(|&(i, _)| !known.contains(&i))
+            .collect::<Vec<_>>();
+        if columns.is_empty() {
+            bound = relaxed.bound;
+            break;
+        }
+        for (i, set) in columns {
+            if known.insert(i) {
+                ids.push(i);
+                restricted.sets.push(set);
+            }
+        }
+    }
+
+    let mut sol = ::solve(&restricted, k, backend, ilp, &IlpOptions::default())?;
+    // the backend's bound only holds for the restricted instance
+    sol.bound = bound.map(|b: f64| b.max(sol.objective));
+    sol.gap = sol.bound.map(|b| backend::relative_gap(sol.objective, b));
+    Ok((restricted, ids, sol))
+}
+
+/// Column generation over the sets of `inst`, listed by a `ListOracle`, starting from its `k`
+/// largest sets. The solution's sets index `inst`.
+pub fn colgen_list<B>(inst: &Instance,
+                      k: usize,
+                      backend: &mut B,
+                      params: &Params,
+                      ilp: &backend::Params)
+                      -> Result<Solution, String>
+    where B: SolverBackend + ?Sized
+{
+    let mut largest = (0..inst.sets.len()).collect::<Vec<_>>();
+    largest.sort_by_key(|&i| Reverse(inst.sets[i].len()));
+    largest.truncate(k);
+    let sets = largest.iter().map(|&i| inst.sets[i].clone()).collect();
+    let initial = Instance {
+        weights: inst.weights.clone(),
+        demands: inst.demands.clone(),
+        ..Instance::new(inst.ground.clone(), sets)
+    };
+    let mut oracle = ListOracle { sets: inst.sets.clone() };
+    let (_, ids, mut sol) = colgen(&initial, &largest, k, &mut oracle, backend, params, ilp)?;
+    sol.sol = sol.sol.iter().map(|&i| ids[i]).collect();
+    sol.sol.sort();
+    Ok(sol)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use backend::{BruteForce, Staged};
+
+    fn instance(n: usize, sets: Vec<Vec<usize>>) -> Instance {
+        Instance::new((0..n).collect(),
+                      sets.into_iter().map(|s| s.into_iter().collect()).collect())
+    }
+
+    #[test]
+    fn prices_in_the_sets_the_largest_miss() {
+        // the two largest sets overlap, so the best pair needs a set priced in
+        let inst = instance(6, vec![vec![0, 1, 2], vec![1, 2, 3], vec![4, 5], vec![0]]);
+        let mut backend = Staged::new(BruteForce);
+        let sol = colgen_list(&inst, 2, &mut backend, &Params::default(), &Default::default())
+            .unwrap();
+        assert!(sol.sol.contains(&2));
+        assert_eq!(sol.objective, 5.0);
+    }
+
+    #[test]
+    fn list_oracle_prices_above_the_threshold() {
+        let sets = instance(3, vec![vec![0], vec![1, 2], vec![2]]).sets;
+        let mut oracle = ListOracle { sets: sets };
+        let prices = vec![(0, 1.0), (1, 0.5), (2, 2.0)].into_iter().collect();
+        let priced = oracle.price(&prices, 1.0, 10).into_iter().map(|(i, _)| i).collect::<Vec<_>>();
+        assert_eq!(priced, vec![1, 2]);
+        assert_eq!(oracle.price(&prices, 1.0, 1).len(), 1);
+    }
+}
@@ -0,0 +1,68 @@
+use rand::Rng;
+use {Instance, Solution};
+use coverage::{Index, Coverage};
+use solvers::lp::pipage;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Params {
+    /// Random sets drawn per estimate of the multilinear extension.
+    pub samples: usize,
+    /// Length of each continuous greedy step, in (0, 1].
+    pub step: f64,
+}
+
+/// Draws a random selection that contains each set `i` independently with probability `x[i]`.
+fn draw<'a, R: Rng>(index: &'a Index, x: &[f64], rng: &mut R) -> Coverage<'a> {
+    let picked = (0..x.len()).filter(|&i| rng.gen::<f64>() < x[i]).collect::<Vec<_>>();
+    Coverage::from_sets(index, &picked)
+}
+
+/// Sampled estimate of the gradient of the multilinear extension `F` at `x`, where
+/// `dF/dx_i = E[f(R + i) - f(R)]` for `R` drawn from `x`.
+fn gradient<R: Rng>(index: &Index, x: &[f64], samples: usize, rng: &mut R) -> Vec<f64> {
+    let mut grad = vec![0.0; x.len()];
+    for _ in 0..samples {
+        let cov = draw(index, x, rng);
+        for i in 0..x.len() {
+            if !cov.contains(i) {
+                grad[i] += cov.gain(i) / samples as f64;
+            }
+        }
+    }
+    grad
+}
+
+/// Continuous greedy (Calinescu et al., 2011). Moves `x` from 0 towards the best vertex of
+/// the cardinality polytope under the sampled gradient, one step at a time, which gives
+/// `F(x) >= (1 - 1/e) OPT` up to the sampling and discretization error. The fractional point
+/// is then rounded with `pipage`; its sampled value is reported as `fractional`.
+pub fn continuous_greedy<R: Rng>(inst: &Instance,
+                                 k: usize,
+                                 params: &Params,
+                                 rng: &mut R)
+                                 -> Solution {
+    assert!(params.step > 0.0 && params.step <= 1.0, "step must be in (0, 1]");
+    let index = Index::new(inst);
+    let n = index.num_sets();
+    let samples = params.samples.max(1);
+    let mut x = vec![0.0; n];
+
+    let mut t = 0.0;
+    while t < 1.0 {
+        let delta = params.step.min(1.0 - t);
+        let grad = gradient(&index, &x, samples, rng);
+        let mut order = (0..n).collect::<Vec<_>>();
+        order.sort_by(|&a, &b| grad[b].partial_cmp(&grad[a]).unwrap());
+        for &i in order.iter().take(k) {
+            x[i] = (x[i] + delta).min(1.0);
+        }
+        t += delta;
+    }
+
+    let fractional = (0..samples).map(|_| draw(&index, &x, rng).value()).sum::<f64>() /
+                     samples as f64;
+
+    let mut sol = pipage(inst, k, &x);
+    sol.fractional = Some(fractional);
+    sol
+}
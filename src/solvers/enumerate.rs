@@ -0,0 +1,164 @@
+use std::cmp::Ordering;
+use {Instance, Solution};
+use coverage::{Index, Coverage};
+use solvers::greedy::lazy_greedy;
+
+/// Minimum bound improvement over the incumbent for a subtree to be explored.
+const EPS: f64 = 1e-9;
+
+/// Sets that are not contained in another set. A dominated set can always be exchanged for
+/// one that dominates it without losing coverage, so some optimal solution avoids them. Of
+/// several identical sets only the first is kept. Returns the kept sets by decreasing
+/// weight.
+pub fn undominated(index: &Index) -> Vec<usize> {
+    let sizes = (0..index.num_sets()).map(|s| set_weight(index, s)).collect::<Vec<_>>();
+    let mut order = (0..index.num_sets()).collect::<Vec<_>>();
+    order.sort_by(|&a, &b| {
+        sizes[b].partial_cmp(&sizes[a]).unwrap_or(Ordering::Equal).then(a.cmp(&b))
+    });
+    let mut rank = vec![0; index.num_sets()];
+    for (r, &s) in order.iter().enumerate() {
+        rank[s] = r;
+    }
+
+    order.iter()
+        .cloned()
+        .filter(|&s| {
+            let set = &index.sets[s];
+            if set.is_empty() {
+                return false;
+            }
+            // candidate supersets are the earlier-ranked sets containing the rarest element
+            let rarest = *set.iter().min_by_key(|&&e| index.containing[e].len()).unwrap();
+            !index.containing[rarest].iter().any(|&t| {
+                t != s && rank[t] < rank[s] &&
+                set.iter().all(|e| index.sets[t].binary_search(e).is_ok())
+            })
+        })
+        .collect()
+}
+
+/// Total weight of the elements of set `s`.
+fn set_weight(index: &Index, s: usize) -> f64 {
+    index.sets[s].iter().map(|&e| index.weights[e]).sum()
+}
+
+struct Search<'a> {
+    cov: Coverage<'a>,
+    /// Candidate sets by decreasing size, and their sizes.
+    cands: Vec<usize>,
+    sizes: Vec<f64>,
+    k: usize,
+    best: (f64, Vec<usize>),
+}
+
+impl<'a> Search<'a> {
+    /// Sum of the `picks` largest potentials among candidates from `start` on, which bound
+    /// their gains in any completion even with demands.
+    fn gain_bound(&self, start: usize, picks: usize) -> f64 {
+        let mut gains = self.cands[start..]
+            .iter()
+            .map(|&s| self.cov.potential(s))
+            .collect::<Vec<_>>();
+        gains.sort_by(|a, b| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+        gains.into_iter().take(picks).sum()
+    }
+
+    /// Enumerates the k-subsets of candidates in lexicographic order. A set's size bounds its
+    /// gain and sizes are sorted, so once the next `picks` sizes cannot beat the incumbent no
+    /// later candidate can either.
+    fn extend(&mut self, start: usize) {
+        if self.cov.value() > self.best.0 + EPS {
+            self.best = (self.cov.value(), self.cov.selection());
+        }
+        let picks = self.k - self.cov.size();
+        if picks == 0 || self.cov.value() + self.gain_bound(start, picks) <= self.best.0 + EPS {
+            return;
+        }
+
+        for i in start..self.cands.len() {
+            let end = (i + picks).min(self.sizes.len());
+            let size_bound = self.sizes[i..end].iter().sum::<f64>();
+            if self.cov.value() + size_bound <= self.best.0 + EPS {
+                break;
+            }
+            let s = self.cands[i];
+            self.cov.add(s);
+            self.extend(i + 1);
+            self.cov.remove(s);
+        }
+    }
+}
+
+/// Exact solver for small `k`: enumerates k-subsets of the undominated sets with
+/// size- and gain-based pruning against the lazy greedy incumbent. The search is
+/// O(n^k) in the worst case but usually far quicker than building an ILP when k <= 6.
+pub fn exact_small_k(inst: &Instance, k: usize) -> Solution {
+    let index = Index::new(inst);
+    let greedy = lazy_greedy(inst, k);
+    let cands = if inst.has_demands() {
+        // a dominated set can still help to meet a demand
+        let mut cands = (0..index.num_sets())
+            .filter(|&s| !index.sets[s].is_empty())
+            .collect::<Vec<_>>();
+        cands.sort_by(|&a, &b| {
+            set_weight(&index, b).partial_cmp(&set_weight(&index, a)).unwrap_or(Ordering::Equal)
+        });
+        cands
+    } else {
+        undominated(&index)
+    };
+    let sizes = cands.iter().map(|&s| set_weight(&index, s)).collect();
+
+    let mut search = Search {
+        cov: Coverage::new(&index),
+        k: k.min(cands.len()),
+        cands: cands,
+        sizes: sizes,
+        best: (greedy.objective, greedy.sol),
+    };
+    search.extend(0);
+
+    let (objective, mut sol) = search.best;
+    sol.sort();
+    let mut sol = Solution::new(objective, sol);
+    sol.bound = Some(objective);
+    sol
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solvers::bnb::bnb;
+
+    fn instance(n: usize, sets: Vec<Vec<usize>>) -> Instance {
+        Instance::new((0..n).collect(),
+                      sets.into_iter().map(|s| s.into_iter().collect()).collect())
+    }
+
+    #[test]
+    fn undominated_drops_subsets_and_copies() {
+        let inst = instance(5, vec![vec![0, 1], vec![0, 1, 2], vec![3], vec![0, 1, 2], vec![]]);
+        assert_eq!(undominated(&Index::new(&inst)), vec![1, 2]);
+    }
+
+    #[test]
+    fn matches_branch_and_bound() {
+        let mut state = 54321u64;
+        let mut next = |n: u64| {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (state >> 33) % n
+        };
+        for _ in 0..20 {
+            let sets = (0..10)
+                .map(|_| (0..12).filter(|_| next(4) == 0).map(|x| x as usize).collect())
+                .collect();
+            let inst = instance(12, sets);
+            for k in 1..4 {
+                let sol = exact_small_k(&inst, k);
+                assert!(sol.sol.len() <= k);
+                assert_eq!(sol.objective, bnb(&inst, k).objective);
+            }
+        }
+    }
+}
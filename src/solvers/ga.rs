@@ -0,0 +1,123 @@
+use std::collections::BTreeSet;
+use rand::Rng;
+use {Instance, Solution};
+use interrupt::interrupted;
+use coverage::{Index, Coverage};
+use solvers::greedy::lazy_greedy;
+use solvers::local::fill_random;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Params {
+    pub population: usize,
+    pub mutation: f64,
+    pub generations: usize,
+}
+
+/// A chromosome: a sorted k-subset of set indices.
+#[derive(Debug, Clone)]
+struct Individual {
+    sets: Vec<usize>,
+    fitness: f64,
+}
+
+impl Individual {
+    fn new(index: &Index, mut sets: Vec<usize>) -> Individual {
+        sets.sort();
+        Individual {
+            fitness: Coverage::from_sets(index, &sets).value(),
+            sets: sets,
+        }
+    }
+}
+
+/// Binary tournament selection.
+fn tournament<'a, R: Rng>(pop: &'a [Individual], rng: &mut R) -> &'a Individual {
+    let a = &pop[rng.gen_range(0, pop.len())];
+    let b = &pop[rng.gen_range(0, pop.len())];
+    if a.fitness >= b.fitness { a } else { b }
+}
+
+/// Keeps the sets both parents agree on and fills the remaining slots greedily from the
+/// sets only one parent has, so the child always has exactly `k` sets.
+fn crossover(index: &Index, a: &Individual, b: &Individual) -> Vec<usize> {
+    let a_sets = a.sets.iter().cloned().collect::<BTreeSet<_>>();
+    let b_sets = b.sets.iter().cloned().collect::<BTreeSet<_>>();
+    let common = a_sets.intersection(&b_sets).cloned().collect::<Vec<_>>();
+    let mut pool = a_sets.symmetric_difference(&b_sets).cloned().collect::<Vec<_>>();
+
+    let mut cov = Coverage::from_sets(index, &common);
+    let mut child = common;
+    while child.len() < a.sets.len() && !pool.is_empty() {
+        let mut best = (0, cov.gain(pool[0]));
+        for i in 1..pool.len() {
+            let gain = cov.gain(pool[i]);
+            if gain > best.1 {
+                best = (i, gain);
+            }
+        }
+        let s = pool.swap_remove(best.0);
+        cov.add(s);
+        child.push(s);
+    }
+    child
+}
+
+/// Replaces each gene with probability `rate` by a random set not already in the chromosome.
+fn mutate<R: Rng>(sets: &mut [usize], num_sets: usize, rate: f64, rng: &mut R) {
+    if sets.len() >= num_sets {
+        return;
+    }
+    let mut present = sets.iter().cloned().collect::<BTreeSet<_>>();
+    for gene in sets.iter_mut() {
+        if rng.gen::<f64>() >= rate {
+            continue;
+        }
+        let mut t = rng.gen_range(0, num_sets);
+        while present.contains(&t) {
+            t = rng.gen_range(0, num_sets);
+        }
+        present.remove(gene);
+        present.insert(t);
+        *gene = t;
+    }
+}
+
+/// Genetic algorithm over k-subsets with elitism, binary tournament selection,
+/// coverage-aware crossover and random replacement mutation. The initial population is
+/// random apart from one greedy individual.
+pub fn ga<R: Rng>(inst: &Instance, k: usize, params: &Params, rng: &mut R) -> Solution {
+    let index = Index::new(inst);
+    let n = index.num_sets();
+    let k = k.min(n);
+
+    let mut seed = Coverage::from_sets(&index, &lazy_greedy(inst, k).sol);
+    fill_random(&mut seed, k, rng);
+    let mut pop = vec![Individual::new(&index, seed.selection())];
+    while pop.len() < params.population.max(2) {
+        let mut sets = (0..n).collect::<Vec<_>>();
+        rng.shuffle(&mut sets);
+        sets.truncate(k);
+        pop.push(Individual::new(&index, sets));
+    }
+
+    let fittest = |pop: &[Individual]| {
+        pop.iter().fold(&pop[0], |best, ind| if ind.fitness > best.fitness { ind } else { best }).clone()
+    };
+    let mut best = fittest(&pop);
+
+    for _ in 0..params.generations {
+        if interrupted() {
+            break;
+        }
+        let mut next = vec![best.clone()];
+        while next.len() < pop.len() {
+            let mut child = crossover(&index, tournament(&pop, rng), tournament(&pop, rng));
+            mutate(&mut child, n, params.mutation, rng);
+            next.push(Individual::new(&index, child));
+        }
+        pop = next;
+        best = fittest(&pop);
+    }
+
+    Solution::new(best.fitness, best.sets)
+}
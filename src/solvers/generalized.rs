@@ -0,0 +1,189 @@
+use {Instance, Solution};
+use backend::{self, Model, Sense, SolverBackend, VarType};
+use coverage::{Index, Coverage};
+
+/// The elements of each set of `index` along with the set's profit for them.
+fn membership(inst: &Instance, index: &Index) -> Vec<Vec<(usize, f64)>> {
+    let profits = inst.profits();
+    (0..index.num_sets())
+        .map(|i| {
+            // the index drops the elements that are not in the ground set, keeping the order
+            inst.sets[i]
+                .iter()
+                .zip(&profits[i])
+                .filter(|&(x, _)| inst.ground.contains(x))
+                .map(|(_, &p)| p)
+                .zip(&index.sets[i])
+                .map(|(p, &e)| (e, p))
+                .collect()
+        })
+        .collect()
+}
+
+/// The set each element of `index` is credited to by `sets`, the one with the most profit
+/// for it, and that profit, if any has some.
+fn credit(index: &Index,
+          members: &[Vec<(usize, f64)>],
+          sets: &[usize])
+          -> Vec<Option<(usize, f64)>> {
+    let mut credited = vec![None; index.num_elements];
+    for &i in sets {
+        for &(e, p) in &members[i] {
+            if p > credited[e].map_or(0.0, |(_, q)| q) {
+                credited[e] = Some((i, p));
+            }
+        }
+    }
+    credited
+}
+
+/// The profit of `sets`, where each element is worth the most profit of the selected sets
+/// containing it, along with the elements each of them is credited with, in the order of
+/// `sets`.
+pub fn profit(inst: &Instance, sets: &[usize]) -> (f64, Vec<Vec<usize>>) {
+    let index = Index::new(inst);
+    let members = membership(inst, &index);
+    let credited = credit(&index, &members, sets);
+    let ground = inst.ground.iter().cloned().collect::<Vec<_>>();
+    let value = credited.iter().filter_map(|c| c.map(|(_, p)| p)).sum();
+    let assignment = sets.iter()
+        .map(|&i| {
+            members[i]
+                .iter()
+                .filter(|&&(e, _)| credited[e].map(|(j, _)| j) == Some(i))
+                .map(|&(e, _)| ground[e])
+                .collect()
+        })
+        .collect();
+    (value, assignment)
+}
+
+/// Greedy on the profit, which is submodular, so this is a (1 - 1/e)-approximation. Starts
+/// from the required sets and only adds the sets that the selection allows, like `greedy`.
+/// The solution's objective is its `profit`, with the credited elements in `assignment`.
+pub fn generalized_greedy(inst: &Instance, k: usize) -> Solution {
+    assert!(!inst.has_demands(), "profits do not support demands");
+    let index = Index::new(inst);
+    let members = membership(inst, &index);
+    // tracks the selection for its constraints
+    let mut cov = Coverage::initial(&index);
+    let mut sol = cov.selection();
+    let mut best = vec![0.0f64; index.num_elements];
+    for &i in &sol {
+        for &(e, p) in &members[i] {
+            best[e] = best[e].max(p);
+        }
+    }
+
+    while sol.len() < k {
+        let mut chosen = None;
+        for s in 0..index.num_sets() {
+            if cov.contains(s) || !cov.allows(s) {
+                continue;
+            }
+            let gain = members[s]
+                .iter()
+                .map(|&(e, p)| (p - best[e]).max(0.0))
+                .sum::<f64>();
+            if gain > 0.0 && chosen.is_none_or(|(_, g)| gain > g) {
+                chosen = Some((s, gain));
+            }
+        }
+
+        match chosen {
+            Some((s, _)) => {
+                cov.add(s);
+                for &(e, p) in &members[s] {
+                    best[e] = best[e].max(p);
+                }
+                sol.push(s);
+            }
+            None => break,
+        }
+    }
+
+    let (value, assignment) = profit(inst, &sol);
+    let mut out = Solution::new(value, sol);
+    out.assignment = Some(assignment);
+    out
+}
+
+/// Generalized max coverage with `backend`: selects at most `k` sets and credits each element
+/// to at most one selected set containing it, maximizing the total profit credited, which
+/// credits every element to its most profitable selected set. The model has the set
+/// variables first, then a credit variable per set and element of it with profit. The
+/// solution's objective is its `profit`, with the credited elements in `assignment`.
+///
+/// The solver is warm started from `generalized_greedy`, and the model is also written to
+/// `problem.lp`. The bound and gap are as for `solve`.
+pub fn generalized<B>(inst: &Instance,
+                      k: usize,
+                      backend: &mut B,
+                      params: &backend::Params)
+                      -> Result<Solution, String>
+    where B: SolverBackend + ?Sized
+{
+    if inst.has_demands() {
+        return Err("profits do not support demands".to_string());
+    }
+    let index = Index::new(inst);
+    let members = membership(inst, &index);
+    let mut model = Model::new("generalized", true);
+    let set_vars = (0..index.num_sets())
+        .map(|i| model.add_var(format!("s{}", i), VarType::Binary, 0.0, 0.0, 1.0))
+        .collect::<Vec<_>>();
+    model.selection = Some(0..set_vars.len());
+    let cardinality = set_vars.iter().map(|&j| (j, 1.0)).collect();
+    model.add_row("cardinality".to_string(), cardinality, Sense::LessEq, k as f64);
+    backend::add_selection_rows(&mut model, inst, &set_vars);
+    let selection_vars = set_vars.len()..model.vars.len();
+
+    // the credit variables of each set and element, and of each element
+    let ground = inst.ground.iter().cloned().collect::<Vec<_>>();
+    let mut credit_vars = Vec::new();
+    let mut crediting = vec![Vec::new(); index.num_elements];
+    for (i, set) in members.iter().enumerate() {
+        for &(e, p) in set.iter().filter(|&&(_, p)| p > 0.0) {
+            let name = format!("c{}_{}", i, ground[e]);
+            let c = model.add_var(name, VarType::Binary, p, 0.0, 1.0);
+            let terms = vec![(c, 1.0), (set_vars[i], -1.0)];
+            model.add_row(format!("credit{}_{}", i, ground[e]), terms, Sense::LessEq, 0.0);
+            crediting[e].push((c, 1.0));
+            credit_vars.push((c, i, e));
+        }
+    }
+    for (e, terms) in crediting.into_iter().enumerate().filter(|(_, t)| !t.is_empty()) {
+        model.add_row(format!("once{}", ground[e]), terms, Sense::LessEq, 1.0);
+    }
+
+    let greedy = generalized_greedy(inst, k);
+    let mut values = vec![0.0; model.vars.len()];
+    for &i in &greedy.sol {
+        values[set_vars[i]] = 1.0;
+    }
+    for (j, value) in selection_vars.zip(backend::selection_values(inst, &greedy.sol)) {
+        values[j] = value;
+    }
+    let credited = credit(&index, &members, &greedy.sol);
+    for &(c, i, e) in &credit_vars {
+        if credited[e].map(|(j, _)| j) == Some(i) {
+            values[c] = 1.0;
+        }
+    }
+    model.start = Some(values);
+    ::write_problem_lp(&model)?;
+
+    params.apply(backend)?;
+    backend.build_model(model)?;
+    backend.solve()?;
+    let sol = backend.extract_solution()?;
+    let selected = (0..set_vars.len())
+        .filter(|&i| sol.values[set_vars[i]] > 0.5)
+        .collect::<Vec<_>>();
+    let (objective, assignment) = profit(inst, &selected);
+
+    let mut out = Solution::new(objective, selected);
+    out.assignment = Some(assignment);
+    ::fill_bound(&mut out, &sol, params);
+    Ok(out)
+}
@@ -0,0 +1,58 @@
+use rand::Rng;
+use {Instance, Solution};
+use interrupt::interrupted;
+use coverage::{Index, Coverage};
+use solvers::local::swap_search;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Params {
+    /// Restricted candidate list parameter: 0 is pure greedy, 1 is uniformly random.
+    pub alpha: f64,
+    pub restarts: usize,
+}
+
+/// Randomized greedy construction. Each step picks uniformly among the sets whose gain is
+/// within `alpha * (max - min)` of the best gain.
+fn construct<R: Rng>(cov: &mut Coverage, k: usize, alpha: f64, rng: &mut R) {
+    let n = cov.index().num_sets();
+    while cov.size() < k {
+        let gains = (0..n)
+            .filter(|&s| !cov.contains(s) && cov.allows(s))
+            .map(|s| (s, cov.gain(s)))
+            .collect::<Vec<_>>();
+        let max = gains.iter().fold(0.0f64, |m, &(_, g)| m.max(g));
+        if max <= 0.0 {
+            break;
+        }
+        let min = gains.iter().fold(max, |m, &(_, g)| m.min(g));
+        let threshold = max - alpha * (max - min);
+        let rcl = gains.into_iter()
+            .filter(|&(_, g)| g >= threshold && g > 0.0)
+            .map(|(s, _)| s)
+            .collect::<Vec<_>>();
+        cov.add(*rng.choose(&rcl).unwrap());
+    }
+}
+
+/// GRASP: repeated randomized greedy construction followed by swap local search, keeping
+/// the best solution over all restarts.
+pub fn grasp<R: Rng>(inst: &Instance, k: usize, params: &Params, rng: &mut R) -> Solution {
+    let index = Index::new(inst);
+    let mut best: Option<(f64, Vec<usize>)> = None;
+
+    for _ in 0..params.restarts.max(1) {
+        let mut cov = Coverage::initial(&index);
+        construct(&mut cov, k, params.alpha, rng);
+        swap_search(&mut cov);
+        if best.as_ref().is_none_or(|&(v, _)| cov.value() > v) {
+            best = Some((cov.value(), cov.selection()));
+        }
+        // stop after a whole restart, so that there is a solution
+        if interrupted() {
+            break;
+        }
+    }
+
+    let (objective, sol) = best.unwrap();
+    Solution::new(objective, sol)
+}
@@ -0,0 +1,43 @@
+use std::sync::Arc;
+use std::thread;
+use rand::Rng;
+use {Instance, Solution};
+use coverage::Index;
+use solvers::greedy::lazy_greedy_over;
+
+/// Two-round distributed greedy (GreeDi, Mirzasoleiman et al., 2013). The sets are split
+/// randomly into `partitions` parts, lazy greedy picks `k` sets from each part in parallel
+/// threads, and a final lazy greedy runs over the union of those picks. Returns the better of
+/// the final solution and the best single partition.
+pub fn greedi<R: Rng>(inst: &Instance, k: usize, partitions: usize, rng: &mut R) -> Solution {
+    let index = Arc::new(Index::new(inst));
+    let mut order = (0..index.num_sets()).collect::<Vec<_>>();
+    rng.shuffle(&mut order);
+
+    let parts = partitions.max(1);
+    let chunk = order.len().div_ceil(parts);
+    let handles = order.chunks(chunk.max(1))
+        .map(|part| {
+            let index = index.clone();
+            let part = part.to_vec();
+            thread::spawn(move || lazy_greedy_over(&index, part, k))
+        })
+        .collect::<Vec<_>>();
+
+    let locals = handles.into_iter()
+        .map(|h| h.join().expect("greedy worker panicked"))
+        .collect::<Vec<_>>();
+
+    let mut union = locals.iter().flat_map(|sol| sol.sol.iter().cloned()).collect::<Vec<_>>();
+    union.sort();
+    union.dedup();
+
+    let merged = lazy_greedy_over(&index, union, k);
+    let best = locals.into_iter().fold(merged, |best, sol| {
+        if sol.objective > best.objective { sol } else { best }
+    });
+
+    let mut sol = best.sol;
+    sol.sort();
+    Solution::new(best.objective, sol)
+}
@@ -0,0 +1,789 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use rand::Rng;
+use {Instance, Solution};
+use coverage::{Index, Coverage};
+use submodular::{CoverageFn, SubmodularFn};
+
+/// The classic greedy algorithm: repeatedly select the set with the largest marginal gain.
+/// Achieves a (1 - 1/e)-approximation.
+///
+/// When elements have demands, this and the other greedy algorithms here weigh sets by
+/// `Coverage::demand_gain` instead, as the gain of a single set is often 0 then, and the
+/// guarantees no longer hold. They all start from the instance's required sets and only add
+/// sets that the selection allows.
+pub fn greedy(inst: &Instance, k: usize) -> Solution {
+    let index = Index::new(inst);
+    greedy_fn(&mut CoverageFn::initial(&index), k)
+}
+
+/// `greedy` for any submodular function: selects up to `k` items, starting from those `f`
+/// has selected already, and leaves `f` at the selection.
+pub fn greedy_fn<F: SubmodularFn + ?Sized>(f: &mut F, k: usize) -> Solution {
+    let mut sol = f.selection();
+    extend_greedy(f, &mut sol, k);
+    Solution::new(f.value(), sol)
+}
+
+/// Continues `greedy` from the selection `sol` of `f` until it has `k` items.
+fn extend_greedy<F: SubmodularFn + ?Sized>(f: &mut F, sol: &mut Vec<usize>, k: usize) {
+    while sol.len() < k {
+        let mut best = None;
+        for s in 0..f.num_items() {
+            if f.contains(s) || !f.allows(s) {
+                continue;
+            }
+            let gain = f.gain(s);
+            if gain > 0.0 && best.is_none_or(|(_, g)| gain > g) {
+                best = Some((s, gain));
+            }
+        }
+
+        match best {
+            Some((s, _)) => {
+                f.add(s);
+                sol.push(s);
+            }
+            None => break,
+        }
+    }
+}
+
+/// Fair greedy: first reaches the fair share of each label that `min_coverage` names, each
+/// step selecting the set that makes the most progress towards the shares still missing,
+/// then continues as `greedy` up to `k` sets. Fails if it cannot reach the shares with `k`
+/// sets this way, which does not mean that no selection can.
+pub fn fair_greedy(inst: &Instance, k: usize) -> Result<Solution, String> {
+    let index = Index::new(inst);
+    let labels = inst.labels();
+    let shares = inst.fair_shares();
+    let mut cov = Coverage::initial(&index);
+    let mut sol = cov.selection();
+    let mut covered = vec![0.0; shares.len()];
+    for e in (0..index.num_elements).filter(|&e| cov.covered(e) && labels[e] < shares.len()) {
+        covered[labels[e]] += index.weights[e];
+    }
+    let missing = |covered: &[f64], l: usize| (shares[l] - covered[l]).max(0.0);
+
+    while (0..shares.len()).any(|l| missing(&covered, l) > 1e-9) {
+        if sol.len() >= k {
+            return Err(format!("fair greedy does not reach the fair shares with k = {}", k));
+        }
+        let mut best = None;
+        for s in 0..index.num_sets() {
+            if cov.contains(s) || !cov.allows(s) {
+                continue;
+            }
+            // progress of each label as in `Coverage::demand_gain`
+            let mut gains = vec![0.0; shares.len()];
+            for &e in index.sets[s].iter().filter(|&&e| !cov.covered(e)) {
+                if labels[e] < gains.len() {
+                    gains[labels[e]] += index.weights[e] / index.demands[e] as f64;
+                }
+            }
+            let progress = (0..shares.len())
+                .map(|l| gains[l].min(missing(&covered, l)))
+                .sum::<f64>();
+            if progress > 0.0 && best.is_none_or(|(_, p)| progress > p) {
+                best = Some((s, progress));
+            }
+        }
+
+        let s = match best {
+            Some((s, _)) => s,
+            None => return Err("fair greedy does not reach the fair shares".to_string()),
+        };
+        let open = index.sets[s].iter().cloned().filter(|&e| !cov.covered(e)).collect::<Vec<_>>();
+        cov.add(s);
+        sol.push(s);
+        for e in open.into_iter().filter(|&e| cov.covered(e) && labels[e] < shares.len()) {
+            covered[labels[e]] += index.weights[e];
+        }
+    }
+
+    let mut f = CoverageFn::new(cov);
+    extend_greedy(&mut f, &mut sol, k);
+    Ok(Solution::new(f.value(), sol))
+}
+
+/// `set` and the unselected sets it needs, directly or through others, with `set` first.
+fn with_prerequisites(cov: &Coverage, set: usize) -> Vec<usize> {
+    let index = cov.index();
+    let mut needed = vec![set];
+    let mut i = 0;
+    while i < needed.len() {
+        for &p in &index.prerequisites[needed[i]] {
+            if !cov.contains(p) && !needed.contains(&p) {
+                needed.push(p);
+            }
+        }
+        i += 1;
+    }
+    needed
+}
+
+/// Precedence greedy: like `greedy`, but each step selects a set together with the
+/// unselected sets it needs, picking the set whose gain per set selected is largest among
+/// those that fit in `k` and that the selection allows with all they need. The solution
+/// keeps to the instance's prerequisites.
+pub fn precedence_greedy(inst: &Instance, k: usize) -> Solution {
+    let index = Index::new(inst);
+    let mut cov = Coverage::initial(&index);
+    let mut sol = cov.selection();
+
+    loop {
+        let mut best = None;
+        for s in 0..index.num_sets() {
+            if cov.contains(s) {
+                continue;
+            }
+            let needed = with_prerequisites(&cov, s);
+            if sol.len() + needed.len() > k {
+                continue;
+            }
+            // tries the sets, then takes them back out
+            let mut gain = 0.0;
+            let mut added = Vec::with_capacity(needed.len());
+            for &t in &needed {
+                if !cov.allows(t) {
+                    break;
+                }
+                gain += cov.demand_gain(t);
+                cov.add(t);
+                added.push(t);
+            }
+            let allowed = added.len() == needed.len();
+            for t in added {
+                cov.remove(t);
+            }
+            let rate = gain / needed.len() as f64;
+            if allowed && rate > 0.0 && best.as_ref().is_none_or(|&(_, r)| rate > r) {
+                best = Some((needed, rate));
+            }
+        }
+
+        match best {
+            Some((needed, _)) => {
+                for s in needed {
+                    cov.add(s);
+                    sol.push(s);
+                }
+            }
+            None => break,
+        }
+    }
+
+    Solution::new(cov.value(), sol)
+}
+
+/// Budgeted greedy (Khuller, Moss and Naor, 1999): repeatedly selects the set with the
+/// largest marginal gain per unit cost that still fits in `budget`, then returns the better of
+/// that selection and the best single set within budget, a (1 - 1/sqrt(e))-approximation.
+/// At most `k` sets are selected either way.
+pub fn budgeted_greedy(inst: &Instance, k: usize, budget: f64) -> Solution {
+    let index = Index::new(inst);
+    let costs = inst.costs();
+    let mut cov = Coverage::initial(&index);
+    let mut sol = cov.selection();
+    let mut spent = inst.cost(&sol);
+
+    while sol.len() < k {
+        let mut best = None;
+        for s in 0..index.num_sets() {
+            if cov.contains(s) || !cov.allows(s) || spent + costs[s] > budget {
+                continue;
+            }
+            let gain = cov.demand_gain(s);
+            // free sets come first
+            let ratio = if costs[s] > 0.0 { gain / costs[s] } else { f64::INFINITY };
+            if gain > 0.0 && best.is_none_or(|(_, r)| ratio > r) {
+                best = Some((s, ratio));
+            }
+        }
+
+        match best {
+            Some((s, _)) => {
+                cov.add(s);
+                sol.push(s);
+                spent += costs[s];
+            }
+            None => break,
+        }
+    }
+
+    // the single set joins the required sets
+    let initial = Coverage::initial(&index);
+    let base = inst.cost(&initial.selection());
+    let single = (0..index.num_sets())
+        .filter(|&s| !initial.contains(s) && base + costs[s] <= budget && initial.allows(s))
+        .map(|s| (s, initial.value() + initial.gain(s)))
+        .fold(None, |best: Option<(usize, f64)>, (s, value)| {
+            if best.is_none_or(|(_, v)| value > v) { Some((s, value)) } else { best }
+        });
+    match single {
+        Some((s, value)) if initial.size() < k && value > cov.value() => {
+            let mut sol = initial.selection();
+            sol.push(s);
+            Solution::new(value, sol)
+        }
+        _ => Solution::new(cov.value(), sol),
+    }
+}
+
+/// Prize-collecting greedy: repeatedly selects the set whose gain in covered weight and
+/// avoided penalties most exceeds its cost, until no set's does or `k` are selected. The
+/// solution's objective is `Instance::prize`.
+pub fn prize_collecting_greedy(inst: &Instance, k: usize) -> Solution {
+    let prizes = inst.prize_instance();
+    let index = Index::new(&prizes);
+    let costs = inst.costs();
+    let mut cov = Coverage::initial(&index);
+    let mut sol = cov.selection();
+
+    while sol.len() < k {
+        let mut best = None;
+        for s in 0..index.num_sets() {
+            if cov.contains(s) || !cov.allows(s) {
+                continue;
+            }
+            let net = cov.demand_gain(s) - costs[s];
+            if net > 0.0 && best.is_none_or(|(_, n)| net > n) {
+                best = Some((s, net));
+            }
+        }
+
+        match best {
+            Some((s, _)) => {
+                cov.add(s);
+                sol.push(s);
+            }
+            None => break,
+        }
+    }
+
+    Solution::new(inst.prize(&sol), sol)
+}
+
+/// Robust greedy: repeatedly selects the set that most raises the least weight covered in
+/// any scenario, breaking ties by the total over the scenarios, until `k` are selected or no
+/// set raises either. A heuristic, as the least weight is not submodular. The solution's
+/// objective is `Instance::worst_case`.
+pub fn robust_greedy(inst: &Instance, k: usize) -> Solution {
+    let index = Index::new(inst);
+    let scenarios = inst.scenarios();
+    let mut cov = Coverage::initial(&index);
+    let mut sol = cov.selection();
+    let mut values = scenarios.iter().map(|w| cov.value_by(w)).collect::<Vec<_>>();
+
+    while sol.len() < k {
+        let worst = values.iter().cloned().fold(f64::INFINITY, f64::min);
+        let total = values.iter().sum::<f64>();
+        let mut best = None;
+        for s in 0..index.num_sets() {
+            if cov.contains(s) || !cov.allows(s) {
+                continue;
+            }
+            let after = values.iter()
+                .zip(&scenarios)
+                .map(|(v, w)| v + cov.gain_by(s, w))
+                .collect::<Vec<_>>();
+            let key = (after.iter().cloned().fold(f64::INFINITY, f64::min) - worst,
+                       after.iter().sum::<f64>() - total);
+            if (key.0 > 0.0 || key.1 > 0.0) && best.is_none_or(|(_, b)| key > b) {
+                best = Some((s, key));
+            }
+        }
+
+        match best {
+            Some((s, _)) => {
+                cov.add(s);
+                sol.push(s);
+                values = scenarios.iter().map(|w| cov.value_by(w)).collect();
+            }
+            None => break,
+        }
+    }
+
+    Solution::new(inst.worst_case(&sol), sol)
+}
+
+/// Overlap-penalized greedy: repeatedly selects the set whose gain in covered weight most
+/// exceeds `penalty` times the number of its elements that are already covered, until no
+/// set's does or `k` are selected. The solution's objective is `Instance::overlap_penalized`.
+pub fn overlap_greedy(inst: &Instance, k: usize, penalty: f64) -> Solution {
+    assert!(!inst.has_demands(), "the overlap penalty does not support demands");
+    let index = Index::new(inst);
+    let mut cov = Coverage::initial(&index);
+    let mut sol = cov.selection();
+
+    while sol.len() < k {
+        let mut best = None;
+        for s in 0..index.num_sets() {
+            if cov.contains(s) || !cov.allows(s) {
+                continue;
+            }
+            let repeated = index.sets[s].iter().filter(|&&e| cov.covered(e)).count();
+            let net = cov.gain(s) - penalty * repeated as f64;
+            if net > 0.0 && best.is_none_or(|(_, n)| net > n) {
+                best = Some((s, net));
+            }
+        }
+
+        match best {
+            Some((s, _)) => {
+                cov.add(s);
+                sol.push(s);
+            }
+            None => break,
+        }
+    }
+
+    Solution::new(inst.overlap_penalized(&sol, penalty), sol)
+}
+
+/// Greedy set cover (Chvatal, 1979): repeatedly selects the set that covers the most new
+/// elements per unit cost, until every element is covered. Achieves a `ln n`-approximation
+/// of the cheapest cover. The solution's objective is its cost.
+///
+/// With a `target` below 1 this is greedy partial cover, which instead weighs the new
+/// elements and stops once their total weight reaches `target` times the total weight. Fails
+/// if the sets cannot reach the target.
+pub fn greedy_set_cover(inst: &Instance, target: f64) -> Result<Solution, String> {
+    let index = Index::new(inst);
+    let costs = inst.costs();
+    let mut cov = Coverage::initial(&index);
+    let mut sol = cov.selection();
+    let full = target >= 1.0;
+    let required = target * index.weights.iter().sum::<f64>();
+    let mut uncovered = (0..index.num_elements).filter(|&e| !cov.covered(e)).count();
+
+    while if full { uncovered > 0 } else { cov.value() < required - 1e-9 } {
+        let mut best = None;
+        for s in 0..index.num_sets() {
+            if !cov.allows(s) {
+                continue;
+            }
+            // a full cover needs every element, whatever its weight
+            let new = if full {
+                index.sets[s].iter().filter(|&&e| !cov.covered(e)).count() as f64
+            } else {
+                cov.demand_gain(s)
+            };
+            let ratio = if costs[s] > 0.0 { new / costs[s] } else { f64::INFINITY };
+            if new > 0.0 && best.is_none_or(|(_, r)| ratio > r) {
+                best = Some((s, ratio));
+            }
+        }
+
+        match best {
+            Some((s, _)) => {
+                let open = index.sets[s].iter().filter(|&&e| !cov.covered(e)).collect::<Vec<_>>();
+                cov.add(s);
+                uncovered -= open.into_iter().filter(|&&e| cov.covered(e)).count();
+                sol.push(s);
+            }
+            None => break,
+        }
+    }
+
+    if full {
+        if let Some(e) = (0..index.num_elements).find(|&e| !cov.covered(e)) {
+            let x = inst.ground.iter().nth(e).unwrap();
+            return Err(format!("element {} is in too few sets to be covered", x));
+        }
+    } else if cov.value() < required - 1e-9 {
+        return Err(format!("the sets cover a weight of at most {}, short of the target {}",
+                           cov.value(),
+                           required));
+    }
+    sol.sort();
+    Ok(Solution::new(inst.cost(&sol), sol))
+}
+
+/// A set together with its marginal gain as of the given round. Ordered by gain, breaking
+/// ties in favour of the lower set index so that lazy greedy matches `greedy` exactly.
+#[derive(Debug, Clone, Copy)]
+struct Candidate {
+    gain: f64,
+    set: usize,
+    round: usize,
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Candidate) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Candidate) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Candidate) -> Ordering {
+        self.gain
+            .partial_cmp(&other.gain)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| other.set.cmp(&self.set))
+    }
+}
+
+/// Lazy greedy (CELF). Marginal gains only shrink as the selection grows, so a stale gain is
+/// an upper bound on the current one: a set whose gain is fresh for this round and still on
+/// top of the heap is the greedy choice, and every other gain need not be recomputed.
+pub fn lazy_greedy(inst: &Instance, k: usize) -> Solution {
+    let index = Index::new(inst);
+    lazy_greedy_over(&index, 0..index.num_sets(), k)
+}
+
+/// Lazy greedy restricted to the sets in `candidates`.
+pub fn lazy_greedy_over<I>(index: &Index, candidates: I, k: usize) -> Solution
+    where I: IntoIterator<Item = usize>
+{
+    lazy_greedy_fn(&mut CoverageFn::initial(index), candidates, k)
+}
+
+/// `lazy_greedy` for any submodular function, over the items in `candidates`: selects up to
+/// `k` items, starting from those `f` has selected already, and leaves `f` at the selection.
+pub fn lazy_greedy_fn<F, I>(f: &mut F, candidates: I, k: usize) -> Solution
+    where F: SubmodularFn + ?Sized,
+          I: IntoIterator<Item = usize>
+{
+    let mut sol = f.selection();
+
+    let mut heap = candidates.into_iter()
+        .filter(|&s| !f.contains(s))
+        .map(|s| {
+            Candidate {
+                gain: f.gain(s),
+                set: s,
+                round: sol.len(),
+            }
+        })
+        .collect::<BinaryHeap<_>>();
+
+    while sol.len() < k {
+        let top = match heap.pop() {
+            Some(top) => top,
+            None => break,
+        };
+
+        if top.gain <= 0.0 {
+            break;
+        }
+        // a set that is not allowed now never will be
+        if !f.allows(top.set) {
+            continue;
+        }
+
+        if top.round == sol.len() {
+            f.add(top.set);
+            sol.push(top.set);
+        } else {
+            heap.push(Candidate {
+                gain: f.gain(top.set),
+                set: top.set,
+                round: sol.len(),
+            });
+        }
+    }
+
+    Solution::new(f.value(), sol)
+}
+
+/// Stochastic greedy (Mirzasoleiman et al., 2015). Each round evaluates only a random sample of
+/// `(n / k) ln(1 / epsilon)` of the remaining sets, giving a (1 - 1/e - epsilon)-approximation
+/// in expectation with a number of gain evaluations linear in the number of sets.
+pub fn stochastic_greedy<R: Rng>(inst: &Instance, k: usize, epsilon: f64, rng: &mut R) -> Solution {
+    let index = Index::new(inst);
+    stochastic_greedy_fn(&mut CoverageFn::initial(&index), k, epsilon, rng)
+}
+
+/// `stochastic_greedy` for any submodular function: selects up to `k` items, starting from
+/// those `f` has selected already, and leaves `f` at the selection.
+pub fn stochastic_greedy_fn<F, R>(f: &mut F, k: usize, epsilon: f64, rng: &mut R) -> Solution
+    where F: SubmodularFn + ?Sized,
+          R: Rng
+{
+    assert!(epsilon > 0.0 && epsilon < 1.0, "epsilon must be in (0, 1)");
+    let n = f.num_items();
+    let mut sol = f.selection();
+    let mut remaining = (0..n).filter(|&s| !f.contains(s)).collect::<Vec<_>>();
+
+    if k == 0 {
+        return Solution::new(f.value(), sol);
+    }
+
+    let sample_size = ((n as f64 / k as f64) * (1.0 / epsilon).ln()).ceil() as usize;
+    let sample_size = sample_size.max(1);
+
+    while sol.len() < k && !remaining.is_empty() {
+        let len = remaining.len();
+        let m = sample_size.min(len);
+        let mut best: Option<(usize, f64)> = None;
+        // partial Fisher-Yates: the sample ends up in remaining[..m]
+        for i in 0..m {
+            let j = rng.gen_range(i, len);
+            remaining.swap(i, j);
+            if !f.allows(remaining[i]) {
+                continue;
+            }
+            let gain = f.gain(remaining[i]);
+            if best.is_none_or(|(_, g)| gain > g) {
+                best = Some((i, gain));
+            }
+        }
+
+        match best {
+            Some((i, gain)) if gain > 0.0 => {
+                let s = remaining.swap_remove(i);
+                f.add(s);
+                sol.push(s);
+            }
+            // gains never increase and disallowed sets stay so, so the sample can be dropped
+            _ => {
+                remaining.drain(..m);
+            }
+        }
+    }
+
+    Solution::new(f.value(), sol)
+}
+
+/// Threshold-decreasing greedy (Badanidiyuru and Vondrak, 2014). Sweeps the sets with a
+/// geometrically decreasing threshold `w`, starting at the largest set, and adds any set
+/// whose gain is still at least `w`. Gives a (1 - 1/e - epsilon)-approximation with
+/// O((n / epsilon) log(n / epsilon)) gain evaluations.
+pub fn threshold_greedy(inst: &Instance, k: usize, epsilon: f64) -> Solution {
+    assert!(epsilon > 0.0 && epsilon < 1.0, "epsilon must be in (0, 1)");
+    let index = Index::new(inst);
+    let mut cov = Coverage::initial(&index);
+    let mut sol = cov.selection();
+
+    let n = index.num_sets() as f64;
+    let d = (0..index.num_sets()).map(|s| cov.demand_gain(s)).fold(0.0, f64::max);
+    let mut w = d;
+
+    while w > 0.0 && w >= epsilon / n * d && sol.len() < k {
+        for s in 0..index.num_sets() {
+            if sol.len() >= k {
+                break;
+            }
+            if !cov.contains(s) && cov.allows(s) && cov.demand_gain(s) >= w {
+                cov.add(s);
+                sol.push(s);
+            }
+        }
+        w *= 1.0 - epsilon;
+    }
+
+    Solution::new(cov.value(), sol)
+}
+
+/// Reverse (deletion) greedy: starts from every set and repeatedly drops the set whose
+/// removal loses the least progress, by `Coverage::demand_loss`, until `k` remain. Removing
+/// a set can only increase the progress the others lose, so stale losses are lower bounds
+/// and the heap is evaluated lazily as in `lazy_greedy`.
+pub fn reverse_greedy(inst: &Instance, k: usize) -> Solution {
+    let index = Index::new(inst);
+    let all = (0..index.num_sets()).collect::<Vec<_>>();
+    let mut cov = Coverage::from_sets(&index, &all);
+
+    // candidates are ordered by gain, so store negated losses
+    let mut heap = all.iter()
+        .map(|&s| {
+            Candidate {
+                gain: -cov.demand_loss(s),
+                set: s,
+                round: 0,
+            }
+        })
+        .collect::<BinaryHeap<_>>();
+
+    let mut round = 0;
+    while cov.size() > k {
+        let top = heap.pop().unwrap();
+        if top.round == round {
+            cov.remove(top.set);
+            round += 1;
+        } else {
+            heap.push(Candidate {
+                gain: -cov.demand_loss(top.set),
+                set: top.set,
+                round: round,
+            });
+        }
+    }
+
+    Solution::new(cov.value(), cov.selection())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn instance(n: usize, sets: Vec<Vec<usize>>) -> Instance {
+        Instance::new((0..n).collect(),
+                      sets.into_iter().map(|s| s.into_iter().collect()).collect())
+    }
+
+    #[test]
+    fn greedy_takes_the_largest_gain_first() {
+        // greedy takes the middle set, after which the two halves add 2 each, though they
+        // cover everything together
+        let inst = instance(8, vec![vec![0, 1, 2, 3], vec![4, 5, 6, 7], vec![2, 3, 4, 5, 6]]);
+        let sol = greedy(&inst, 2);
+        assert_eq!(sol.sol, vec![2, 0]);
+        assert_eq!(sol.objective, 7.0);
+    }
+
+    #[test]
+    fn greedy_stops_without_gain() {
+        let inst = instance(3, vec![vec![0, 1, 2], vec![0, 1], vec![2]]);
+        let sol = greedy(&inst, 3);
+        assert_eq!(sol.sol, vec![0]);
+        assert_eq!(sol.objective, 3.0);
+    }
+
+    #[test]
+    fn candidates_with_nan_gains_compare() {
+        let nan = Candidate { gain: f64::NAN, set: 0, round: 0 };
+        let one = Candidate { gain: 1.0, set: 1, round: 0 };
+        assert_eq!(nan.cmp(&one), Ordering::Greater);
+        let mut heap = vec![nan, one].into_iter().collect::<BinaryHeap<_>>();
+        assert_eq!(heap.pop().map(|c| c.set), Some(0));
+    }
+
+    #[test]
+    fn lazy_greedy_matches_greedy() {
+        let inst = instance(8,
+                            vec![vec![0, 1, 2],
+                                 vec![2, 3],
+                                 vec![3, 4, 5],
+                                 vec![0, 5, 6, 7],
+                                 vec![1, 6],
+                                 vec![4, 7]]);
+        for k in 1..5 {
+            let lazy = lazy_greedy(&inst, k);
+            let plain = greedy(&inst, k);
+            assert_eq!(lazy.sol, plain.sol);
+            assert_eq!(lazy.objective, plain.objective);
+        }
+    }
+
+    #[test]
+    fn threshold_greedy_lowers_the_threshold_to_the_gains() {
+        let inst = instance(8, vec![vec![0, 1, 2, 3], vec![4, 5, 6, 7], vec![2, 3, 4, 5, 6]]);
+        let sol = threshold_greedy(&inst, 2, 0.1);
+        assert_eq!(sol.sol, vec![2, 0]);
+        assert_eq!(sol.objective, 7.0);
+        assert_eq!(threshold_greedy(&inst, 3, 0.1).objective, 8.0);
+    }
+
+    #[test]
+    fn reverse_greedy_drops_the_least_loss() {
+        let inst = instance(8, vec![vec![0, 1, 2, 3], vec![4, 5, 6, 7], vec![2, 3, 4, 5, 6]]);
+        let sol = reverse_greedy(&inst, 2);
+        assert_eq!(sol.sol, vec![0, 1]);
+        assert_eq!(sol.objective, 8.0);
+        assert_eq!(reverse_greedy(&inst, 3).sol, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn budgeted_greedy_falls_back_to_the_best_single_set() {
+        // the cheap set has the best ratio, but leaves no budget for the big one
+        let inst = Instance {
+            costs: Some(vec![1.0, 10.0]),
+            ..instance(10, vec![vec![0], (1..10).collect()])
+        };
+        let sol = budgeted_greedy(&inst, 2, 10.0);
+        assert_eq!(sol.sol, vec![1]);
+        assert_eq!(sol.objective, 9.0);
+        assert_eq!(budgeted_greedy(&inst, 2, 11.0).objective, 10.0);
+        assert!(budgeted_greedy(&inst, 2, 0.5).sol.is_empty());
+    }
+
+    #[test]
+    fn greedy_set_cover_covers_every_element() {
+        let inst = instance(5, vec![vec![0, 1, 2], vec![2, 3], vec![3, 4], vec![4]]);
+        let sol = greedy_set_cover(&inst, 1.0).unwrap();
+        assert_eq!(sol.sol, vec![0, 2]);
+        assert_eq!(sol.objective, 2.0);
+        let uncoverable = instance(6, vec![vec![0, 1, 2], vec![3, 4]]);
+        assert!(greedy_set_cover(&uncoverable, 1.0).is_err());
+    }
+
+    #[test]
+    fn partial_cover_stops_at_the_target() {
+        let inst = instance(6, vec![vec![0, 1, 2], vec![3, 4], vec![5]]);
+        assert_eq!(greedy_set_cover(&inst, 0.5).unwrap().sol, vec![0]);
+        assert_eq!(greedy_set_cover(&inst, 0.6).unwrap().sol, vec![0, 1]);
+        let short = instance(6, vec![vec![0, 1, 2]]);
+        assert!(greedy_set_cover(&short, 0.6).is_err());
+    }
+
+    #[test]
+    fn prize_collecting_greedy_skips_sets_worth_less_than_their_cost() {
+        let inst = Instance {
+            costs: Some(vec![1.0, 3.0]),
+            penalties: Some(vec![0.0, 0.0, 1.0]),
+            ..instance(3, vec![vec![0, 1], vec![2]])
+        };
+        let sol = prize_collecting_greedy(&inst, 2);
+        assert_eq!(sol.sol, vec![0]);
+        assert_eq!(sol.objective, inst.prize(&[0]));
+        assert_eq!(sol.objective, 0.0);
+    }
+
+    #[test]
+    fn robust_greedy_raises_the_worst_scenario() {
+        let inst = Instance {
+            scenarios: Some(vec![vec![1.0, 1.0, 0.0, 0.0], vec![0.0, 0.0, 1.0, 1.0]]),
+            ..instance(4, vec![vec![0, 1], vec![2, 3], vec![0, 2]])
+        };
+        assert_eq!(greedy(&inst, 1).sol, vec![0]);
+        let sol = robust_greedy(&inst, 1);
+        assert_eq!(sol.sol, vec![2]);
+        assert_eq!(sol.objective, 1.0);
+        assert_eq!(robust_greedy(&inst, 2).objective, 1.0);
+    }
+
+    #[test]
+    fn fair_greedy_reaches_the_shares_first() {
+        let inst = Instance {
+            labels: Some(vec![0, 0, 0, 1]),
+            min_coverage: Some(vec![0.0, 1.0]),
+            ..instance(4, vec![vec![0, 1, 2], vec![3]])
+        };
+        assert_eq!(fair_greedy(&inst, 1).unwrap().sol, vec![1]);
+        assert_eq!(fair_greedy(&inst, 2).unwrap().sol, vec![1, 0]);
+        let unfair = Instance { sets: vec![vec![0, 1, 2].into_iter().collect()], ..inst };
+        assert!(fair_greedy(&unfair, 1).is_err());
+    }
+
+    #[test]
+    fn overlap_greedy_avoids_covered_elements() {
+        let inst = instance(4, vec![vec![0, 1, 2], vec![2, 3], vec![3]]);
+        let sol = overlap_greedy(&inst, 3, 2.0);
+        assert_eq!(sol.sol, vec![0, 2]);
+        assert_eq!(sol.objective, 4.0);
+        assert_eq!(overlap_greedy(&inst, 2, 0.0).sol, vec![0, 1]);
+    }
+
+    #[test]
+    fn precedence_greedy_selects_sets_with_their_prerequisites() {
+        let inst = Instance {
+            prerequisites: Some(vec![(0, 1)]),
+            ..instance(5, vec![vec![0, 1, 2, 3], vec![4], vec![0, 1, 2]])
+        };
+        // set 0 does not fit with the set it needs
+        assert_eq!(precedence_greedy(&inst, 1).sol, vec![2]);
+        let sol = precedence_greedy(&inst, 3);
+        assert_eq!(sol.sol, vec![2, 0, 1]);
+        assert_eq!(sol.objective, 5.0);
+    }
+}
@@ -0,0 +1,70 @@
+use rand::{SeedableRng, StdRng};
+use {Instance, Solution};
+use interrupt::interrupted;
+use coverage::{Index, Coverage};
+use solvers::local::fill_random;
+
+/// Minimum improvement for a move to be accepted.
+const EPS: f64 = 1e-9;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Params {
+    pub restarts: usize,
+    /// Restart `r` draws its starting selection from an rng seeded with `[seed, r]`.
+    pub seed: usize,
+}
+
+/// The best allowed exchange of a selected set for an unselected one, as
+/// `(out, in, new value)`.
+fn best_swap(cov: &mut Coverage) -> Option<(usize, usize, f64)> {
+    let mut best = None;
+    for s in cov.removable_selection() {
+        cov.remove(s);
+        for t in 0..cov.index().num_sets() {
+            if t == s || cov.contains(t) || !cov.allows(t) {
+                continue;
+            }
+            let value = cov.value() + cov.gain(t);
+            if best.is_none_or(|(_, _, v)| value > v) {
+                best = Some((s, t, value));
+            }
+        }
+        cov.add(s);
+    }
+    best
+}
+
+/// Steepest-ascent hill climbing: applies the best 1-swap until none improves.
+fn climb(cov: &mut Coverage) {
+    while let Some((s, t, value)) = best_swap(cov) {
+        if value <= cov.value() + EPS {
+            break;
+        }
+        cov.remove(s);
+        cov.add(t);
+    }
+}
+
+/// Multi-start hill climbing from random selections, keeping the best local optimum. Each
+/// restart is seeded independently, so any single restart can be reproduced from `seed`.
+pub fn hillclimb(inst: &Instance, k: usize, params: &Params) -> Solution {
+    let index = Index::new(inst);
+    let mut best: Option<(f64, Vec<usize>)> = None;
+
+    for r in 0..params.restarts.max(1) {
+        let mut rng = StdRng::from_seed(&[params.seed, r][..]);
+        let mut cov = Coverage::initial(&index);
+        fill_random(&mut cov, k, &mut rng);
+        climb(&mut cov);
+        if best.as_ref().is_none_or(|&(v, _)| cov.value() > v) {
+            best = Some((cov.value(), cov.selection()));
+        }
+        // stop after a whole restart, so that there is a solution
+        if interrupted() {
+            break;
+        }
+    }
+
+    let (objective, sol) = best.unwrap();
+    Solution::new(objective, sol)
+}
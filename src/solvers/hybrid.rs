@@ -0,0 +1,29 @@
+use std::cmp::Ordering;
+use rplex::*;
+use {Instance, Solution};
+use cplex::{cplex_env, build_model, fix_sets, selected_sets};
+use coverage::{Index, Coverage};
+use solvers::greedy::lazy_greedy;
+
+/// Fix-and-optimize: runs lazy greedy, fixes all but `free` of its sets into the CPLEX model
+/// and lets the MIP choose the remaining `free` sets from the whole collection. The sets
+/// left free are those whose removal loses the least coverage.
+pub fn hybrid(inst: &Instance, k: usize, free: usize, threads: Option<usize>) -> Result<Solution, String> {
+    let greedy = lazy_greedy(inst, k);
+    let index = Index::new(inst);
+    let cov = Coverage::from_sets(&index, &greedy.sol);
+
+    let mut fixed = greedy.sol.clone();
+    fixed.sort_by(|&a, &b| cov.loss(b).partial_cmp(&cov.loss(a)).unwrap_or(Ordering::Equal));
+    let keep = fixed.len().saturating_sub(free);
+    fixed.truncate(keep);
+
+    let env = cplex_env(threads)?;
+    let mut prob = Problem::new(&env, "maxcover-hybrid")?;
+    let (_, set_vars) = build_model(&mut prob, inst, k, VariableType::Binary)?;
+    fix_sets(&mut prob, &set_vars, &fixed, 1.0)?;
+
+    let sol = prob.solve()?;
+    // the greedy solution is feasible for the restricted model, so this never does worse
+    Ok(Solution::new(sol.objective, selected_sets(&sol, &set_vars)))
+}
@@ -0,0 +1,190 @@
+use {Instance, Solution};
+use backend::{self, Model, Sense, SolverBackend, VarType};
+use coverage::{Index, Coverage};
+use solvers::greedy::{lazy_greedy, precedence_greedy};
+use solvers::master;
+
+/// The least coverage that `sets` keep after an adversary deletes up to `b` of them, along
+/// with the deleted sets. Deleting more never covers more, so every way of deleting
+/// `min(b, sets.len())` sets is tried.
+pub fn worst_attack(index: &Index, sets: &[usize], b: usize) -> (f64, Vec<usize>) {
+    let b = b.min(sets.len());
+    let mut deleted = (0..b).collect::<Vec<_>>();
+    let mut worst = (f64::INFINITY, Vec::new());
+    loop {
+        let kept = (0..sets.len())
+            .filter(|t| !deleted.contains(t))
+            .map(|t| sets[t])
+            .collect::<Vec<_>>();
+        let value = Coverage::from_sets(index, &kept).value();
+        if value < worst.0 {
+            worst = (value, deleted.iter().map(|&t| sets[t]).collect());
+        }
+
+        // the next combination in lexicographic order
+        let n = sets.len();
+        match (0..b).rev().find(|&t| deleted[t] < n - b + t) {
+            Some(t) => {
+                deleted[t] += 1;
+                for u in t + 1..b {
+                    deleted[u] = deleted[u - 1] + 1;
+                }
+            }
+            None => break,
+        }
+    }
+    worst
+}
+
+/// Adds attack `t`, which deletes `deleted`, to the master: a copy of the element variables
+/// that only the other selected sets may cover, and a row that bounds `z` by their weight.
+/// Returns the copy's variables, one per element.
+fn add_attack(model: &mut Model,
+              index: &Index,
+              t: usize,
+              z: usize,
+              set_vars: &[usize],
+              deleted: &[usize])
+              -> Vec<usize> {
+    let xs = (0..index.num_elements)
+        .map(|e| model.add_var(format!("x{}_{}", t, e), VarType::Binary, 0.0, 0.0, 1.0))
+        .collect::<Vec<_>>();
+    for e in 0..index.num_elements {
+        let mut terms = vec![(xs[e], index.demands[e] as f64)];
+        for &i in index.containing[e].iter().filter(|i| !deleted.contains(i)) {
+            terms.push((set_vars[i], -1.0));
+        }
+        model.add_row(format!("cover{}_{}", t, e), terms, Sense::LessEq, 0.0);
+    }
+    let mut terms = vec![(z, 1.0)];
+    terms.extend((0..index.num_elements)
+        .filter(|&e| index.weights[e] != 0.0)
+        .map(|e| (xs[e], -index.weights[e])));
+    model.add_row(format!("attack{}", t), terms, Sense::LessEq, 0.0);
+    xs
+}
+
+/// Max k-coverage under interdiction, by cutting planes: after at most `k` sets are selected,
+/// an adversary deletes up to `b` of them to cover least, and the selection maximizes what
+/// remains. The master selects the sets and bounds their worst case `z` by the coverage left
+/// after each attack found so far. Each master solution's best attack is found exactly by
+/// `worst_attack` and added, until it leaves as much as the master claims.
+///
+/// The solution's objective is the coverage its best attack leaves, with the deleted sets in
+/// `attack`. The master is warm started from lazy greedy, or precedence greedy if some set
+/// needs another, and from the best selection after that. Every master solve has the full
+/// time limit; the loop stops when one does not finish, at `params.gap`, or on an
+/// interrupt, with the best selection and the last bound.
+pub fn interdiction<B>(inst: &Instance,
+                       k: usize,
+                       b: usize,
+                       backend: &mut B,
+                       params: &backend::Params)
+                       -> Result<Solution, String>
+    where B: SolverBackend + ?Sized
+{
+    let index = Index::new(inst);
+    let greedy = if inst.has_prerequisites() {
+        precedence_greedy(inst, k)
+    } else {
+        lazy_greedy(inst, k)
+    };
+    let total = index.weights.iter().sum::<f64>();
+
+    let mut model = Model::new("interdiction", true);
+    let set_vars = (0..index.num_sets())
+        .map(|i| model.add_var(format!("s{}", i), VarType::Binary, 0.0, 0.0, 1.0))
+        .collect::<Vec<_>>();
+    let cardinality = set_vars.iter().map(|&j| (j, 1.0)).collect();
+    model.add_row("cardinality".to_string(), cardinality, Sense::LessEq, k as f64);
+    backend::add_selection_rows(&mut model, inst, &set_vars);
+    let selection_vars = set_vars.len()..model.vars.len();
+    let z = model.add_var("z".to_string(), VarType::Continuous, 1.0, 0.0, total);
+
+    let (value, attack) = worst_attack(&index, &greedy.sol, b);
+    let mut best = Solution::new(value, greedy.sol);
+    best.attack = Some(attack.clone());
+    let xs = add_attack(&mut model, &index, 0, z, &set_vars, &attack);
+    let mut attacks = vec![(attack, xs)];
+
+    params.apply(backend)?;
+    let mut bound = total;
+    loop {
+        let mut values = master::start(inst,
+                                       model.vars.len(),
+                                       &set_vars,
+                                       selection_vars.clone(),
+                                       &best.sol);
+        let mut worst = total;
+        for (deleted, xs) in &attacks {
+            let kept = best.sol
+                .iter()
+                .cloned()
+                .filter(|i| !deleted.contains(i))
+                .collect::<Vec<_>>();
+            let cov = Coverage::from_sets(&index, &kept);
+            for e in (0..index.num_elements).filter(|&e| cov.covered(e)) {
+                values[xs[e]] = 1.0;
+            }
+            worst = worst.min(cov.value());
+        }
+        values[z] = worst;
+        model.start = Some(values);
+
+        backend.build_model(model.clone())?;
+        backend.solve()?;
+        let sol = backend.extract_solution()?;
+        let selected = (0..set_vars.len())
+            .filter(|&i| sol.values[set_vars[i]] > 0.5)
+            .collect::<Vec<_>>();
+        let (value, attack) = worst_attack(&index, &selected, b);
+        if value > best.objective {
+            best = Solution::new(value, selected);
+            best.attack = Some(attack.clone());
+        }
+        if let Some(bound_found) = master::bound(&sol, params) {
+            bound = bound.min(bound_found);
+        }
+        if master::should_stop(best.objective, bound, &sol, params) {
+            break;
+        }
+        let xs = add_attack(&mut model, &index, attacks.len(), z, &set_vars, &attack);
+        attacks.push((attack, xs));
+    }
+
+    master::finish(&mut best, bound);
+    Ok(best)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn worst_attack_finds_the_least_coverage_of_any_deletion() {
+        let sets = vec![vec![0, 1, 2], vec![2, 3], vec![4], vec![0, 4]];
+        let inst = Instance::new((0..5).collect(),
+                                 sets.into_iter().map(|s| s.into_iter().collect()).collect());
+        let index = Index::new(&inst);
+        let selected = vec![0, 1, 2, 3];
+        for b in 0..selected.len() + 1 {
+            let mut least = f64::INFINITY;
+            for mask in 0..1usize << selected.len() {
+                if mask.count_ones() as usize > b {
+                    continue;
+                }
+                let kept = (0..selected.len())
+                    .filter(|&t| mask & 1 << t == 0)
+                    .map(|t| selected[t])
+                    .collect::<Vec<_>>();
+                least = least.min(Coverage::from_sets(&index, &kept).value());
+            }
+
+            let (value, deleted) = worst_attack(&index, &selected, b);
+            assert_eq!(value, least);
+            assert_eq!(deleted.len(), b);
+            let kept = selected.iter().cloned().filter(|i| !deleted.contains(i));
+            assert_eq!(Coverage::from_sets(&index, &kept.collect::<Vec<_>>()).value(), value);
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use std::cmp::Ordering;
+use rplex::*;
+use {Instance, Solution};
+use cplex::{cplex_env_with, build_model, fix_sets, selected_sets};
+use solvers::lp::relax;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Params {
+    /// Number of sets in each bucket.
+    pub bucket: usize,
+    /// CPLEX time limit in seconds for each restricted MIP.
+    pub time_limit: f64,
+}
+
+/// Solves the MIP restricted to the sets marked in `allowed`.
+fn restricted(inst: &Instance,
+              k: usize,
+              allowed: &[bool],
+              threads: Option<usize>,
+              cplex_params: &[(String, String)])
+              -> Result<Solution, String> {
+    let env = cplex_env_with(threads, cplex_params)?;
+    let mut prob = Problem::new(&env, "maxcover-kernel")?;
+    let (_, set_vars) = build_model(&mut prob, inst, k, VariableType::Binary)?;
+    let excluded = (0..allowed.len()).filter(|&i| !allowed[i]).collect::<Vec<_>>();
+    fix_sets(&mut prob, &set_vars, &excluded, 0.0)?;
+
+    let sol = prob.solve()?;
+    Ok(Solution::new(sol.objective, selected_sets(&sol, &set_vars)))
+}
+
+/// Kernel search (Angelelli et al., 2010). Solves the LP relaxation, takes the sets with
+/// positive LP value (at least `k` of them) as the kernel and splits the others, ordered by LP
+/// value and then size, into buckets. A restricted MIP is solved over the kernel and then over
+/// the kernel plus each bucket in turn; bucket sets used by an improving solution join the
+/// kernel. The incumbent stays feasible in every restricted MIP, so coverage never drops.
+pub fn kernel(inst: &Instance, k: usize, params: &Params, threads: Option<usize>) -> Result<Solution, String> {
+    let (relaxation, x) = relax(inst, k, threads)?;
+    let mut order = (0..x.len()).collect::<Vec<_>>();
+    order.sort_by(|&a, &b| {
+        x[b].partial_cmp(&x[a])
+            .unwrap_or(Ordering::Equal)
+            .then(inst.sets[b].len().cmp(&inst.sets[a].len()))
+    });
+
+    let kernel_size = order.iter().take_while(|&&i| x[i] > 1e-6).count().max(k).min(order.len());
+    let mut allowed = vec![false; x.len()];
+    for &i in &order[..kernel_size] {
+        allowed[i] = true;
+    }
+
+    let cplex_params = vec![("CPXPARAM_TimeLimit".to_string(), params.time_limit.to_string())];
+    let mut best = restricted(inst, k, &allowed, threads, &cplex_params)?;
+
+    for bucket in order[kernel_size..].chunks(params.bucket.max(1)) {
+        for &i in bucket {
+            allowed[i] = true;
+        }
+        let sol = restricted(inst, k, &allowed, threads, &cplex_params)?;
+        // bucket sets stay in the kernel only if an improving solution uses them
+        for &i in bucket {
+            allowed[i] = sol.objective > best.objective && sol.sol.binary_search(&i).is_ok();
+        }
+        if sol.objective > best.objective {
+            best = sol;
+        }
+    }
+
+    best.relaxation = Some(relaxation);
+    Ok(best)
+}
@@ -0,0 +1,155 @@
+use std::cmp::Ordering;
+use {Instance, Solution};
+use interrupt::interrupted;
+use coverage::{Index, Coverage};
+use solvers::greedy::lazy_greedy;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Params {
+    pub iterations: usize,
+    /// Initial Polyak step scale; halved whenever the bound stalls for `patience` iterations.
+    pub theta: f64,
+    pub patience: usize,
+}
+
+impl Default for Params {
+    fn default() -> Params {
+        Params {
+            iterations: 1000,
+            theta: 2.0,
+            patience: 20,
+        }
+    }
+}
+
+/// Lagrangian relaxation solved by subgradient optimization.
+///
+/// Dualizing the cardinality constraint alone leaves a prize-collecting coverage problem,
+/// which is as hard as the original, so this dualizes the coverage constraints
+/// `r_e y_e <= sum_{i : e in S_i} x_i`, for the demand `r_e` of each element, with
+/// multipliers `u_e >= 0` instead and keeps the cardinality constraint. The subproblem then
+/// separates: `y_e = 1` iff `r_e u_e` is below the element's weight `w_e`, and `x` picks
+/// the (at most) `k` sets with the largest positive `sum_{e in S_i} u_e`. Its value is an
+/// upper bound on the optimum, and the chosen sets double as a primal solution.
+///
+/// The returned solution is the best primal found (starting from lazy greedy) with the best
+/// Lagrangian bound in `bound`.
+pub fn lagrangian(inst: &Instance, k: usize, params: &Params) -> Solution {
+    multipliers(inst, k, params).0
+}
+
+/// Runs `lagrangian`, also returning the multipliers that gave the best bound, one per
+/// element of the ground set in order. They price the elements like the duals of the
+/// coverage constraints.
+pub fn multipliers(inst: &Instance, k: usize, params: &Params) -> (Solution, Vec<f64>) {
+    let index = Index::new(inst);
+    let greedy = lazy_greedy(inst, k);
+    let mut best = (greedy.objective, greedy.sol);
+    let mut bound = index.weights.iter().sum::<f64>();
+
+    let demand = index.demands.iter().map(|&r| r as f64).collect::<Vec<_>>();
+    let mut u = vec![0.0; index.num_elements];
+    let mut best_u = u.clone();
+    let mut theta = params.theta;
+    let mut stalled = 0;
+    let mut order = (0..index.num_sets()).collect::<Vec<_>>();
+
+    for _ in 0..params.iterations {
+        if interrupted() {
+            break;
+        }
+        let reduced = index.sets
+            .iter()
+            .map(|set| set.iter().map(|&e| u[e]).sum::<f64>())
+            .collect::<Vec<f64>>();
+        order.sort_by(|&a, &b| reduced[b].partial_cmp(&reduced[a]).unwrap_or(Ordering::Equal));
+        let chosen = order.iter()
+            .cloned()
+            .take(k)
+            .filter(|&s| reduced[s] > 0.0)
+            .collect::<Vec<_>>();
+
+        let value = (0..u.len())
+            .map(|e| (index.weights[e] - demand[e] * u[e]).max(0.0))
+            .sum::<f64>() +
+                    chosen.iter().map(|&s| reduced[s]).sum::<f64>();
+        if value < bound {
+            bound = value;
+            best_u = u.clone();
+            stalled = 0;
+        } else {
+            stalled += 1;
+            if stalled >= params.patience {
+                theta /= 2.0;
+                stalled = 0;
+            }
+        }
+
+        let cov = Coverage::from_sets(&index, &chosen);
+        if cov.value() > best.0 {
+            best = (cov.value(), cov.selection());
+        }
+
+        if bound - best.0 < 1e-6 || theta < 1e-6 {
+            break;
+        }
+
+        // subgradient of the dualized constraints: r_e y_e - (number of chosen sets covering e)
+        let mut h = (0..u.len())
+            .map(|e| if demand[e] * u[e] < index.weights[e] { demand[e] } else { 0.0 })
+            .collect::<Vec<f64>>();
+        for &s in &chosen {
+            for &e in &index.sets[s] {
+                h[e] -= 1.0;
+            }
+        }
+        let norm = h.iter().map(|&he| he * he).sum::<f64>();
+        if norm == 0.0 {
+            break;
+        }
+
+        let step = theta * (value - best.0) / norm;
+        for e in 0..u.len() {
+            u[e] = (u[e] + step * h[e]).max(0.0);
+        }
+    }
+
+    let mut sol = Solution::new(best.0, best.1);
+    sol.bound = Some(bound);
+    (sol, best_u)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solvers::bnb::bnb;
+
+    fn instance(n: usize, sets: Vec<Vec<usize>>) -> Instance {
+        Instance::new((0..n).collect(),
+                      sets.into_iter().map(|s| s.into_iter().collect()).collect())
+    }
+
+    #[test]
+    fn bound_holds_for_the_optimum() {
+        let mut state = 2718u64;
+        let mut next = |n: u64| {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (state >> 33) % n
+        };
+        for _ in 0..10 {
+            let sets = (0..10)
+                .map(|_| (0..12).filter(|_| next(4) == 0).map(|x| x as usize).collect())
+                .collect();
+            let inst = instance(12, sets);
+            for k in 1..4 {
+                let (sol, u) = multipliers(&inst, k, &Params::default());
+                let optimum = bnb(&inst, k).objective;
+                assert!(sol.objective <= optimum);
+                assert!(sol.bound.unwrap() >= optimum - 1e-6);
+                assert_eq!(sol.objective,
+                           Coverage::from_sets(&Index::new(&inst), &sol.sol).value());
+                assert_eq!(u.len(), 12);
+            }
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use rand::Rng;
+use rplex::*;
+use {Instance, Solution};
+use interrupt::interrupted;
+use cplex::{cplex_env_with, build_model, fix_sets, selected_sets};
+use solvers::greedy::lazy_greedy;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Params {
+    pub iterations: usize,
+    /// Fraction of the incumbent's sets released in each destroy step.
+    pub destroy: f64,
+    /// CPLEX time limit in seconds for each repair.
+    pub repair_time: f64,
+}
+
+/// Large neighbourhood search with CPLEX as the repair operator. Starting from lazy greedy,
+/// each iteration frees a random `destroy` fraction of the incumbent's sets, fixes the rest
+/// to 1 and re-solves the restricted MIP under a short time limit, keeping improvements.
+pub fn lns<R: Rng>(inst: &Instance,
+                   k: usize,
+                   params: &Params,
+                   threads: Option<usize>,
+                   rng: &mut R)
+                   -> Result<Solution, String> {
+    let mut best = lazy_greedy(inst, k);
+    if best.sol.len() < k {
+        // greedy stopped early because every coverable element is covered
+        return Ok(best);
+    }
+
+    let cplex_params = vec![("CPXPARAM_TimeLimit".to_string(), params.repair_time.to_string())];
+    let release = ((params.destroy * k as f64).ceil() as usize).max(1).min(k);
+
+    for _ in 0..params.iterations {
+        if interrupted() {
+            break;
+        }
+        let mut fixed = best.sol.clone();
+        rng.shuffle(&mut fixed);
+        fixed.truncate(k - release);
+
+        let env = cplex_env_with(threads, &cplex_params)?;
+        let mut prob = Problem::new(&env, "maxcover-lns")?;
+        let (_, set_vars) = build_model(&mut prob, inst, k, VariableType::Binary)?;
+        fix_sets(&mut prob, &set_vars, &fixed, 1.0)?;
+
+        let sol = prob.solve()?;
+        if sol.objective > best.objective {
+            best = Solution::new(sol.objective, selected_sets(&sol, &set_vars));
+        }
+    }
+
+    Ok(best)
+}
@@ -0,0 +1,132 @@
+use rand::Rng;
+use {Instance, Solution};
+use backend;
+use coverage::{Index, Coverage};
+
+/// Minimum improvement for a move to be accepted.
+const EPS: f64 = 1e-9;
+
+/// The best unselected set to add that the selection allows, and its gain.
+fn best_addition(cov: &Coverage) -> Option<(usize, f64)> {
+    let mut best = None;
+    for s in 0..cov.index().num_sets() {
+        if cov.contains(s) || !cov.allows(s) {
+            continue;
+        }
+        let gain = cov.gain(s);
+        if best.is_none_or(|(_, g)| gain > g) {
+            best = Some((s, gain));
+        }
+    }
+    best
+}
+
+/// Tops up the selection with random unselected sets until it holds `k` sets, or no more
+/// are allowed.
+pub fn fill_random<R: Rng>(cov: &mut Coverage, k: usize, rng: &mut R) {
+    let mut unselected = (0..cov.index().num_sets()).filter(|&s| !cov.contains(s)).collect::<Vec<_>>();
+    rng.shuffle(&mut unselected);
+    for s in unselected {
+        if cov.size() >= k {
+            break;
+        }
+        if cov.allows(s) {
+            cov.add(s);
+        }
+    }
+}
+
+/// Tries to replace one selected set with an unselected one. Applies the first improving
+/// exchange found and returns whether one was found.
+fn one_swap(cov: &mut Coverage) -> bool {
+    let current = cov.value();
+    for s in cov.removable_selection() {
+        cov.remove(s);
+        if let Some((t, gain)) = best_addition(cov) {
+            if cov.value() + gain > current + EPS {
+                cov.add(t);
+                return true;
+            }
+        }
+        cov.add(s);
+    }
+    false
+}
+
+/// Tries to replace two selected sets with two unselected ones. The replacement pair is
+/// chosen greedily, which keeps each pass at O(k^2 n) gain evaluations instead of O(k^2 n^2).
+fn two_swap(cov: &mut Coverage) -> bool {
+    let current = cov.value();
+    let selection = cov.removable_selection();
+    for (i, &s1) in selection.iter().enumerate() {
+        for &s2 in &selection[i + 1..] {
+            cov.remove(s1);
+            cov.remove(s2);
+            let mut added = Vec::with_capacity(2);
+            for _ in 0..2 {
+                if let Some((t, _)) = best_addition(cov) {
+                    cov.add(t);
+                    added.push(t);
+                }
+            }
+            if cov.value() > current + EPS {
+                return true;
+            }
+            for t in added {
+                cov.remove(t);
+            }
+            cov.add(s1);
+            cov.add(s2);
+        }
+    }
+    false
+}
+
+/// Improves a selection with 1-swap and 2-swap exchanges until it is locally optimal. The
+/// number of selected sets never changes.
+pub fn swap_search(cov: &mut Coverage) {
+    while one_swap(cov) || two_swap(cov) {}
+}
+
+/// Polishes `sol` with `swap_search`. Only the bound of `sol` is kept, as it still holds.
+pub fn swap(inst: &Instance, sol: &Solution) -> Solution {
+    let index = Index::new(inst);
+    let mut cov = Coverage::from_sets(&index, &sol.sol);
+    swap_search(&mut cov);
+
+    let mut out = Solution::new(cov.value(), cov.selection());
+    out.bound = sol.bound;
+    out.gap = sol.bound.map(|b| backend::relative_gap(out.objective, b));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn instance(n: usize, sets: Vec<Vec<usize>>) -> Instance {
+        Instance::new((0..n).collect(),
+                      sets.into_iter().map(|s| s.into_iter().collect()).collect())
+    }
+
+    #[test]
+    fn swap_improves_greedy() {
+        let inst = instance(8, vec![vec![0, 1, 2, 3], vec![4, 5, 6, 7], vec![2, 3, 4, 5, 6]]);
+        let mut sol = Solution::new(7.0, vec![2, 0]);
+        sol.bound = Some(8.0);
+        let polished = swap(&inst, &sol);
+        assert_eq!(polished.sol, vec![0, 1]);
+        assert_eq!(polished.objective, 8.0);
+        assert_eq!(polished.gap, Some(0.0));
+    }
+
+    #[test]
+    fn swap_keeps_required_sets() {
+        let inst = Instance {
+            required: Some(vec![2]),
+            ..instance(8, vec![vec![0, 1, 2, 3], vec![4, 5, 6, 7], vec![2, 3, 4, 5, 6]])
+        };
+        let polished = swap(&inst, &Solution::new(7.0, vec![0, 2]));
+        assert_eq!(polished.sol, vec![0, 2]);
+    }
+}
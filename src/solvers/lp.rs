@@ -0,0 +1,213 @@
+use rand::Rng;
+#[cfg(feature = "cplex")]
+use rplex::*;
+use {Instance, Solution};
+#[cfg(feature = "cplex")]
+use cplex::{cplex_env, build_model};
+use coverage::{Index, Coverage};
+
+/// Values within this distance of 0 or 1 are treated as integral.
+const TOL: f64 = 1e-6;
+
+/// Solves the LP relaxation of the max k-coverage model, returning its objective and the
+/// fractional value of each set variable.
+#[cfg(feature = "cplex")]
+pub fn relax(inst: &Instance, k: usize, threads: Option<usize>) -> Result<(f64, Vec<f64>), String> {
+    let env = cplex_env(threads)?;
+    let mut prob = Problem::new(&env, "maxcover-lp")?;
+    let (_, set_vars) = build_model(&mut prob, inst, k, VariableType::Continuous)?;
+
+    let sol = prob.solve_as(ProblemType::Linear)?;
+    let x = set_vars.iter()
+        .map(|&var| match sol.variables[var] {
+            VariableValue::Continuous(v) => v.clamp(0.0, 1.0),
+            ref v => panic!("unexpected value for relaxed variable: {:?}", v),
+        })
+        .collect();
+
+    Ok((sol.objective, x))
+}
+
+fn is_fractional(v: f64) -> bool {
+    v > TOL && v < 1.0 - TOL
+}
+
+/// Probability that element `e` is covered when each set `i` is picked independently with
+/// probability `x[i]`.
+fn covered(index: &Index, x: &[f64], e: usize) -> f64 {
+    1.0 - index.containing[e].iter().fold(1.0, |p, &i| p * (1.0 - x[i]))
+}
+
+/// The part of `F(x) = sum_e w_e covered(e)` that depends on sets `i` and `j`. Demands are
+/// ignored, so with them the rounding is only a heuristic.
+fn local_value(index: &Index, x: &[f64], i: usize, j: usize) -> f64 {
+    let i_only = index.sets[i]
+        .iter()
+        .map(|&e| index.weights[e] * covered(index, x, e))
+        .sum::<f64>();
+    let j_only = index.sets[j]
+        .iter()
+        .filter(|e| index.sets[i].binary_search(e).is_err())
+        .map(|&e| index.weights[e] * covered(index, x, e))
+        .sum::<f64>();
+    i_only + j_only
+}
+
+/// Pipage rounding (Ageev and Sviridenko, 2004). While two variables are fractional, moves
+/// along the direction that keeps their sum fixed until one of them is integral, picking the
+/// endpoint that does not decrease `F`. `F` is convex along that direction, so one of the two
+/// endpoints always qualifies, and the rounded coverage is at least (1 - 1/e) times the LP
+/// value.
+pub fn pipage(inst: &Instance, k: usize, x: &[f64]) -> Solution {
+    let index = Index::new(inst);
+    let mut x = x.iter().map(|&v| if is_fractional(v) { v } else { v.round() }).collect::<Vec<_>>();
+
+    loop {
+        let mut frac = (0..x.len()).filter(|&i| is_fractional(x[i]));
+        let (i, j) = match (frac.next(), frac.next()) {
+            (Some(i), Some(j)) => (i, j),
+            _ => break,
+        };
+
+        let (xi, xj) = (x[i], x[j]);
+        let up = (1.0 - xi).min(xj);
+        let down = xi.min(1.0 - xj);
+
+        x[i] = xi + up;
+        x[j] = xj - up;
+        let value_up = local_value(&index, &x, i, j);
+
+        x[i] = xi - down;
+        x[j] = xj + down;
+        let value_down = local_value(&index, &x, i, j);
+
+        if value_up >= value_down {
+            x[i] = xi + up;
+            x[j] = xj - up;
+        }
+        for &l in &[i, j] {
+            if !is_fractional(x[l]) {
+                x[l] = x[l].round();
+            }
+        }
+    }
+
+    // at most one fractional variable is left; rounding it up never hurts coverage as long
+    // as the cardinality constraint allows it
+    let ones = x.iter().filter(|&&v| v == 1.0).count();
+    let sol = (0..x.len())
+        .filter(|&i| x[i] == 1.0 || (is_fractional(x[i]) && ones < k))
+        .collect::<Vec<_>>();
+
+    let objective = Coverage::from_sets(&index, &sol).value();
+    Solution::new(objective, sol)
+}
+
+/// Solves the LP relaxation and rounds it with `pipage`.
+#[cfg(feature = "cplex")]
+pub fn lp_pipage(inst: &Instance, k: usize, threads: Option<usize>) -> Result<Solution, String> {
+    let (relaxation, x) = relax(inst, k, threads)?;
+    let mut sol = pipage(inst, k, &x);
+    sol.relaxation = Some(relaxation);
+    Ok(sol)
+}
+
+/// Brings a selection to at most `k` sets by repeatedly dropping the set whose removal loses
+/// the least coverage, then spends any remaining slots greedily.
+fn repair(cov: &mut Coverage, k: usize) {
+    let mut selection = cov.selection();
+    while selection.len() > k {
+        let mut worst = (0, cov.loss(selection[0]));
+        for i in 1..selection.len() {
+            let loss = cov.loss(selection[i]);
+            if loss < worst.1 {
+                worst = (i, loss);
+            }
+        }
+        cov.remove(selection.swap_remove(worst.0));
+    }
+
+    while selection.len() < k {
+        let mut best: Option<(usize, f64)> = None;
+        for s in 0..cov.index().num_sets() {
+            let gain = cov.gain(s);
+            if !cov.contains(s) && gain > 0.0 && best.is_none_or(|(_, g)| gain > g) {
+                best = Some((s, gain));
+            }
+        }
+        match best {
+            Some((s, _)) => {
+                cov.add(s);
+                selection.push(s);
+            }
+            None => break,
+        }
+    }
+}
+
+/// Independent randomized rounding: each trial selects set `i` with probability `x[i]`,
+/// is repaired to satisfy the cardinality constraint, and the best trial is kept.
+pub fn randomized_rounding<R: Rng>(inst: &Instance,
+                                   k: usize,
+                                   x: &[f64],
+                                   trials: usize,
+                                   rng: &mut R)
+                                   -> Solution {
+    let index = Index::new(inst);
+    let mut best: Option<(f64, Vec<usize>)> = None;
+
+    for _ in 0..trials.max(1) {
+        let picked = (0..x.len()).filter(|&i| rng.gen::<f64>() < x[i]).collect::<Vec<_>>();
+        let mut cov = Coverage::from_sets(&index, &picked);
+        repair(&mut cov, k);
+        if best.as_ref().is_none_or(|&(v, _)| cov.value() > v) {
+            best = Some((cov.value(), cov.selection()));
+        }
+    }
+
+    let (objective, sol) = best.unwrap();
+    Solution::new(objective, sol)
+}
+
+/// Solves the LP relaxation once and keeps the best of `trials` randomized roundings.
+#[cfg(feature = "cplex")]
+pub fn lp_round<R: Rng>(inst: &Instance,
+                        k: usize,
+                        trials: usize,
+                        threads: Option<usize>,
+                        rng: &mut R)
+                        -> Result<Solution, String> {
+    let (relaxation, x) = relax(inst, k, threads)?;
+    let mut sol = randomized_rounding(inst, k, &x, trials, rng);
+    sol.relaxation = Some(relaxation);
+    Ok(sol)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A cycle of four elements, each set covering two neighbours.
+    fn cycle() -> Instance {
+        let sets = vec![vec![0, 1], vec![1, 2], vec![2, 3], vec![3, 0]];
+        Instance::new((0..4).collect(),
+                      sets.into_iter().map(|s| s.into_iter().collect()).collect())
+    }
+
+    #[test]
+    fn pipage_keeps_integral_solutions() {
+        let sol = pipage(&cycle(), 2, &[1.0, 0.0, 1.0, 0.0]);
+        assert_eq!(sol.sol, vec![0, 2]);
+        assert_eq!(sol.objective, 4.0);
+    }
+
+    #[test]
+    fn pipage_rounds_without_losing_coverage() {
+        // every element is in two sets at 1/2, so F is 4 * (1 - 1/4)
+        let sol = pipage(&cycle(), 2, &[0.5, 0.5, 0.5, 0.5]);
+        assert_eq!(sol.sol.len(), 2);
+        assert!(sol.objective >= 3.0);
+        let index = Index::new(&cycle());
+        assert_eq!(Coverage::from_sets(&index, &sol.sol).value(), sol.objective);
+    }
+}
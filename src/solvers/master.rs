@@ -0,0 +1,59 @@
+//! The steps shared by the cutting-plane loops of `benders` and `interdiction`, which solve
+//! a master MIP, cut off its solution and solve it again until its bound meets the best
+//! selection found.
+
+use std::ops::Range;
+use {Instance, Solution};
+use interrupt::interrupted;
+use backend::{self, MipSolution, Params};
+
+/// Slack below which the master's estimate counts as exact.
+const EPS: f64 = 1e-6;
+
+/// A start for the master with `num_vars` variables that selects `sets`, whose set variables
+/// are `set_vars` and whose `selection_values` are `selection_vars`. Every other variable is
+/// 0, for the caller to fill in.
+pub fn start(inst: &Instance,
+             num_vars: usize,
+             set_vars: &[usize],
+             selection_vars: Range<usize>,
+             sets: &[usize])
+             -> Vec<f64> {
+    let mut values = vec![0.0; num_vars];
+    for &i in sets {
+        values[set_vars[i]] = 1.0;
+    }
+    for (j, value) in selection_vars.zip(backend::selection_values(inst, sets)) {
+        values[j] = value;
+    }
+    values
+}
+
+/// The bound that the master's solution `sol` proves: its objective if the solver proved it
+/// optimal, and otherwise the solver's bound, if it reports one. With a gap tolerance the
+/// master's objective is not a bound.
+pub fn bound(sol: &MipSolution, params: &Params) -> Option<f64> {
+    if sol.optimal && params.gap.is_none() && params.abs_gap.is_none() {
+        Some(sol.objective)
+    } else {
+        sol.bound
+    }
+}
+
+/// Whether the loop stops with the best selection so far worth `objective` and the bound
+/// `bound`, after the master's solution `sol`: once they meet, within the gap tolerances, or
+/// if the master stopped early or the solve is interrupted.
+pub fn should_stop(objective: f64, bound: f64, sol: &MipSolution, params: &Params) -> bool {
+    let gap = backend::relative_gap(objective, bound);
+    objective >= bound - EPS || !sol.optimal || interrupted() ||
+    params.gap.is_some_and(|g| gap <= g) ||
+    params.abs_gap.is_some_and(|g| bound - objective <= g)
+}
+
+/// Sets the bound and gap of the loop's best selection `best` from the last `bound`, which
+/// is never below it.
+pub fn finish(best: &mut Solution, bound: f64) {
+    let bound = bound.max(best.objective);
+    best.bound = Some(bound);
+    best.gap = Some(backend::relative_gap(best.objective, bound));
+}
@@ -0,0 +1,41 @@
+use std::env;
+use std::fs::{self, File};
+use std::io::BufWriter;
+use std::process::{self, Command};
+use {Instance, Solution};
+use coverage::{Index, Coverage};
+use maxsat::{Encoding, parse_output};
+
+/// Solves `inst` by handing its WCNF encoding to an external MaxSAT solver. `solver_cmd` is
+/// split on whitespace and the instance file is appended as the last argument.
+pub fn maxsat(inst: &Instance, k: usize, solver_cmd: &str) -> Result<Solution, String> {
+    if inst.has_demands() {
+        return Err("maxsat does not support demands".to_string());
+    }
+    let enc = Encoding::new(inst, k);
+    let path = env::temp_dir().join(format!("maxcover-{}.wcnf", process::id()));
+    {
+        let mut f = BufWriter::new(File::create(&path).map_err(|e| e.to_string())?);
+        enc.write_wcnf(&mut f).map_err(|e| e.to_string())?;
+    }
+
+    let mut words = solver_cmd.split_whitespace();
+    let program = words.next().ok_or_else(|| "empty solver command".to_string())?;
+    let output = Command::new(program)
+        .args(words.collect::<Vec<_>>())
+        .arg(&path)
+        .output();
+    let _ = fs::remove_file(&path);
+    let output = output.map_err(|e| format!("failed to run {}: {}", program, e))?;
+
+    let model = parse_output(&String::from_utf8_lossy(&output.stdout), enc.num_vars)?;
+    let index = Index::new(inst);
+    let sets = (0..index.num_sets()).filter(|&i| model.values[i]).collect::<Vec<_>>();
+    let cov = Coverage::from_sets(&index, &sets);
+
+    let mut sol = Solution::new(cov.value(), sets);
+    if model.optimal {
+        sol.bound = Some(cov.value());
+    }
+    Ok(sol)
+}
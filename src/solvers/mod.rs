@@ -0,0 +1,155 @@
+use std::str::FromStr;
+use Instance;
+
+pub mod aco;
+pub mod anneal;
+pub mod beam;
+pub mod benders;
+pub mod bnb;
+pub mod capacitated;
+pub mod cem;
+pub mod colgen;
+pub mod continuous;
+pub mod enumerate;
+pub mod ga;
+pub mod generalized;
+pub mod grasp;
+pub mod greedi;
+pub mod greedy;
+pub mod hillclimb;
+#[cfg(feature = "cplex")]
+pub mod hybrid;
+pub mod interdiction;
+#[cfg(feature = "cplex")]
+pub mod kernel;
+pub mod lagrangian;
+pub mod local;
+#[cfg(feature = "cplex")]
+pub mod lns;
+pub mod lp;
+pub mod master;
+pub mod maxsat;
+pub mod pareto;
+pub mod portfolio;
+pub mod probabilistic;
+pub mod stochastic;
+pub mod sweep;
+pub mod tabu;
+pub mod temporal;
+pub mod variant;
+
+/// Solution method for `cover solve`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    Ilp,
+    Greedy,
+    LazyGreedy,
+    StochasticGreedy,
+    Anneal,
+    Genetic,
+    Tabu,
+    Grasp,
+    LpPipage,
+    LpRound,
+    Lagrangian,
+    BranchAndBound,
+    ExactSmallK,
+    MaxSat,
+    Portfolio,
+    Auto,
+    Hybrid,
+    Lns,
+    Kernel,
+    Beam,
+    ThresholdGreedy,
+    Greedi,
+    ReverseGreedy,
+    AntColony,
+    CrossEntropy,
+    HillClimb,
+    ContinuousGreedy,
+    Benders,
+    ColumnGeneration,
+}
+
+impl FromStr for Method {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Method, String> {
+        match s {
+            "ilp" => Ok(Method::Ilp),
+            "greedy" => Ok(Method::Greedy),
+            "lazy-greedy" => Ok(Method::LazyGreedy),
+            "stochastic-greedy" => Ok(Method::StochasticGreedy),
+            "anneal" => Ok(Method::Anneal),
+            "ga" => Ok(Method::Genetic),
+            "tabu" => Ok(Method::Tabu),
+            "grasp" => Ok(Method::Grasp),
+            "lp-pipage" => Ok(Method::LpPipage),
+            "lp-round" => Ok(Method::LpRound),
+            "lagrangian" => Ok(Method::Lagrangian),
+            "bnb" => Ok(Method::BranchAndBound),
+            "exact-smallk" => Ok(Method::ExactSmallK),
+            "maxsat" => Ok(Method::MaxSat),
+            "portfolio" => Ok(Method::Portfolio),
+            "auto" => Ok(Method::Auto),
+            "hybrid" => Ok(Method::Hybrid),
+            "lns" => Ok(Method::Lns),
+            "kernel" => Ok(Method::Kernel),
+            "beam" => Ok(Method::Beam),
+            "threshold-greedy" => Ok(Method::ThresholdGreedy),
+            "greedi" => Ok(Method::Greedi),
+            "reverse-greedy" => Ok(Method::ReverseGreedy),
+            "aco" => Ok(Method::AntColony),
+            "cem" => Ok(Method::CrossEntropy),
+            "hillclimb" => Ok(Method::HillClimb),
+            "continuous-greedy" => Ok(Method::ContinuousGreedy),
+            "benders" => Ok(Method::Benders),
+            "colgen" => Ok(Method::ColumnGeneration),
+            _ => Err(format!("unknown method: {}", s)),
+        }
+    }
+}
+
+/// Size statistics used by `auto`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub elements: usize,
+    pub sets: usize,
+    /// Total number of (set, element) memberships.
+    pub memberships: usize,
+    /// `memberships / (elements * sets)`
+    pub density: f64,
+}
+
+impl Stats {
+    pub fn new(inst: &Instance) -> Stats {
+        let memberships = inst.sets.iter().map(|s| s.len()).sum::<usize>();
+        let cells = inst.ground.len() * inst.sets.len();
+        Stats {
+            elements: inst.ground.len(),
+            sets: inst.sets.len(),
+            memberships: memberships,
+            density: if cells == 0 { 0.0 } else { memberships as f64 / cells as f64 },
+        }
+    }
+}
+
+/// Largest ILP (in nonzeros of the coverage constraints) `auto` hands to CPLEX.
+const AUTO_ILP_MEMBERSHIPS: usize = 200000;
+/// Dense instances have weak LP relaxations and greedy is usually near-optimal on them.
+const AUTO_ILP_DENSITY: f64 = 0.5;
+/// Largest number of sets `auto` runs deterministic (lazy) greedy on.
+const AUTO_GREEDY_SETS: usize = 100000;
+
+/// Picks a method from the instance size: the exact ILP for small sparse models, lazy greedy
+/// for moderate set counts, and stochastic greedy beyond that.
+pub fn auto(stats: &Stats) -> Method {
+    if stats.memberships <= AUTO_ILP_MEMBERSHIPS && stats.density <= AUTO_ILP_DENSITY {
+        Method::Ilp
+    } else if stats.sets <= AUTO_GREEDY_SETS {
+        Method::LazyGreedy
+    } else {
+        Method::StochasticGreedy
+    }
+}
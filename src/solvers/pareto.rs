@@ -0,0 +1,119 @@
+use std::io::{self, Write};
+use std::str::FromStr;
+use {Instance, IlpOptions};
+use interrupt::interrupted;
+use backend::{self, SolverBackend};
+
+/// What the frontier trades coverage against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tradeoff {
+    /// The total cost of the selected sets.
+    Cost,
+    /// The number of selected sets.
+    Cardinality,
+}
+
+impl FromStr for Tradeoff {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Tradeoff, String> {
+        match s {
+            "cost" => Ok(Tradeoff::Cost),
+            "k" => Ok(Tradeoff::Cardinality),
+            _ => Err(format!("unknown tradeoff: {}", s)),
+        }
+    }
+}
+
+/// A selection on the frontier.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Point {
+    /// The selection's cost, or its number of sets when trading against `k`.
+    pub cost: f64,
+    pub coverage: f64,
+    pub sol: Vec<usize>,
+}
+
+/// The Pareto frontier of coverage against `tradeoff`, cheapest first, by the
+/// epsilon-constraint method: each solve maximizes the coverage with `solve` within a bound
+/// on the cost, which then drops to just below the cost of the selection found, by
+/// `resolution`, until nothing is covered. Trading against `k`, the bound is `k` itself,
+/// which rises by 1 from the number of required sets, or 1, until every element is covered.
+/// Selections that another covers as much as for less are left out.
+///
+/// Each solve has the full time limit, and one that stops early may miss the frontier. An
+/// interrupt ends the sweep with the points found so far.
+pub fn pareto<B>(inst: &Instance,
+                 tradeoff: Tradeoff,
+                 resolution: f64,
+                 backend: &mut B,
+                 params: &backend::Params)
+                 -> Result<Vec<Point>, String>
+    where B: SolverBackend + ?Sized
+{
+    let m = inst.sets.len();
+    let total = inst.weights().iter().sum::<f64>();
+    let mut points = Vec::new();
+    match tradeoff {
+        Tradeoff::Cost => {
+            // the budgeted greedy start ignores prerequisites
+            if inst.has_prerequisites() {
+                return Err("the cost tradeoff does not support prerequisites".to_string());
+            }
+            if !(resolution > 0.0) {
+                return Err(format!("resolution must be positive, not {}", resolution));
+            }
+            // no selection is cheaper than the required sets
+            let least = inst.cost(&inst.required());
+            let mut budget = inst.cost(&(0..m).collect::<Vec<_>>());
+            while budget >= least && !interrupted() {
+                let options = IlpOptions { budget: Some(budget), ..IlpOptions::default() };
+                let sol = ::solve(inst, m, backend, params, &options)?;
+                if sol.objective <= 0.0 {
+                    break;
+                }
+                let cost = inst.cost(&sol.sol);
+                points.push(Point { cost: cost, coverage: sol.objective, sol: sol.sol });
+                // a solver tolerance could find the same selection again
+                if cost - resolution >= budget {
+                    break;
+                }
+                budget = cost - resolution;
+            }
+        }
+        Tradeoff::Cardinality => {
+            let mut k = inst.required().len().max(1);
+            while k <= m && !interrupted() {
+                let sol = ::solve(inst, k, backend, params, &IlpOptions::default())?;
+                let covered = sol.objective;
+                points.push(Point { cost: sol.sol.len() as f64, coverage: covered, sol: sol.sol });
+                if covered >= total {
+                    break;
+                }
+                k += 1;
+            }
+        }
+    }
+
+    points.sort_by(|a, b| {
+        a.cost.partial_cmp(&b.cost).unwrap().then(b.coverage.partial_cmp(&a.coverage).unwrap())
+    });
+    let mut frontier: Vec<Point> = Vec::with_capacity(points.len());
+    for point in points {
+        if frontier.last().is_none_or(|last| point.coverage > last.coverage) {
+            frontier.push(point);
+        }
+    }
+    Ok(frontier)
+}
+
+/// Writes `frontier` as CSV with a header, one point per line, with the selected sets
+/// separated by spaces.
+pub fn write_csv<W: Write>(frontier: &[Point], out: &mut W) -> io::Result<()> {
+    writeln!(out, "cost,coverage,sets")?;
+    for point in frontier {
+        let sets = point.sol.iter().map(|i| i.to_string()).collect::<Vec<_>>();
+        writeln!(out, "{},{},{}", point.cost, point.coverage, sets.join(" "))?;
+    }
+    Ok(())
+}
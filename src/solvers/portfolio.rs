@@ -0,0 +1,110 @@
+use std::sync::Arc;
+use std::sync::mpsc::{channel, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
+use {Instance, Solution};
+#[cfg(feature = "cplex")]
+use solve;
+#[cfg(feature = "cplex")]
+use backend::{self, Cplex, Staged};
+use solvers::greedy::lazy_greedy;
+use solvers::local;
+
+type Outcome = (&'static str, Result<Solution, String>);
+
+/// Seconds of the time limit, at most a tenth of it, that the ILP leaves for building its
+/// model and reporting back, so that its incumbent arrives within the limit.
+#[cfg(feature = "cplex")]
+const ILP_MARGIN: f64 = 1.0;
+
+/// Runs `f` on its own thread and sends its result, labelled with `name`, to `tx`.
+fn race<F>(name: &'static str, inst: &Arc<Instance>, tx: &Sender<Outcome>, f: F)
+    where F: FnOnce(&Instance) -> Result<Solution, String> + Send + 'static
+{
+    let inst = inst.clone();
+    let tx = tx.clone();
+    thread::spawn(move || {
+        let _ = tx.send((name, f(&inst)));
+    });
+}
+
+/// Races lazy greedy, lazy greedy polished by swap local search, and the CPLEX ILP in
+/// separate threads and returns the best solution, with `method` naming the winner. Without
+/// the cplex feature only the two heuristics race.
+///
+/// With a `time_limit` (in seconds) the ILP stops shortly before the limit with its
+/// incumbent, and this returns once the limit passes even if a racer is still running; its
+/// thread is detached. A proven optimum ends the race early.
+#[cfg_attr(not(feature = "cplex"), allow(unused_variables))]
+pub fn portfolio(inst: &Instance,
+                 k: usize,
+                 threads: Option<usize>,
+                 time_limit: Option<f64>)
+                 -> Result<Solution, String> {
+    let inst = Arc::new(inst.clone());
+    let (tx, rx) = channel();
+
+    let num_racers = if cfg!(feature = "cplex") { 3 } else { 2 };
+    race("lazy-greedy", &inst, &tx, move |inst| Ok(lazy_greedy(inst, k)));
+    race("swap", &inst, &tx, move |inst| Ok(local::swap(inst, &lazy_greedy(inst, k))));
+    #[cfg(feature = "cplex")]
+    {
+        let params = backend::Params {
+            threads: threads,
+            time_limit: time_limit.map(|t| t - ILP_MARGIN.min(t / 10.0)),
+            ..Default::default()
+        };
+        race("ilp", &inst, &tx, move |inst| {
+            solve(inst, k, &mut Staged::new(Cplex::default()), &params, &Default::default())
+        });
+    }
+
+    let deadline = time_limit.map(|t| Instant::now() + Duration::from_millis((t * 1000.0) as u64));
+    let mut best: Option<Solution> = None;
+    let mut errors = Vec::new();
+
+    for _ in 0..num_racers {
+        let received = match deadline {
+            Some(deadline) => {
+                let now = Instant::now();
+                if now >= deadline {
+                    break;
+                }
+                match rx.recv_timeout(deadline - now) {
+                    Ok(msg) => msg,
+                    Err(RecvTimeoutError::Timeout) => break,
+                    Err(RecvTimeoutError::Disconnected) => break,
+                }
+            }
+            None => {
+                match rx.recv() {
+                    Ok(msg) => msg,
+                    Err(_) => break,
+                }
+            }
+        };
+
+        match received {
+            (name, Ok(mut sol)) => {
+                sol.method = Some(name.to_string());
+                if best.as_ref().is_none_or(|b| sol.objective > b.objective) {
+                    best = Some(sol);
+                }
+            }
+            (name, Err(e)) => errors.push(format!("{}: {}", name, e)),
+        }
+
+        let proven = best.as_ref().is_some_and(|b| b.bound.is_some_and(|ub| b.objective >= ub));
+        if proven {
+            break;
+        }
+    }
+
+    best.ok_or_else(|| {
+        if errors.is_empty() {
+            "no method finished within the time limit".to_string()
+        } else {
+            errors.join("; ")
+        }
+    })
+}
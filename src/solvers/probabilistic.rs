@@ -0,0 +1,165 @@
+use rand::Rng;
+use {Instance, Solution};
+use backend::{self, Model, Sense, SolverBackend, VarType};
+use coverage::{Index, Coverage};
+
+/// The elements of each set of `index` along with the probability that the set covers them.
+fn membership(inst: &Instance, index: &Index) -> Vec<Vec<(usize, f64)>> {
+    let probabilities = inst.probabilities();
+    (0..index.num_sets())
+        .map(|i| {
+            // the index drops the elements that are not in the ground set, keeping the order
+            inst.sets[i]
+                .iter()
+                .zip(&probabilities[i])
+                .filter(|&(x, _)| inst.ground.contains(x))
+                .map(|(_, &p)| p)
+                .zip(&index.sets[i])
+                .map(|(p, &e)| (e, p))
+                .collect()
+        })
+        .collect()
+}
+
+/// Multiplies the probability that each element is missed by that of missing it with the
+/// set of `members`.
+fn miss(missed: &mut [f64], members: &[(usize, f64)]) {
+    for &(e, p) in members {
+        missed[e] *= 1.0 - p;
+    }
+}
+
+/// The expected weight `sets` cover, where element `e` is covered with probability
+/// `1 - prod (1 - p)` over the selected sets that contain it with probability `p`.
+pub fn expected_coverage(inst: &Instance, sets: &[usize]) -> f64 {
+    let index = Index::new(inst);
+    let members = membership(inst, &index);
+    let mut missed = vec![1.0; index.num_elements];
+    for &i in sets {
+        miss(&mut missed, &members[i]);
+    }
+    missed.iter().zip(&index.weights).map(|(q, w)| w * (1.0 - q)).sum()
+}
+
+/// Greedy on the exact expected coverage, which is submodular, so this is a
+/// (1 - 1/e)-approximation. Starts from the required sets and only adds the sets that the
+/// selection allows, like `greedy`.
+pub fn probabilistic_greedy(inst: &Instance, k: usize) -> Solution {
+    assert!(!inst.has_demands(), "probabilistic coverage does not support demands");
+    let index = Index::new(inst);
+    let members = membership(inst, &index);
+    // tracks the selection for its constraints
+    let mut cov = Coverage::initial(&index);
+    let mut sol = cov.selection();
+    let mut missed = vec![1.0; index.num_elements];
+    for &i in &sol {
+        miss(&mut missed, &members[i]);
+    }
+
+    while sol.len() < k {
+        let mut best = None;
+        for s in 0..index.num_sets() {
+            if cov.contains(s) || !cov.allows(s) {
+                continue;
+            }
+            let gain = members[s]
+                .iter()
+                .map(|&(e, p)| index.weights[e] * missed[e] * p)
+                .sum::<f64>();
+            if gain > 0.0 && best.is_none_or(|(_, g)| gain > g) {
+                best = Some((s, gain));
+            }
+        }
+
+        match best {
+            Some((s, _)) => {
+                cov.add(s);
+                miss(&mut missed, &members[s]);
+                sol.push(s);
+            }
+            None => break,
+        }
+    }
+
+    Solution::new(expected_coverage(inst, &sol), sol)
+}
+
+/// Sample average approximation of probabilistic coverage: draws `samples` realizations of
+/// which sets cover which elements and maximizes the mean weight covered over them with
+/// `backend`. The model has the set variables first, then an element variable for each
+/// element of each sample that some set may cover. It is warm started from
+/// `probabilistic_greedy` and also written to `problem.lp`.
+///
+/// The solution's objective is its exact expected coverage. The solver's bound only holds
+/// for the samples, so the solution has none.
+pub fn sample_average<B, R>(inst: &Instance,
+                            k: usize,
+                            samples: usize,
+                            backend: &mut B,
+                            params: &backend::Params,
+                            rng: &mut R)
+                            -> Result<Solution, String>
+    where B: SolverBackend + ?Sized,
+          R: Rng
+{
+    if inst.has_demands() {
+        return Err("probabilistic coverage does not support demands".to_string());
+    }
+    let index = Index::new(inst);
+    let members = membership(inst, &index);
+    let samples = samples.max(1);
+    let mut model = Model::new("saa", true);
+    let set_vars = (0..index.num_sets())
+        .map(|i| model.add_var(format!("s{}", i), VarType::Binary, 0.0, 0.0, 1.0))
+        .collect::<Vec<_>>();
+    let cardinality = set_vars.iter().map(|&j| (j, 1.0)).collect();
+    model.add_row("cardinality".to_string(), cardinality, Sense::LessEq, k as f64);
+    backend::add_selection_rows(&mut model, inst, &set_vars);
+    let selection_vars = set_vars.len()..model.vars.len();
+
+    // each element variable of a sample, with the sets covering it there
+    let mut elements = Vec::new();
+    for t in 0..samples {
+        let mut covering = vec![Vec::new(); index.num_elements];
+        for (i, set) in members.iter().enumerate() {
+            for &(e, p) in set {
+                if rng.gen::<f64>() < p {
+                    covering[e].push(i);
+                }
+            }
+        }
+        for (e, sets) in covering.into_iter().enumerate().filter(|(_, s)| !s.is_empty()) {
+            let obj = index.weights[e] / samples as f64;
+            let x = model.add_var(format!("x{}_{}", t, e), VarType::Binary, obj, 0.0, 1.0);
+            let mut terms = vec![(x, 1.0)];
+            terms.extend(sets.iter().map(|&i| (set_vars[i], -1.0)));
+            model.add_row(format!("cover{}_{}", t, e), terms, Sense::LessEq, 0.0);
+            elements.push((x, sets));
+        }
+    }
+
+    let greedy = probabilistic_greedy(inst, k);
+    let mut values = vec![0.0; model.vars.len()];
+    for &i in &greedy.sol {
+        values[set_vars[i]] = 1.0;
+    }
+    for (j, value) in selection_vars.zip(backend::selection_values(inst, &greedy.sol)) {
+        values[j] = value;
+    }
+    for &(x, ref sets) in &elements {
+        if sets.iter().any(|i| greedy.sol.contains(i)) {
+            values[x] = 1.0;
+        }
+    }
+    model.start = Some(values);
+    ::write_problem_lp(&model)?;
+
+    params.apply(backend)?;
+    backend.build_model(model)?;
+    backend.solve()?;
+    let sol = backend.extract_solution()?;
+    let selected = (0..set_vars.len())
+        .filter(|&i| sol.values[set_vars[i]] > 0.5)
+        .collect::<Vec<_>>();
+    Ok(Solution::new(expected_coverage(inst, &selected), selected))
+}
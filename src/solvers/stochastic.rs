@@ -0,0 +1,167 @@
+use {Instance, Solution};
+use backend::{self, Model, Sense, SolverBackend, VarType};
+use coverage::{Index, Coverage};
+use solvers::greedy::lazy_greedy;
+
+/// The instance whose elements weigh their mean weight over the scenarios.
+pub fn mean_instance(inst: &Instance) -> Instance {
+    let scenarios = inst.scenarios();
+    let n = scenarios.len() as f64;
+    let mean = (0..inst.ground.len())
+        .map(|e| scenarios.iter().map(|w| w[e]).sum::<f64>() / n)
+        .collect();
+    Instance { weights: Some(mean), scenarios: None, ..inst.clone() }
+}
+
+/// The expected coverage of selecting `first`, and then `recourse[s]` in scenario `s`, with
+/// every scenario equally likely.
+pub fn expected_coverage(inst: &Instance, first: &[usize], recourse: &[Vec<usize>]) -> f64 {
+    let index = Index::new(inst);
+    let scenarios = inst.scenarios();
+    let total = scenarios.iter()
+        .zip(recourse)
+        .map(|(weights, second)| {
+            let mut cov = Coverage::from_sets(&index, first);
+            for &i in second.iter().filter(|i| !first.contains(i)) {
+                cov.add(i);
+            }
+            cov.value_by(weights)
+        })
+        .sum::<f64>();
+    total / scenarios.len() as f64
+}
+
+/// The at most `k2` sets that greedy adds to `first` for the element weights `weights`.
+fn greedy_recourse(index: &Index, first: &[usize], k2: usize, weights: &[f64]) -> Vec<usize> {
+    let mut cov = Coverage::from_sets(index, first);
+    let mut second = Vec::with_capacity(k2);
+    while second.len() < k2 {
+        let best = (0..index.num_sets())
+            .filter(|&s| !cov.contains(s))
+            .map(|s| (s, cov.gain_by(s, weights)))
+            .fold(None, |best: Option<(usize, f64)>, (s, gain)| {
+                if best.is_none_or(|(_, g)| gain > g) { Some((s, gain)) } else { best }
+            });
+        match best {
+            Some((s, gain)) if gain > 0.0 => {
+                cov.add(s);
+                second.push(s);
+            }
+            _ => break,
+        }
+    }
+    second
+}
+
+/// Two-stage greedy: lazy greedy on `mean_instance` picks the `k1` first-stage sets, and
+/// greedy the `k2` recourse sets of each scenario for its weights. The solution's objective
+/// is `expected_coverage`.
+pub fn two_stage_greedy(inst: &Instance, k1: usize, k2: usize) -> Solution {
+    let first = lazy_greedy(&mean_instance(inst), k1).sol;
+    let index = Index::new(inst);
+    let recourse = inst.scenarios()
+        .iter()
+        .map(|weights| greedy_recourse(&index, &first, k2, weights))
+        .collect::<Vec<_>>();
+    let mut sol = Solution::new(expected_coverage(inst, &first, &recourse), first);
+    sol.recourse = Some(recourse);
+    sol
+}
+
+/// Two-stage stochastic coverage by its deterministic equivalent: `k1` sets are selected
+/// before the scenario is known and `k2` more in each scenario, which are equally likely,
+/// maximizing the expected coverage. The model has the first-stage set variables, then for
+/// each scenario its element variables and its recourse set variables. The solution selects
+/// the first-stage sets, with each scenario's recourse in `recourse`, and its objective is
+/// `expected_coverage`.
+///
+/// The solver is warm started from `two_stage_greedy`, and the model is also written to
+/// `problem.lp`. The bound and gap are as for `solve`.
+pub fn two_stage<B>(inst: &Instance,
+                    k1: usize,
+                    k2: usize,
+                    backend: &mut B,
+                    params: &backend::Params)
+                    -> Result<Solution, String>
+    where B: SolverBackend + ?Sized
+{
+    let index = Index::new(inst);
+    let scenarios = inst.scenarios();
+    let n = scenarios.len() as f64;
+    let m = index.num_sets();
+    let mut model = Model::new("two-stage", true);
+
+    let first_vars = (0..m)
+        .map(|i| model.add_var(format!("y{}", i), VarType::Binary, 0.0, 0.0, 1.0))
+        .collect::<Vec<_>>();
+    let terms = first_vars.iter().map(|&j| (j, 1.0)).collect();
+    model.add_row("first".to_string(), terms, Sense::LessEq, k1 as f64);
+
+    let mut element_vars = Vec::with_capacity(scenarios.len());
+    let mut recourse_vars = Vec::with_capacity(scenarios.len());
+    for (s, weights) in scenarios.iter().enumerate() {
+        let xs = (0..index.num_elements)
+            .map(|e| {
+                let name = format!("x{}_{}", s, e);
+                model.add_var(name, VarType::Binary, weights[e] / n, 0.0, 1.0)
+            })
+            .collect::<Vec<_>>();
+        let zs = (0..m)
+            .map(|i| model.add_var(format!("z{}_{}", s, i), VarType::Binary, 0.0, 0.0, 1.0))
+            .collect::<Vec<_>>();
+        let terms = zs.iter().map(|&j| (j, 1.0)).collect();
+        model.add_row(format!("recourse{}", s), terms, Sense::LessEq, k2 as f64);
+
+        for e in 0..index.num_elements {
+            let mut terms = vec![(xs[e], index.demands[e] as f64)];
+            for &i in &index.containing[e] {
+                terms.push((first_vars[i], -1.0));
+                terms.push((zs[i], -1.0));
+            }
+            model.add_row(format!("cover{}_{}", s, e), terms, Sense::LessEq, 0.0);
+        }
+        // a set selected in both stages would count twice towards a demand
+        if inst.has_demands() {
+            for i in 0..m {
+                let terms = vec![(first_vars[i], 1.0), (zs[i], 1.0)];
+                model.add_row(format!("once{}_{}", s, i), terms, Sense::LessEq, 1.0);
+            }
+        }
+        element_vars.push(xs);
+        recourse_vars.push(zs);
+    }
+
+    let start = two_stage_greedy(inst, k1, k2);
+    let mut values = vec![0.0; model.vars.len()];
+    for &i in &start.sol {
+        values[first_vars[i]] = 1.0;
+    }
+    for (s, second) in start.recourse.as_ref().unwrap().iter().enumerate() {
+        let mut cov = Coverage::from_sets(&index, &start.sol);
+        for &i in second {
+            cov.add(i);
+            values[recourse_vars[s][i]] = 1.0;
+        }
+        for e in (0..index.num_elements).filter(|&e| cov.covered(e)) {
+            values[element_vars[s][e]] = 1.0;
+        }
+    }
+    model.start = Some(values);
+    ::write_problem_lp(&model)?;
+
+    params.apply(backend)?;
+    backend.build_model(model)?;
+    backend.solve()?;
+    let sol = backend.extract_solution()?;
+    let selected = |vars: &[usize]| {
+        (0..m).filter(|&i| sol.values[vars[i]] > 0.5).collect::<Vec<_>>()
+    };
+    let first = selected(&first_vars);
+    let recourse = recourse_vars.iter().map(|zs| selected(zs)).collect::<Vec<_>>();
+    let objective = expected_coverage(inst, &first, &recourse);
+
+    let mut out = Solution::new(objective, first);
+    out.recourse = Some(recourse);
+    ::fill_bound(&mut out, &sol, params);
+    Ok(out)
+}
@@ -0,0 +1,144 @@
+use std::io::{self, Write};
+use {Instance, IlpOptions};
+use interrupt::interrupted;
+use backend::{self, SolverBackend};
+use coverage::{Index, Coverage};
+use solvers::greedy::{greedy, greedy_fn};
+use submodular::CoverageFn;
+
+/// The coverage for one `k` of a sweep.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Point {
+    pub k: usize,
+    pub coverage: f64,
+    /// The best bound on the coverage for `k`, where the solver reports one.
+    pub bound: Option<f64>,
+    pub sol: Vec<usize>,
+}
+
+/// Parses a range of `k` given as `a..b`, from `a` to `b` inclusive, or as `b`, from 1.
+pub fn parse_range(s: &str) -> Result<(usize, usize), String> {
+    let bad = |_| format!("bad range of k: {}", s);
+    let (first, last) = match s.find("..") {
+        Some(at) => (s[..at].parse().map_err(&bad)?, s[at + 2..].parse().map_err(&bad)?),
+        None => (1, s.parse().map_err(&bad)?),
+    };
+    if first > last {
+        return Err(format!("empty range of k: {}", s));
+    }
+    Ok((first, last))
+}
+
+/// The greedy coverage curve from `first` to `last`. Greedy picks the same sets for each `k`
+/// as it does first for larger ones, so a single run up to `last` gives every point, as the
+/// coverage of its prefixes. The points below the number of required sets are left out.
+pub fn greedy_sweep(inst: &Instance, first: usize, last: usize) -> Vec<Point> {
+    let index = Index::new(inst);
+    let sol = greedy(inst, last).sol;
+    let required = inst.required().len();
+    let mut cov = Coverage::new(&index);
+    let mut points = Vec::new();
+    for k in first.max(required)..last + 1 {
+        let prefix = &sol[..k.min(sol.len())];
+        while cov.size() < prefix.len() {
+            let s = prefix[cov.size()];
+            cov.add(s);
+        }
+        let mut sets = prefix.to_vec();
+        sets.sort();
+        points.push(Point { k: k, coverage: cov.value(), bound: None, sol: sets });
+    }
+    points
+}
+
+/// The optimal coverage curve from `first` to `last`, by `solve` for the first `k` and
+/// `resolve` for the rest, which only changes the cardinality of the loaded model, or by
+/// `solve` for each with `options.lazy`. The selection for each `k` also selects few enough
+/// sets for the next, so each solve is warm started from the last one's selection, extended
+/// by `greedy` while that keeps to the constraints, and once every element is covered the
+/// rest of the curve is the same. `options` apply to every solve, but for the start.
+///
+/// Each solve has the full time limit. An interrupt ends the sweep with the points found so
+/// far, and the points below the number of required sets are left out.
+pub fn ilp_sweep<B>(inst: &Instance,
+                    first: usize,
+                    last: usize,
+                    backend: &mut B,
+                    params: &backend::Params,
+                    options: &IlpOptions)
+                    -> Result<Vec<Point>, String>
+    where B: SolverBackend + ?Sized
+{
+    let index = Index::new(inst);
+    let total = index.weights.iter().sum::<f64>();
+    // greedy ignores the prerequisites and the budget
+    let extend = !inst.has_prerequisites() && options.budget.is_none();
+    let mut points: Vec<Point> = Vec::new();
+    for k in first.max(inst.required().len())..last + 1 {
+        if interrupted() {
+            break;
+        }
+        if let Some(point) = points.last().cloned() {
+            if point.coverage >= total {
+                points.push(Point { k: k, ..point });
+                continue;
+            }
+        }
+        let start = match points.last() {
+            Some(point) if extend => {
+                let mut f = CoverageFn::new(Coverage::from_sets(&index, &point.sol));
+                greedy_fn(&mut f, k).sol
+            }
+            Some(point) => point.sol.clone(),
+            None if options.start.is_some() => options.start.clone().unwrap(),
+            None => greedy(inst, k).sol,
+        };
+        let loaded = !points.is_empty() && !options.lazy;
+        let options = IlpOptions { start: Some(start), ..options.clone() };
+        let mut sol = if loaded {
+            ::resolve(inst, k, backend, params, &options)?
+        } else {
+            ::solve(inst, k, backend, params, &options)?
+        };
+        sol.sol.sort();
+        points.push(Point {
+            k: k,
+            coverage: sol.objective,
+            bound: sol.bound,
+            sol: sol.sol,
+        });
+    }
+    Ok(points)
+}
+
+/// Writes `curve` as CSV with a header, one point per line, with the selected sets separated
+/// by spaces and the bound left empty where there is none.
+pub fn write_csv<W: Write>(curve: &[Point], out: &mut W) -> io::Result<()> {
+    writeln!(out, "k,coverage,bound,sets")?;
+    for point in curve {
+        let bound = point.bound.map_or(String::new(), |b| b.to_string());
+        let sets = point.sol.iter().map(|i| i.to_string()).collect::<Vec<_>>();
+        writeln!(out, "{},{},{},{}", point.k, point.coverage, bound, sets.join(" "))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_ranges() {
+        assert_eq!(parse_range("3..7"), Ok((3, 7)));
+        assert_eq!(parse_range("4..4"), Ok((4, 4)));
+        assert_eq!(parse_range("5"), Ok((1, 5)));
+    }
+
+    #[test]
+    fn rejects_bad_ranges() {
+        assert!(parse_range("7..3").is_err());
+        assert!(parse_range("x..3").is_err());
+        assert!(parse_range("3..").is_err());
+        assert!(parse_range("").is_err());
+    }
+}
@@ -0,0 +1,85 @@
+use rand::Rng;
+use {Instance, Solution};
+use interrupt::interrupted;
+use coverage::{Index, Coverage};
+use solvers::greedy::lazy_greedy;
+use solvers::local::fill_random;
+
+/// When a tabu move may be taken anyway.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aspiration {
+    /// Never override the tabu list.
+    None,
+    /// Allow a tabu move that yields a new best solution.
+    Best,
+}
+
+impl Aspiration {
+    pub fn new(name: &str) -> Result<Aspiration, String> {
+        match name {
+            "none" => Ok(Aspiration::None),
+            "best" => Ok(Aspiration::Best),
+            _ => Err(format!("unknown aspiration criterion: {}", name)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Params {
+    pub iterations: usize,
+    pub tenure: usize,
+    pub aspiration: Aspiration,
+}
+
+/// Tabu search over swap moves, starting from the greedy solution. Each iteration takes the
+/// best admissible swap even if it worsens coverage; sets that were just swapped in or out
+/// may not change status again for `tenure` iterations.
+pub fn tabu<R: Rng>(inst: &Instance, k: usize, params: &Params, rng: &mut R) -> Solution {
+    let index = Index::new(inst);
+    let start = lazy_greedy(inst, k);
+    let mut cov = Coverage::from_sets(&index, &start.sol);
+    fill_random(&mut cov, k, rng);
+
+    let mut best = (cov.value(), cov.selection());
+    let mut tabu_until = vec![0; index.num_sets()];
+
+    for it in 0..params.iterations {
+        if interrupted() {
+            break;
+        }
+        let mut chosen: Option<(usize, usize, f64)> = None;
+        for s in cov.removable_selection() {
+            cov.remove(s);
+            for t in 0..index.num_sets() {
+                if cov.contains(t) || t == s || !cov.allows(t) {
+                    continue;
+                }
+                let value = cov.value() + cov.gain(t);
+                let is_tabu = tabu_until[s] > it || tabu_until[t] > it;
+                let aspires = params.aspiration == Aspiration::Best && value > best.0;
+                if is_tabu && !aspires {
+                    continue;
+                }
+                if chosen.is_none_or(|(_, _, v)| value > v) {
+                    chosen = Some((s, t, value));
+                }
+            }
+            cov.add(s);
+        }
+
+        let (s, t, _) = match chosen {
+            Some(mv) => mv,
+            None => break,
+        };
+        cov.remove(s);
+        cov.add(t);
+        tabu_until[s] = it + 1 + params.tenure;
+        tabu_until[t] = it + 1 + params.tenure;
+
+        if cov.value() > best.0 {
+            best = (cov.value(), cov.selection());
+        }
+    }
+
+    Solution::new(best.0, best.1)
+}
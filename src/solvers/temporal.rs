@@ -0,0 +1,294 @@
+use std::collections::BTreeSet;
+use std::str::FromStr;
+use {Instance, Solution};
+use backend::{self, Model, Sense, SolverBackend, VarType};
+use coverage::{Index, Coverage};
+
+/// When an element counts as covered over the time windows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rule {
+    /// Covered in every window.
+    Every,
+    /// Covered in at least one window.
+    Any,
+}
+
+impl FromStr for Rule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Rule, String> {
+        match s {
+            "every" => Ok(Rule::Every),
+            "some" => Ok(Rule::Any),
+            _ => Err(format!("unknown window rule: {}", s)),
+        }
+    }
+}
+
+/// Whether each set is active in each window.
+fn activity(inst: &Instance) -> Vec<Vec<bool>> {
+    let n = inst.num_windows();
+    inst.windows()
+        .iter()
+        .map(|windows| (0..n).map(|t| windows.contains(&t)).collect())
+        .collect()
+}
+
+/// Whether each element is covered in each window by `schedule`, the sets selected in each
+/// window, of which only those active in the window count.
+fn covered(index: &Index, active: &[Vec<bool>], schedule: &[Vec<usize>]) -> Vec<Vec<bool>> {
+    let mut covered = vec![vec![false; schedule.len()]; index.num_elements];
+    for (t, sets) in schedule.iter().enumerate() {
+        for &i in sets.iter().filter(|&&i| active[i][t]) {
+            for &e in &index.sets[i] {
+                covered[e][t] = true;
+            }
+        }
+    }
+    covered
+}
+
+/// The weight covered under `rule` by `schedule`, the sets selected in each window.
+pub fn value(inst: &Instance, rule: Rule, schedule: &[Vec<usize>]) -> f64 {
+    let index = Index::new(inst);
+    let covered = covered(&index, &activity(inst), schedule);
+    (0..index.num_elements)
+        .filter(|&e| match rule {
+            Rule::Every => covered[e].iter().all(|&c| c),
+            Rule::Any => covered[e].iter().any(|&c| c),
+        })
+        .map(|e| index.weights[e])
+        .sum()
+}
+
+/// The solution that selects `schedule[t]` in window `t`: with `per_window` its sets are
+/// every set selected in some window, with the schedule in `schedule`; otherwise every
+/// window selects the same sets, which are its sets.
+fn solution(inst: &Instance, rule: Rule, schedule: Vec<Vec<usize>>, per_window: bool) -> Solution {
+    let objective = value(inst, rule, &schedule);
+    if per_window {
+        let sets = schedule.iter().flat_map(|s| s.iter().cloned()).collect::<BTreeSet<_>>();
+        let mut sol = Solution::new(objective, sets.into_iter().collect());
+        sol.schedule = Some(schedule);
+        sol
+    } else {
+        let sets = schedule.into_iter().next().unwrap_or_default();
+        Solution::new(objective, sets)
+    }
+}
+
+/// Temporal greedy: repeatedly selects the set, or with `per_window` the set and window,
+/// that makes the most progress, until `k` sets are selected, in each window with
+/// `per_window`, or none makes any. Under `Rule::Every` an element progresses by its weight
+/// over the number of windows for each window it becomes covered in, as its weight only
+/// counts once it is covered in every one. Without `per_window` it starts from the required
+/// sets and only adds the sets that the selection allows, like `greedy`.
+pub fn temporal_greedy(inst: &Instance, k: usize, rule: Rule, per_window: bool) -> Solution {
+    assert!(!inst.has_demands(), "time windows do not support demands");
+    let index = Index::new(inst);
+    let active = activity(inst);
+    let n = inst.num_windows();
+    // tracks the shared selection for its constraints
+    let mut cov = Coverage::initial(&index);
+    let mut schedule = vec![if per_window { Vec::new() } else { cov.selection() }; n];
+    let mut covered = covered(&index, &active, &schedule);
+
+    let progress = |covered: &[Vec<bool>], i: usize, windows: &[usize]| {
+        index.sets[i]
+            .iter()
+            .map(|&e| {
+                let new = windows.iter().filter(|&&t| active[i][t] && !covered[e][t]).count();
+                match rule {
+                    Rule::Every => index.weights[e] * new as f64 / n as f64,
+                    Rule::Any if new > 0 && !covered[e].iter().any(|&c| c) => index.weights[e],
+                    Rule::Any => 0.0,
+                }
+            })
+            .sum::<f64>()
+    };
+    let all = (0..n).collect::<Vec<_>>();
+
+    loop {
+        let mut best = None;
+        for i in 0..index.num_sets() {
+            if per_window {
+                for t in (0..n).filter(|&t| active[i][t] && schedule[t].len() < k) {
+                    if schedule[t].contains(&i) {
+                        continue;
+                    }
+                    let gain = progress(&covered, i, &[t]);
+                    if gain > 0.0 && best.is_none_or(|(_, _, g)| gain > g) {
+                        best = Some((i, Some(t), gain));
+                    }
+                }
+            } else if schedule[0].len() < k && !cov.contains(i) && cov.allows(i) {
+                let gain = progress(&covered, i, &all);
+                if gain > 0.0 && best.is_none_or(|(_, _, g)| gain > g) {
+                    best = Some((i, None, gain));
+                }
+            }
+        }
+
+        let windows = match best {
+            Some((i, Some(t), _)) => {
+                schedule[t].push(i);
+                vec![t]
+            }
+            Some((i, None, _)) => {
+                cov.add(i);
+                for sets in &mut schedule {
+                    sets.push(i);
+                }
+                all.clone()
+            }
+            None => break,
+        };
+        let i = best.unwrap().0;
+        for &t in windows.iter().filter(|&&t| active[i][t]) {
+            for &e in &index.sets[i] {
+                covered[e][t] = true;
+            }
+        }
+    }
+
+    solution(inst, rule, schedule, per_window)
+}
+
+/// Temporal max coverage with `backend`: selects at most `k` sets, used in every window, or
+/// with `per_window` at most `k` sets in each window, maximizing the weight covered under
+/// `rule`, where a set only covers its elements in its windows. The model has the element
+/// variables first, in ground set order, then the set variables, one per set, or per set and
+/// window it is active in with `per_window`. Under `Rule::Every` each element has a row per
+/// window, and under `Rule::Any` a single one.
+///
+/// The solver is warm started from `temporal_greedy`, and the model is also written to
+/// `problem.lp`. The bound and gap are as for `solve`.
+pub fn temporal<B>(inst: &Instance,
+                   k: usize,
+                   rule: Rule,
+                   per_window: bool,
+                   backend: &mut B,
+                   params: &backend::Params)
+                   -> Result<Solution, String>
+    where B: SolverBackend + ?Sized
+{
+    if inst.has_demands() {
+        return Err("time windows do not support demands".to_string());
+    }
+    if per_window && inst.has_selection_constraints() {
+        return Err("per-window selections do not support constraints on the selection"
+            .to_string());
+    }
+    let index = Index::new(inst);
+    let active = activity(inst);
+    let n = inst.num_windows();
+    let mut model = Model::new("temporal", true);
+    for (&x, w) in inst.ground.iter().zip(inst.weights()) {
+        model.add_var(format!("e{}", x), VarType::Binary, w, 0.0, 1.0);
+    }
+
+    // the variable of each set in each window it is active in
+    let mut set_vars = vec![vec![None; n]; index.num_sets()];
+    let mut selection_vars = 0..0;
+    if per_window {
+        for t in 0..n {
+            let mut terms = Vec::new();
+            for i in (0..index.num_sets()).filter(|&i| active[i][t]) {
+                let y = model.add_var(format!("s{}_{}", i, t), VarType::Binary, 0.0, 0.0, 1.0);
+                set_vars[i][t] = Some(y);
+                terms.push((y, 1.0));
+            }
+            model.add_row(format!("window{}", t), terms, Sense::LessEq, k as f64);
+        }
+    } else {
+        let shared = (0..index.num_sets())
+            .map(|i| model.add_var(format!("s{}", i), VarType::Binary, 0.0, 0.0, 1.0))
+            .collect::<Vec<_>>();
+        model.selection = Some(inst.ground.len()..model.vars.len());
+        let cardinality = shared.iter().map(|&j| (j, 1.0)).collect();
+        model.add_row("cardinality".to_string(), cardinality, Sense::LessEq, k as f64);
+        let first = model.vars.len();
+        backend::add_selection_rows(&mut model, inst, &shared);
+        selection_vars = first..model.vars.len();
+        for i in 0..index.num_sets() {
+            for t in (0..n).filter(|&t| active[i][t]) {
+                set_vars[i][t] = Some(shared[i]);
+            }
+        }
+    }
+
+    let ground = inst.ground.iter().cloned().collect::<Vec<_>>();
+    for e in 0..index.num_elements {
+        let covering = |t: usize| {
+            index.containing[e].iter().filter_map(|&i| set_vars[i][t]).collect::<Vec<_>>()
+        };
+        match rule {
+            Rule::Every => {
+                for t in 0..n {
+                    let mut terms = vec![(e, 1.0)];
+                    terms.extend(covering(t).into_iter().map(|y| (y, -1.0)));
+                    model.add_row(format!("cover{}_{}", ground[e], t), terms, Sense::LessEq, 0.0);
+                }
+            }
+            Rule::Any => {
+                let vars = (0..n).flat_map(covering).collect::<BTreeSet<_>>();
+                let mut terms = vec![(e, 1.0)];
+                terms.extend(vars.into_iter().map(|y| (y, -1.0)));
+                model.add_row(format!("cover{}", ground[e]), terms, Sense::LessEq, 0.0);
+            }
+        }
+    }
+
+    let greedy = temporal_greedy(inst, k, rule, per_window);
+    let schedule = match greedy.schedule {
+        Some(ref schedule) => schedule.clone(),
+        None => vec![greedy.sol.clone(); n],
+    };
+    let mut values = vec![0.0; model.vars.len()];
+    let covered = covered(&index, &active, &schedule);
+    for e in 0..index.num_elements {
+        let counts = match rule {
+            Rule::Every => covered[e].iter().all(|&c| c),
+            Rule::Any => covered[e].iter().any(|&c| c),
+        };
+        if counts {
+            values[e] = 1.0;
+        }
+    }
+    for (t, sets) in schedule.iter().enumerate() {
+        for &i in sets {
+            if let Some(y) = set_vars[i][t] {
+                values[y] = 1.0;
+            }
+        }
+    }
+    for (j, value) in selection_vars.zip(backend::selection_values(inst, &greedy.sol)) {
+        values[j] = value;
+    }
+    model.start = Some(values);
+    ::write_problem_lp(&model)?;
+
+    params.apply(backend)?;
+    backend.build_model(model)?;
+    backend.solve()?;
+    let sol = backend.extract_solution()?;
+    let schedule = (0..n)
+        .map(|t| {
+            (0..index.num_sets())
+                .filter(|&i| set_vars[i][t].is_some_and(|y| sol.values[y] > 0.5))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let mut out = if per_window {
+        solution(inst, rule, schedule, true)
+    } else {
+        // sets that are active in no window are selected in none of them
+        let selected = (0..index.num_sets())
+            .filter(|&i| (0..n).any(|t| set_vars[i][t].is_some_and(|y| sol.values[y] > 0.5)))
+            .collect::<Vec<_>>();
+        solution(inst, rule, vec![selected; n], false)
+    };
+
+    ::fill_bound(&mut out, &sol, params);
+    Ok(out)
+}
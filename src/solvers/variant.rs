@@ -0,0 +1,258 @@
+use {Instance, Objective};
+use solvers::Method;
+
+/// The methods that solve every variant of `cover solve`.
+const BASIC_METHODS: &'static [Method] = &[Method::Ilp, Method::Greedy, Method::LazyGreedy];
+
+/// The flags that tune the max coverage model, which the models of the variants lack.
+const MODEL_FLAGS: &'static [&'static str] = &["--budget", "--pool", "--polish", "--lazy",
+                                               "--symmetry"];
+
+/// `MODEL_FLAGS` and `--warm-start`, for the variants whose solves are not warm started.
+const UNSTARTED_FLAGS: &'static [&'static str] = &["--budget", "--pool", "--polish", "--lazy",
+                                                   "--symmetry", "--warm-start"];
+
+/// The variant of the problem `cover solve` is asked for, beyond what the instance itself
+/// asks for, and the options given for its solve. Errors name the options by their flags.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Variant {
+    pub objective: Objective,
+    pub robust: bool,
+    /// The second-stage budget of the two-stage problem.
+    pub recourse: Option<usize>,
+    pub overlap: Option<f64>,
+    /// The sets the attacker may remove.
+    pub interdict: Option<usize>,
+    pub per_window: bool,
+    pub budget: bool,
+    pub pool: bool,
+    pub polish: bool,
+    pub lazy: bool,
+    pub symmetry: bool,
+    pub warm_start: bool,
+    pub checkpoint: bool,
+}
+
+impl Default for Variant {
+    /// Plain max coverage, with no options.
+    fn default() -> Variant {
+        Variant {
+            objective: Objective::MaxCoverage,
+            robust: false,
+            recourse: None,
+            overlap: None,
+            interdict: None,
+            per_window: false,
+            budget: false,
+            pool: false,
+            polish: false,
+            lazy: false,
+            symmetry: false,
+            warm_start: false,
+            checkpoint: false,
+        }
+    }
+}
+
+/// Fails unless `method` is one of the `methods` that support `mode`, a variant of the
+/// problem, and none of the flags `given` is among those it does not support.
+fn check_mode(mode: &str,
+              method: Method,
+              methods: &[Method],
+              unsupported: &[&str],
+              given: &[(&str, bool)])
+              -> Result<(), String> {
+    if !methods.contains(&method) {
+        return Err(format!("{} is not supported by {:?}", mode, method));
+    }
+    if let Some(&(flag, _)) = given.iter().find(|&&(f, on)| on && unsupported.contains(&f)) {
+        return Err(format!("{} is not supported with {}", flag, mode));
+    }
+    Ok(())
+}
+
+/// Checks that the variants `variant` and `inst` ask for may be combined, and that `method`
+/// and the options of `variant` support them.
+pub fn check(inst: &Instance, variant: &Variant, method: Method) -> Result<(), String> {
+    let objective = variant.objective;
+    let robust = variant.robust;
+    let recourse = variant.recourse;
+    let overlap = variant.overlap;
+    let interdict = variant.interdict;
+    let probabilistic = inst.probabilities.is_some();
+    let fair = inst.min_coverage.is_some();
+    let capacitated = inst.capacities.is_some();
+    let generalized = inst.profits.is_some();
+    let temporal = inst.windows.is_some();
+    let prerequisites = inst.has_prerequisites();
+    let selection = inst.has_selection_constraints();
+
+    if robust && objective != Objective::MaxCoverage {
+        return Err("--robust only applies to --objective max-coverage".to_string());
+    }
+    if recourse.is_some() && (objective != Objective::MaxCoverage || robust) {
+        return Err("--recourse only applies to --objective max-coverage without --robust"
+            .to_string());
+    }
+    if recourse.is_some() && selection {
+        return Err("--recourse does not support constraints on the selection".to_string());
+    }
+    if probabilistic && (objective != Objective::MaxCoverage || robust || recourse.is_some()) {
+        return Err("probabilistic instances only support --objective max-coverage".to_string());
+    }
+    if capacitated &&
+       (objective != Objective::MaxCoverage || robust || recourse.is_some() || probabilistic) {
+        return Err("capacities only apply to deterministic max coverage".to_string());
+    }
+    if generalized &&
+       (objective != Objective::MaxCoverage || robust || recourse.is_some() || probabilistic ||
+        capacitated) {
+        return Err("profits only apply to deterministic, uncapacitated max coverage"
+            .to_string());
+    }
+    if interdict.is_some() &&
+       (objective != Objective::MaxCoverage || robust || recourse.is_some() || probabilistic ||
+        capacitated || generalized || fair || overlap.is_some()) {
+        return Err("--interdict only applies to deterministic max coverage by weight"
+            .to_string());
+    }
+    if variant.per_window && !temporal {
+        return Err("--per-window needs an instance with windows".to_string());
+    }
+    if temporal &&
+       (objective != Objective::MaxCoverage || robust || recourse.is_some() || probabilistic ||
+        capacitated || generalized || interdict.is_some() || fair || overlap.is_some()) {
+        return Err("windows only apply to deterministic max coverage by weight".to_string());
+    }
+    if variant.per_window && selection {
+        return Err("--per-window does not support constraints on the selection".to_string());
+    }
+    if fair &&
+       (objective != Objective::MaxCoverage || robust || recourse.is_some() || probabilistic ||
+        capacitated || generalized) {
+        return Err("min_coverage only applies to deterministic max coverage by weight"
+            .to_string());
+    }
+    if let Some(penalty) = overlap {
+        if !(penalty >= 0.0) {
+            return Err(format!("--overlap-penalty must be at least 0, not {}", penalty));
+        }
+        if objective != Objective::MaxCoverage || robust || recourse.is_some() || probabilistic ||
+           fair || capacitated || generalized {
+            return Err("--overlap-penalty only applies to deterministic max coverage by weight"
+                .to_string());
+        }
+    }
+    if prerequisites &&
+       (objective != Objective::MaxCoverage || robust || recourse.is_some() || probabilistic ||
+        overlap.is_some() || capacitated || generalized || temporal) {
+        return Err("prerequisites only apply to deterministic max coverage by weight"
+            .to_string());
+    }
+    let demands = [("probabilistic instances", probabilistic),
+                   ("capacities", capacitated),
+                   ("profits", generalized),
+                   ("windows", temporal),
+                   ("--overlap-penalty", overlap.is_some())];
+    if let Some(&(mode, _)) = demands.iter().find(|&&(_, on)| on && inst.has_demands()) {
+        return Err(format!("demands are not supported with {}", mode));
+    }
+    let greedy = method == Method::Greedy || method == Method::LazyGreedy;
+    if greedy && fair && variant.budget {
+        return Err("fair greedy does not keep to --budget".to_string());
+    }
+    if greedy && prerequisites && (fair || variant.budget) {
+        return Err("precedence greedy does not keep to min_coverage or --budget".to_string());
+    }
+
+    let objective_mode = match objective {
+        Objective::MaxCoverage => "",
+        Objective::SetCover => "--objective set-cover",
+        Objective::PrizeCollecting => "--objective prize-collecting",
+    };
+    let prerequisite_methods = [Method::Ilp, Method::Benders, Method::Greedy, Method::LazyGreedy];
+    let selection_methods = [Method::Ilp,
+                             Method::Benders,
+                             Method::Greedy,
+                             Method::LazyGreedy,
+                             Method::StochasticGreedy,
+                             Method::ThresholdGreedy,
+                             Method::Anneal,
+                             Method::Tabu,
+                             Method::Grasp,
+                             Method::HillClimb];
+    // each variant given: its name, the methods that support it and the flags it does not
+    let modes: &[(bool, &str, &[Method], &[&str])] =
+        &[(objective != Objective::MaxCoverage,
+           objective_mode,
+           BASIC_METHODS,
+           &["--budget", "--pool", "--polish", "--checkpoint"]),
+          (robust, "--robust", BASIC_METHODS, MODEL_FLAGS),
+          (recourse.is_some(), "--recourse", BASIC_METHODS, UNSTARTED_FLAGS),
+          (probabilistic, "probabilistic instances", BASIC_METHODS, UNSTARTED_FLAGS),
+          (capacitated, "capacities", BASIC_METHODS, UNSTARTED_FLAGS),
+          (generalized, "profits", BASIC_METHODS, UNSTARTED_FLAGS),
+          (interdict.is_some(), "--interdict", &[Method::Ilp], UNSTARTED_FLAGS),
+          (temporal, "windows", BASIC_METHODS, UNSTARTED_FLAGS),
+          (fair, "min_coverage", BASIC_METHODS, &["--polish"]),
+          (overlap.is_some(), "--overlap-penalty", BASIC_METHODS, MODEL_FLAGS),
+          (variant.budget, "--budget", BASIC_METHODS, &["--polish"]),
+          (prerequisites, "prerequisites", &prerequisite_methods, &["--polish"]),
+          (selection, "constraints on the selection", &selection_methods, &[])];
+    let given = [("--budget", variant.budget),
+                 ("--pool", variant.pool),
+                 ("--polish", variant.polish),
+                 ("--lazy", variant.lazy),
+                 ("--symmetry", variant.symmetry),
+                 ("--warm-start", variant.warm_start),
+                 ("--checkpoint", variant.checkpoint)];
+    for &(on, mode, methods, unsupported) in modes {
+        if on {
+            check_mode(mode, method, methods, unsupported, &given)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn instance() -> Instance {
+        let sets = vec![vec![0, 1], vec![1, 2]];
+        Instance::new((0..3).collect(),
+                      sets.into_iter().map(|s| s.into_iter().collect()).collect())
+    }
+
+    #[test]
+    fn plain_max_coverage_takes_any_method() {
+        let inst = instance();
+        for &method in &[Method::Ilp, Method::Anneal, Method::BranchAndBound] {
+            assert!(check(&inst, &Variant::default(), method).is_ok());
+        }
+    }
+
+    #[test]
+    fn variants_limit_methods_and_flags() {
+        let inst = instance();
+        let robust = Variant { robust: true, ..Default::default() };
+        assert!(check(&inst, &robust, Method::Ilp).is_ok());
+        assert!(check(&inst, &robust, Method::Anneal).is_err());
+        let pooled = Variant { pool: true, ..robust.clone() };
+        assert_eq!(check(&inst, &pooled, Method::Ilp),
+                   Err("--pool is not supported with --robust".to_string()));
+        let cover = Variant { objective: Objective::SetCover, robust: true, ..Default::default() };
+        assert!(check(&inst, &cover, Method::Ilp).is_err());
+    }
+
+    #[test]
+    fn instance_variants_conflict() {
+        let mut inst = instance();
+        inst.capacities = Some(vec![1, 1]);
+        let recourse = Variant { recourse: Some(1), ..Default::default() };
+        assert!(check(&inst, &recourse, Method::Ilp).is_err());
+        assert!(check(&inst, &Variant::default(), Method::Ilp).is_ok());
+        let overlap = Variant { overlap: Some(-1.0), ..Default::default() };
+        assert!(check(&instance(), &overlap, Method::Ilp).is_err());
+    }
+}
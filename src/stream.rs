@@ -0,0 +1,184 @@
+//! One-pass max k-coverage over a stream of sets.
+//!
+//! The stream format has one set per line, given as whitespace-separated element ids. Blank
+//! lines denote empty sets and lines starting with `#` are ignored. Sets are numbered by
+//! their order in the stream.
+
+use std::collections::{BTreeMap, HashSet};
+use std::io::{self, BufRead, Write};
+use {Instance, Solution};
+
+/// Writes the sets of `inst` in the stream format.
+pub fn write_sets<W: Write>(inst: &Instance, out: &mut W) -> io::Result<()> {
+    for set in &inst.sets {
+        let line = set.iter().map(|e| e.to_string()).collect::<Vec<_>>().join(" ");
+        writeln!(out, "{}", line)?;
+    }
+    Ok(())
+}
+
+/// Iterator over the sets of a stream, reading one line at a time.
+pub struct SetReader<R> {
+    lines: io::Lines<R>,
+}
+
+impl<R: BufRead> SetReader<R> {
+    pub fn new(reader: R) -> SetReader<R> {
+        SetReader { lines: reader.lines() }
+    }
+}
+
+impl<R: BufRead> Iterator for SetReader<R> {
+    type Item = Result<Vec<usize>, String>;
+
+    fn next(&mut self) -> Option<Result<Vec<usize>, String>> {
+        loop {
+            let line = match self.lines.next() {
+                Some(Ok(line)) => line,
+                Some(Err(e)) => return Some(Err(e.to_string())),
+                None => return None,
+            };
+            if line.trim().starts_with('#') {
+                continue;
+            }
+            let set = line.split_whitespace()
+                .map(|tok| tok.parse::<usize>().map_err(|e| format!("bad element {}: {}", tok, e)))
+                .collect::<Result<Vec<_>, _>>();
+            return Some(set.map(|mut set| {
+                set.sort();
+                set.dedup();
+                set
+            }));
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct Candidate {
+    sets: Vec<usize>,
+    covered: HashSet<usize>,
+}
+
+/// Sieve-streaming (Badanidiyuru et al., 2014). Keeps one candidate solution per guess
+/// `v = (1 + epsilon)^i` of the optimum in `[m, 2km]`, where `m` is the largest set seen so
+/// far, and adds a set to a candidate when its gain is at least `(v / 2 - f(S)) / (k - |S|)`.
+/// The best candidate is a (1/2 - epsilon)-approximation, using memory for
+/// O(k log(k) / epsilon) candidate sets' coverage.
+pub struct Sieve {
+    k: usize,
+    epsilon: f64,
+    max_single: f64,
+    seen: usize,
+    sieves: BTreeMap<i64, Candidate>,
+}
+
+impl Sieve {
+    pub fn new(k: usize, epsilon: f64) -> Sieve {
+        assert!(epsilon > 0.0, "epsilon must be positive");
+        Sieve {
+            k: k,
+            epsilon: epsilon,
+            max_single: 0.0,
+            seen: 0,
+            sieves: BTreeMap::new(),
+        }
+    }
+
+    /// Processes the next set of the stream. `set` must be sorted and free of duplicates.
+    pub fn push(&mut self, set: &[usize]) {
+        let id = self.seen;
+        self.seen += 1;
+
+        if self.k == 0 || set.is_empty() {
+            return;
+        }
+
+        let size = set.len() as f64;
+        if size > self.max_single {
+            self.max_single = size;
+            let base = (1.0 + self.epsilon).ln();
+            let lo = (self.max_single.ln() / base).ceil() as i64;
+            let hi = ((2.0 * self.k as f64 * self.max_single).ln() / base).floor() as i64;
+            let stale = self.sieves.keys().cloned().filter(|&i| i < lo).collect::<Vec<_>>();
+            for i in stale {
+                self.sieves.remove(&i);
+            }
+            for i in lo..hi + 1 {
+                self.sieves.entry(i).or_default();
+            }
+        }
+
+        let k = self.k;
+        for (&i, cand) in self.sieves.iter_mut() {
+            if cand.sets.len() >= k {
+                continue;
+            }
+            let v = (1.0 + self.epsilon).powi(i as i32);
+            let value = cand.covered.len() as f64;
+            let gain = set.iter().filter(|e| !cand.covered.contains(e)).count() as f64;
+            if gain > 0.0 && gain >= (v / 2.0 - value) / (k - cand.sets.len()) as f64 {
+                cand.sets.push(id);
+                cand.covered.extend(set.iter().cloned());
+            }
+        }
+    }
+
+    /// The best candidate solution for the sets seen so far.
+    pub fn best(&self) -> Solution {
+        self.sieves
+            .values()
+            .max_by_key(|cand| cand.covered.len())
+            .map(|cand| Solution::new(cand.covered.len() as f64, cand.sets.clone()))
+            .unwrap_or_else(|| Solution::new(0.0, Vec::new()))
+    }
+}
+
+/// Runs sieve-streaming over every set of `reader`.
+pub fn sieve_stream<R: BufRead>(reader: R, k: usize, epsilon: f64) -> Result<Solution, String> {
+    let mut sieve = Sieve::new(k, epsilon);
+    for set in SetReader::new(reader) {
+        sieve.push(&set?);
+    }
+    Ok(sieve.best())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sieve_is_half_optimal() {
+        let stream = vec![vec![0, 1, 2], vec![2, 3], vec![0], vec![3, 4, 5], vec![1, 4]];
+        let (k, epsilon) = (2, 0.1);
+        let mut sieve = Sieve::new(k, epsilon);
+        for set in &stream {
+            sieve.push(set);
+        }
+        let best = sieve.best();
+        assert!(best.sol.len() <= k);
+        let covered = best.sol
+            .iter()
+            .flat_map(|&i| stream[i].iter().cloned())
+            .collect::<HashSet<_>>();
+        assert_eq!(best.objective, covered.len() as f64);
+        // the first and fourth sets cover all 6 elements
+        assert!(best.objective >= (0.5 - epsilon) * 6.0);
+    }
+
+    #[test]
+    fn sieve_numbers_every_set() {
+        let mut sieve = Sieve::new(1, 0.1);
+        sieve.push(&[]);
+        sieve.push(&[0, 1]);
+        let best = sieve.best();
+        assert_eq!(best.sol, vec![1]);
+        assert_eq!(best.objective, 2.0);
+    }
+
+    #[test]
+    fn sieve_selects_nothing_for_k_zero() {
+        let mut sieve = Sieve::new(0, 0.1);
+        sieve.push(&[0, 1]);
+        assert!(sieve.best().sol.is_empty());
+    }
+}
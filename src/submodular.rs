@@ -0,0 +1,93 @@
+//! Monotone submodular maximization over a ground set of items, of which max coverage, with
+//! the sets as items, is the special case `CoverageFn`. The greedy heuristics `greedy_fn`,
+//! `lazy_greedy_fn` and `stochastic_greedy_fn` work with any `SubmodularFn`.
+
+use coverage::{Coverage, Index};
+
+/// A monotone submodular function together with a selection of its items that grows one
+/// item at a time, as the greedy heuristics need it. The guarantees of the heuristics hold
+/// as long as `gain` never grows with the selection; they only rely on `value` for the
+/// objective they report.
+pub trait SubmodularFn {
+    /// Number of items, which are `0..num_items()`.
+    fn num_items(&self) -> usize;
+
+    /// Value of the current selection.
+    fn value(&self) -> f64;
+
+    /// Marginal gain of adding `item` to the selection.
+    fn gain(&self, item: usize) -> f64;
+
+    /// Adds `item`, which is not yet selected, to the selection.
+    fn add(&mut self, item: usize);
+
+    fn contains(&self, item: usize) -> bool;
+
+    /// Whether `item` may be added to the selection as well. Adding items must never make
+    /// another item allowed. Every item is by default.
+    fn allows(&self, _item: usize) -> bool {
+        true
+    }
+
+    /// The selected items, in increasing order.
+    fn selection(&self) -> Vec<usize> {
+        (0..self.num_items()).filter(|&i| self.contains(i)).collect()
+    }
+}
+
+/// Coverage, with the sets as items: its value is the weight covered, and its gains are
+/// `Coverage::demand_gain`, which is the marginal gain without demands, and it keeps to the
+/// instance's constraints on the selection through `Coverage::allows`.
+#[derive(Debug, Clone)]
+pub struct CoverageFn<'a> {
+    cov: Coverage<'a>,
+}
+
+impl<'a> CoverageFn<'a> {
+    pub fn new(cov: Coverage<'a>) -> CoverageFn<'a> {
+        CoverageFn { cov: cov }
+    }
+
+    /// Coverage of the instance's required sets, which the heuristics start from.
+    pub fn initial(index: &'a Index) -> CoverageFn<'a> {
+        CoverageFn::new(Coverage::initial(index))
+    }
+
+    pub fn coverage(&self) -> &Coverage<'a> {
+        &self.cov
+    }
+
+    pub fn into_coverage(self) -> Coverage<'a> {
+        self.cov
+    }
+}
+
+impl<'a> SubmodularFn for CoverageFn<'a> {
+    fn num_items(&self) -> usize {
+        self.cov.index().num_sets()
+    }
+
+    fn value(&self) -> f64 {
+        self.cov.value()
+    }
+
+    fn gain(&self, item: usize) -> f64 {
+        self.cov.demand_gain(item)
+    }
+
+    fn add(&mut self, item: usize) {
+        self.cov.add(item)
+    }
+
+    fn contains(&self, item: usize) -> bool {
+        self.cov.contains(item)
+    }
+
+    fn allows(&self, item: usize) -> bool {
+        self.cov.allows(item)
+    }
+
+    fn selection(&self) -> Vec<usize> {
+        self.cov.selection()
+    }
+}
@@ -0,0 +1,240 @@
+//! Families of generated instances, over grids of generator parameters.
+//!
+//! A suite is configured in TOML, with a list of values for each parameter of `Generator`
+//! that it varies, and one instance is generated for every combination of them, as in
+//!
+//! ```toml
+//! elements = [1000, 10000]
+//! sets = [100, 500]
+//! density = [0.01, 0.05]
+//! seeds = [1, 2, 3]
+//! ```
+//!
+//! `elements`, `sets` and `seeds` are needed; the other parameters are left unset when
+//! missing or empty. A suite varies at most one family of instances, of `density`,
+//! `power_law`, `radius`, `communities` and `nested`; graph, grid, interval and adversarial
+//! instances are not generated by suites. A suite with a combination of parameters that
+//! `Generator::validate` rejects fails before any instance is written.
+
+use std::fs::{self, File};
+use std::path::Path;
+use serde_json;
+use {Distribution, Generator};
+
+/// The parameter grid of a suite. Each field lists the values of the parameter of
+/// `Generator` of the same name, with `seeds` those of `seed`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct Suite {
+    pub elements: Vec<usize>,
+    pub sets: Vec<usize>,
+    pub seeds: Vec<usize>,
+    #[serde(default)]
+    pub max_size: Vec<usize>,
+    #[serde(default)]
+    pub density: Vec<f64>,
+    #[serde(default)]
+    pub power_law: Vec<f64>,
+    #[serde(default)]
+    pub zipf: Vec<f64>,
+    #[serde(default)]
+    pub radius: Vec<f64>,
+    #[serde(default)]
+    pub communities: Vec<usize>,
+    #[serde(default)]
+    pub mixing: Vec<f64>,
+    #[serde(default)]
+    pub nested: Vec<f64>,
+    #[serde(default)]
+    pub planted: Vec<usize>,
+    #[serde(default)]
+    pub weights: Vec<Distribution>,
+    #[serde(default)]
+    pub costs: Vec<Distribution>,
+    #[serde(default)]
+    pub scenarios: Vec<usize>,
+    #[serde(default)]
+    pub presence: Vec<f64>,
+}
+
+/// An instance of a suite, as it is listed in the manifest.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Entry {
+    /// The instance's file name, in the output directory.
+    pub file: String,
+    /// The file name of the planted solution, for instances that have one.
+    pub planted: Option<String>,
+    pub generator: Generator,
+}
+
+/// Every generator in `gens` with each of `values` set by `set`, or `gens` without values.
+fn expand<T, F>(gens: Vec<Generator>, values: &[T], set: F) -> Vec<Generator>
+    where T: Clone,
+          F: Fn(&mut Generator, T)
+{
+    if values.is_empty() {
+        return gens;
+    }
+    let mut out = Vec::new();
+    for gen in gens {
+        for value in values {
+            let mut gen = gen.clone();
+            set(&mut gen, value.clone());
+            out.push(gen);
+        }
+    }
+    out
+}
+
+impl Suite {
+    /// Reads a suite from TOML.
+    pub fn parse(config: &str) -> Result<Suite, String> {
+        ::toml::from_str(config).map_err(|e| e.to_string())
+    }
+
+    /// The generator of each instance of the suite, with the seeds varying fastest, or an error
+    /// if the suite varies more than one family or if any generator does not validate.
+    pub fn generators(&self) -> Result<Vec<Generator>, String> {
+        if self.elements.is_empty() || self.sets.is_empty() || self.seeds.is_empty() {
+            return Err("a suite needs elements, sets and seeds".to_string());
+        }
+        let families = [("density", self.density.is_empty()),
+                        ("power_law", self.power_law.is_empty()),
+                        ("radius", self.radius.is_empty()),
+                        ("communities", self.communities.is_empty()),
+                        ("nested", self.nested.is_empty())];
+        let chosen = families.iter()
+            .filter(|&&(_, empty)| !empty)
+            .map(|&(name, _)| name)
+            .collect::<Vec<_>>();
+        if chosen.len() > 1 {
+            return Err(format!("a suite varies one family of instances, not both {} and {}",
+                               chosen[0],
+                               chosen[1]));
+        }
+        let base = Generator {
+            seed: 0,
+            elements: 0,
+            sets: 0,
+            max_size: None,
+            density: None,
+            power_law: None,
+            zipf: None,
+            radius: None,
+            grid: None,
+            sensor: None,
+            intervals: None,
+            communities: None,
+            mixing: None,
+            nested: None,
+            graph: None,
+            degree: None,
+            planted: None,
+            adversarial: None,
+            weights: None,
+            costs: None,
+            scenarios: None,
+            presence: None,
+        };
+        let gens = expand(vec![base], &self.elements, |g, n| g.elements = n);
+        let gens = expand(gens, &self.sets, |g, m| g.sets = m);
+        let gens = expand(gens, &self.max_size, |g, s| g.max_size = Some(s));
+        let gens = expand(gens, &self.density, |g, p| g.density = Some(p));
+        let gens = expand(gens, &self.power_law, |g, a| g.power_law = Some(a));
+        let gens = expand(gens, &self.zipf, |g, s| g.zipf = Some(s));
+        let gens = expand(gens, &self.radius, |g, r| g.radius = Some(r));
+        let gens = expand(gens, &self.communities, |g, c| g.communities = Some(c));
+        let gens = expand(gens, &self.mixing, |g, p| g.mixing = Some(p));
+        let gens = expand(gens, &self.nested, |g, p| g.nested = Some(p));
+        let gens = expand(gens, &self.planted, |g, k| g.planted = Some(k));
+        let gens = expand(gens, &self.weights, |g, d| g.weights = Some(d));
+        let gens = expand(gens, &self.costs, |g, d| g.costs = Some(d));
+        let gens = expand(gens, &self.scenarios, |g, n| g.scenarios = Some(n));
+        let gens = expand(gens, &self.presence, |g, p| g.presence = Some(p));
+        let gens = expand(gens, &self.seeds, |g, seed| g.seed = seed);
+        for gen in &gens {
+            gen.validate()
+                .map_err(|e| {
+                    format!("bad instance with {} elements and {} sets: {}",
+                            gen.elements,
+                            gen.sets,
+                            e)
+                })?;
+        }
+        Ok(gens)
+    }
+
+    /// Generates every instance of the suite into `outdir`, which is created if need be, as
+    /// `instance-<i>.json` for the `i`th generator, with planted solutions alongside as
+    /// `instance-<i>.planted.json`, and writes the manifest of them to `manifest.json`,
+    /// which it also returns. Nothing is written if the suite is not valid.
+    pub fn generate<P: AsRef<Path>>(&self, outdir: P) -> Result<Vec<Entry>, String> {
+        let gens = self.generators()?;
+        let outdir = outdir.as_ref();
+        fs::create_dir_all(outdir).map_err(|e| e.to_string())?;
+        let mut manifest = Vec::new();
+        for (i, gen) in gens.into_iter().enumerate() {
+            let (inst, planted) = gen.generate();
+            let file = format!("instance-{:04}.json", i);
+            let path = outdir.join(&file);
+            ::write(&inst, path.to_str().unwrap()).map_err(|e| e.to_string())?;
+            let planted = match planted {
+                Some(sol) => {
+                    let file = format!("instance-{:04}.planted.json", i);
+                    let mut f = File::create(outdir.join(&file)).map_err(|e| e.to_string())?;
+                    serde_json::to_writer_pretty(&mut f, &sol).map_err(|e| e.to_string())?;
+                    Some(file)
+                }
+                None => None,
+            };
+            manifest.push(Entry {
+                file: file,
+                planted: planted,
+                generator: gen,
+            });
+        }
+        let mut f = File::create(outdir.join("manifest.json")).map_err(|e| e.to_string())?;
+        serde_json::to_writer_pretty(&mut f, &manifest).map_err(|e| e.to_string())?;
+        Ok(manifest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn suite() -> Suite {
+        Suite {
+            elements: vec![100, 200],
+            sets: vec![10, 20, 30],
+            seeds: vec![1, 2],
+            density: vec![0.1, 0.2],
+            ..Suite::default()
+        }
+    }
+
+    #[test]
+    fn generators_cover_the_grid() {
+        let gens = suite().generators().unwrap();
+        assert_eq!(gens.len(), 2 * 3 * 2 * 2);
+        assert_eq!((gens[0].seed, gens[1].seed), (1, 2));
+        assert_eq!(gens[0].density, gens[1].density);
+        assert_eq!(gens[0].density, Some(0.1));
+        assert_eq!(gens[2].density, Some(0.2));
+        assert_eq!((gens[0].elements, gens[0].sets), (100, 10));
+        assert_eq!((gens[23].elements, gens[23].sets), (200, 30));
+    }
+
+    #[test]
+    fn generators_skip_empty_parameters() {
+        let gens = Suite { density: Vec::new(), ..suite() }.generators().unwrap();
+        assert_eq!(gens.len(), 2 * 3 * 2);
+        assert!(gens.iter().all(|gen| gen.density.is_none()));
+    }
+
+    #[test]
+    fn generators_reject_bad_suites() {
+        assert!(Suite { seeds: Vec::new(), ..suite() }.generators().is_err());
+        assert!(Suite { nested: vec![0.5], ..suite() }.generators().is_err());
+        assert!(Suite { density: vec![0.1, 1.5], ..suite() }.generators().is_err());
+    }
+}
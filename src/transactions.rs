@@ -0,0 +1,135 @@
+//! Covering instances of transaction data.
+//!
+//! Transactions are read as CSV, with one (set, element) pair per row, given by their labels
+//! in two of its columns; the other columns are ignored. Fields may be quoted with `"`, with
+//! `""` for a quote inside them. Blank lines are ignored.
+
+use std::collections::{BTreeSet, HashMap};
+use std::io::BufRead;
+use Instance;
+
+/// Splits a CSV row into its fields.
+fn fields(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(field.split_off(0)),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+/// Numbers labels in the order they are first seen.
+#[derive(Debug, Clone, Default)]
+struct Interner {
+    ids: HashMap<String, usize>,
+    names: Vec<String>,
+}
+
+impl Interner {
+    fn intern(&mut self, name: &str) -> usize {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+        let id = self.names.len();
+        self.ids.insert(name.to_string(), id);
+        self.names.push(name.to_string());
+        id
+    }
+}
+
+/// Reads the covering instance of transactions in CSV, whose sets are the distinct labels in
+/// column `set_col` and whose elements those in column `element_col`, each set containing
+/// the elements it is paired with. Columns are given by their name in the header, the first
+/// row, or by their number from 0; without `header` the first row is data, and columns can
+/// only be given by number. Sets and elements are numbered in the order they first appear,
+/// with their labels kept in `set_names` and `element_names`.
+pub fn read<R: BufRead>(reader: R,
+                        set_col: &str,
+                        element_col: &str,
+                        header: bool)
+                        -> Result<Instance, String> {
+    let mut lines = reader.lines();
+    let names = if header {
+        match lines.next() {
+            Some(line) => fields(&line.map_err(|e| e.to_string())?),
+            None => return Err("the CSV has no header".to_string()),
+        }
+    } else {
+        Vec::new()
+    };
+    let column = |col: &str| {
+        names.iter()
+            .position(|name| name.trim() == col)
+            .or_else(|| col.parse().ok())
+            .ok_or_else(|| format!("no column {}", col))
+    };
+    let (set_col, element_col) = (column(set_col)?, column(element_col)?);
+
+    let mut sets = Interner::default();
+    let mut elements = Interner::default();
+    let mut members: Vec<BTreeSet<usize>> = Vec::new();
+    for line in lines {
+        let line = line.map_err(|e| e.to_string())?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let row = fields(&line);
+        if set_col >= row.len() || element_col >= row.len() {
+            return Err(format!("row has too few columns: {}", line));
+        }
+        let i = sets.intern(row[set_col].trim());
+        let x = elements.intern(row[element_col].trim());
+        if i == members.len() {
+            members.push(BTreeSet::new());
+        }
+        members[i].insert(x);
+    }
+
+    let ground = (0..elements.names.len()).collect();
+    Ok(Instance {
+        set_names: Some(sets.names),
+        element_names: Some(elements.names),
+        ..Instance::new(ground, members)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_quoted_fields() {
+        assert_eq!(fields(r#"a,"b, c","say ""hi""",d"#),
+                   vec!["a", "b, c", "say \"hi\"", "d"]);
+    }
+
+    #[test]
+    fn reads_sets_by_column_name() {
+        let csv = "id,customer,item\n1,ann,tea\n2,bob,\"tea\"\n\n3,ann,jam\n4,ann,tea\n";
+        let inst = read(csv.as_bytes(), "customer", "item", true).unwrap();
+        assert_eq!(inst.set_names, Some(vec!["ann".to_string(), "bob".to_string()]));
+        assert_eq!(inst.element_names, Some(vec!["tea".to_string(), "jam".to_string()]));
+        assert_eq!(inst.sets[0], vec![0, 1].into_iter().collect());
+        assert_eq!(inst.sets[1], vec![0].into_iter().collect());
+    }
+
+    #[test]
+    fn reads_columns_by_number_without_a_header() {
+        let inst = read("x,1\ny,2\nx,2\n".as_bytes(), "0", "1", false).unwrap();
+        assert_eq!(inst.sets.len(), 2);
+        assert_eq!(inst.sets[0], vec![0, 1].into_iter().collect());
+        assert!(read("x,1\ny\n".as_bytes(), "0", "1", false).is_err());
+        assert!(read("a,b\n".as_bytes(), "c", "b", true).is_err());
+    }
+}